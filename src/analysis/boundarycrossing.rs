@@ -0,0 +1,77 @@
+use std::fmt;
+use crate::dimensions::asset::{Asset,AssetEnvironmentProperties};
+use crate::dimensions::dataflow::DataFlow;
+use crate::dimensions::securityproperty::{SecurityProperty,QualitativeValue};
+use crate::dimensions::trustboundary::TrustBoundary;
+
+#[derive(Clone,PartialEq,Debug)]
+pub struct BoundaryCrossing {
+  pub data_flow : String,
+  pub environment : String,
+  pub trust_boundaries : Vec<String>,
+  pub properties : Vec<(SecurityProperty,QualitativeValue)>
+}
+
+impl fmt::Display for BoundaryCrossing {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let props = &self.properties.iter().map(|(p,v)| format!("{:?}: {:?}",p,v)).collect::<Vec<String>>().join(",");
+    write!(f,"Data flow: {}, Environment: {}, Trust boundaries: {}, Properties: {}",self.data_flow,self.environment,self.trust_boundaries.join(","),props)
+  }
+}
+
+pub fn boundary_crossings(flows : &[DataFlow], boundaries : &[TrustBoundary], assets : &[Asset]) -> Vec<BoundaryCrossing> {
+  let mut crossings = Vec::<BoundaryCrossing>::new();
+  for df in flows {
+    let tbs : Vec<String> = boundaries.iter().filter(|tb| tb.crossed_by(df)).map(|tb| tb.name().to_string()).collect();
+    if tbs.is_empty() {
+      continue;
+    }
+
+    let mut props : Vec<(SecurityProperty,QualitativeValue)> = AssetEnvironmentProperties::new(df.environment()).properties().iter().map(|p| (p.name.clone(),p.value.clone())).collect();
+    for a in assets.iter().filter(|a| df.assets.iter().any(|n| n == a.name())) {
+      if let Some(aep) = a.environment_properties.get(df.environment()) {
+        for (idx,p) in aep.properties().iter().enumerate() {
+          if p.value > props[idx].1 {
+            props[idx].1 = p.value.clone();
+          }
+        }
+      }
+    }
+    crossings.push(BoundaryCrossing { data_flow: df.name().to_string(), environment: df.environment().to_string(), trust_boundaries: tbs, properties: props });
+  }
+  crossings
+}
+
+#[test]
+fn test_boundary_crossings() {
+  let mut data = Asset::new("Data","DAT","Information",false);
+  data.add_environment("Default");
+  data.update_security_property("Default","confidentiality","High","TBC");
+  data.update_security_property("Default","integrity","Low","TBC");
+  let mut creds = Asset::new("Credentials","CRD","Information",false);
+  creds.add_environment("Default");
+  creds.update_security_property("Default","integrity","Medium","TBC");
+
+  let mut upload = DataFlow::new("Upload","Default","Researcher","entity","Upload data","process");
+  upload.assets.push("Data".to_string());
+  upload.assets.push("Credentials".to_string());
+  let mut store = DataFlow::new("Store","Default","Upload data","process","Repository","datastore");
+  store.assets.push("Data".to_string());
+  let mut night_upload = DataFlow::new("Upload","Night","Researcher","entity","Upload data","process");
+  night_upload.assets.push("Data".to_string());
+
+  let mut tb = TrustBoundary::new("Server","Network");
+  let mut tbe = crate::dimensions::trustboundary::TrustBoundaryEnvironment::new("Default","Authenticated");
+  tbe.add_component("process","Upload data");
+  tbe.add_component("datastore","Repository");
+  tb.environments.push(tbe);
+
+  let crossings = boundary_crossings(&[upload,store,night_upload],&[tb],&[data,creds]);
+  assert_eq!(crossings.len(),1);
+  assert_eq!(crossings[0].data_flow,"Upload".to_string());
+  assert_eq!(crossings[0].environment,"Default".to_string());
+  assert_eq!(crossings[0].trust_boundaries,vec!["Server".to_string()]);
+  assert_eq!(crossings[0].properties[0],(SecurityProperty::Confidentiality,QualitativeValue::High));
+  assert_eq!(crossings[0].properties[1],(SecurityProperty::Integrity,QualitativeValue::Medium));
+  assert_eq!(crossings[0].properties[2],(SecurityProperty::Availability,QualitativeValue::None));
+}
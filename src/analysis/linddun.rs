@@ -0,0 +1,148 @@
+use std::fmt;
+use crate::dimensions::asset::Asset;
+use crate::dimensions::role::{Role,RoleType};
+use crate::dimensions::securityproperty::{SecurityProperty,SecurityPropertyValue,QualitativeValue};
+
+#[derive(Clone,PartialEq,Debug)]
+pub enum LinddunCategory {
+  Linkability,
+  Identifiability,
+  NonRepudiation,
+  Detectability,
+  Disclosure,
+  Unawareness,
+  NonCompliance
+}
+
+impl LinddunCategory {
+  pub fn all() -> Vec<LinddunCategory> {
+    vec![LinddunCategory::Linkability,LinddunCategory::Identifiability,LinddunCategory::NonRepudiation,LinddunCategory::Detectability,LinddunCategory::Disclosure,LinddunCategory::Unawareness,LinddunCategory::NonCompliance]
+  }
+
+  pub fn property(&self) -> SecurityProperty {
+    match self {
+      LinddunCategory::Linkability => SecurityProperty::Unlinkability,
+      LinddunCategory::Identifiability => SecurityProperty::Anonymity,
+      LinddunCategory::NonRepudiation => SecurityProperty::Pseudonymity,
+      LinddunCategory::Detectability => SecurityProperty::Unobservability,
+      LinddunCategory::Disclosure => SecurityProperty::Confidentiality,
+      LinddunCategory::Unawareness => SecurityProperty::Accountability,
+      LinddunCategory::NonCompliance => SecurityProperty::Accountability
+    }
+  }
+
+  pub fn role_type(&self) -> RoleType {
+    match self {
+      LinddunCategory::Disclosure => RoleType::DataProcessor,
+      LinddunCategory::NonCompliance => RoleType::DataController,
+      _ => RoleType::DataSubject
+    }
+  }
+}
+
+impl fmt::Display for LinddunCategory {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      LinddunCategory::Linkability => write!(f,"Linkability"),
+      LinddunCategory::Identifiability => write!(f,"Identifiability"),
+      LinddunCategory::NonRepudiation => write!(f,"Non-repudiation"),
+      LinddunCategory::Detectability => write!(f,"Detectability"),
+      LinddunCategory::Disclosure => write!(f,"Disclosure of information"),
+      LinddunCategory::Unawareness => write!(f,"Unawareness"),
+      LinddunCategory::NonCompliance => write!(f,"Non-compliance")
+    }
+  }
+}
+
+#[derive(Clone,PartialEq,Debug)]
+pub struct PrivacyThreat {
+  pub name : String,
+  pub category : LinddunCategory,
+  pub environment : String,
+  pub asset : String,
+  pub property : SecurityProperty,
+  pub value : QualitativeValue,
+  pub role_type : RoleType,
+  pub roles : Vec<String>
+}
+
+impl fmt::Display for PrivacyThreat {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f,"Name: {}, Category: {}, Environment: {}, Asset: {}, Property: {:?} ({:?}), Role type: {}, Roles: {}",self.name,self.category,self.environment,self.asset,self.property,self.value,self.role_type,self.roles.join(","))
+  }
+}
+
+pub fn is_privacy_property(p : &SecurityProperty) -> bool {
+  matches!(p,SecurityProperty::Anonymity | SecurityProperty::Pseudonymity | SecurityProperty::Unlinkability | SecurityProperty::Unobservability)
+}
+
+pub fn is_personal_data(props : &[SecurityPropertyValue]) -> bool {
+  props.iter().any(|p| is_privacy_property(&p.name) && p.value != QualitativeValue::None)
+}
+
+pub fn linddun_threats(assets : &[Asset], roles : &[Role]) -> Vec<PrivacyThreat> {
+  let mut threats = Vec::<PrivacyThreat>::new();
+  for cat in LinddunCategory::all() {
+    let prop = cat.property();
+    let role_type = cat.role_type();
+    let cat_roles : Vec<String> = roles.iter().filter(|r| *r.role_type() == role_type).map(|r| r.name().to_string()).collect();
+    for a in assets {
+      let mut env_names : Vec<&String> = a.environment_properties.keys().collect();
+      env_names.sort();
+      for env_name in env_names {
+        let props = a.environment_properties[env_name].properties();
+        let p = &props[prop.clone() as usize];
+        if p.value == QualitativeValue::None || !is_personal_data(props) {
+          continue;
+        }
+        threats.push(PrivacyThreat {
+          name: format!("{} of {}",cat,a.name()),
+          category: cat.clone(),
+          environment: env_name.clone(),
+          asset: a.name().to_string(),
+          property: prop.clone(),
+          value: p.value.clone(),
+          role_type: role_type.clone(),
+          roles: cat_roles.clone()
+        });
+      }
+    }
+  }
+  threats
+}
+
+#[test]
+fn test_linddun_threats() {
+  let mut records = Asset::new("Patient records","PR","Information",true);
+  records.add_environment("Day");
+  records.add_environment("Night");
+  records.update_security_property("Day","unlinkability","High","TBC");
+  records.update_security_property("Night","unlinkability","Medium","TBC");
+  records.update_security_property("Day","confidentiality","High","TBC");
+  let mut server = Asset::new("Server","SRV","Hardware",false);
+  server.add_environment("Day");
+  server.update_security_property("Day","availability","High","TBC");
+  let mut key = Asset::new("Signing key","SK","Information",true);
+  key.add_environment("Day");
+  key.update_security_property("Day","confidentiality","High","TBC");
+  let roles = [
+    Role::new("Patient","Data Subject","PAT","A patient"),
+    Role::new("Hospital","Data Controller","HOS","The hospital"),
+    Role::new("Cloud provider","Data Processor","CP","A cloud provider"),
+    Role::new("Nurse","Stakeholder","NUR","A nurse")
+  ];
+
+  let threats = linddun_threats(&[records,server,key],&roles);
+  assert_eq!(threats.len(),3);
+  assert_eq!(threats[0].name,"Linkability of Patient records".to_string());
+  assert_eq!(threats[0].environment,"Day".to_string());
+  assert_eq!(threats[0].property,SecurityProperty::Unlinkability);
+  assert_eq!(threats[0].value,QualitativeValue::High);
+  assert_eq!(threats[0].role_type,RoleType::DataSubject);
+  assert_eq!(threats[0].roles,vec!["Patient".to_string()]);
+  assert_eq!(threats[1].environment,"Night".to_string());
+  assert_eq!(threats[1].value,QualitativeValue::Medium);
+  assert_eq!(threats[2].category,LinddunCategory::Disclosure);
+  assert_eq!(threats[2].property,SecurityProperty::Confidentiality);
+  assert_eq!(threats[2].roles,vec!["Cloud provider".to_string()]);
+}
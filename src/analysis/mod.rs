@@ -0,0 +1,5 @@
+pub mod propagation;
+pub mod boundarycrossing;
+pub mod stride;
+pub mod linddun;
+pub mod riskmatrix;
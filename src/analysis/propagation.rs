@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::fmt;
+use crate::dimensions::asset::{Asset,AssetEnvironmentProperties};
+use crate::dimensions::assetassociation::{AssetAssociation,Adornment};
+use crate::dimensions::securityproperty::{SecurityProperty,QualitativeValue};
+
+#[derive(Clone,PartialEq,Debug)]
+pub struct PropagationFinding {
+  pub environment : String,
+  pub asset : String,
+  pub property : SecurityProperty,
+  pub declared : QualitativeValue,
+  pub inherited : QualitativeValue,
+  pub source : String
+}
+
+impl fmt::Display for PropagationFinding {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f,"Environment: {}, Asset: {}, Property: {:?}, Declared: {:?}, Inherited: {:?}, Source: {}",self.environment,self.asset,self.property,self.declared,self.inherited,self.source)
+  }
+}
+
+fn part_whole(aa : &AssetAssociation) -> Option<(&String,&String)> {
+  let is_whole = |a : &Adornment| *a == Adornment::Aggregation || *a == Adornment::Composition;
+  if is_whole(&aa.head_adornment) {
+    Some((&aa.tail_asset,&aa.head_asset))
+  }
+  else if is_whole(&aa.tail_adornment) {
+    Some((&aa.head_asset,&aa.tail_asset))
+  }
+  else {
+    None
+  }
+}
+
+pub fn propagate_security_properties(assets : &[Asset], assocs : &[AssetAssociation]) -> Vec<PropagationFinding> {
+  let mut envs = Vec::<String>::new();
+  for aa in assocs {
+    if !envs.contains(&aa.environment) {
+      envs.push(aa.environment.clone());
+    }
+  }
+
+  let mut findings = Vec::<PropagationFinding>::new();
+  for env_name in &envs {
+    let declared : HashMap<&str,AssetEnvironmentProperties> = assets.iter().map(|a| {
+      let aep = match a.environment_properties.get(env_name) {
+        Some(aep) => aep.clone(),
+        None => AssetEnvironmentProperties::new(env_name)
+      };
+      (a.name(),aep)
+    }).collect();
+    let links : Vec<(&String,&String)> = assocs.iter().filter(|aa| &aa.environment == env_name).filter_map(part_whole).collect();
+
+    let mut effective : HashMap<&str,Vec<(QualitativeValue,String)>> = declared.iter().map(|(a,aep)| {
+      (*a,aep.properties().iter().map(|p| (p.value.clone(),a.to_string())).collect())
+    }).collect();
+    let mut changed = true;
+    while changed {
+      changed = false;
+      for (part,whole) in &links {
+        if !effective.contains_key(whole.as_str()) {
+          continue;
+        }
+        let part_values = match effective.get(part.as_str()) {
+          Some(pv) => pv.clone(),
+          None => continue
+        };
+        let whole_values = effective.get_mut(whole.as_str()).unwrap();
+        for (idx,pv) in part_values.into_iter().enumerate() {
+          if pv.0 > whole_values[idx].0 {
+            whole_values[idx] = pv;
+            changed = true;
+          }
+        }
+      }
+    }
+
+    for a in assets {
+      let dv = declared[a.name()].properties();
+      for (idx,(v,src)) in effective[a.name()].iter().enumerate() {
+        if *v > dv[idx].value {
+          findings.push(PropagationFinding {
+            environment: env_name.clone(),
+            asset: a.name().to_string(),
+            property: dv[idx].name.clone(),
+            declared: dv[idx].value.clone(),
+            inherited: v.clone(),
+            source: src.clone()
+          });
+        }
+      }
+    }
+  }
+  findings
+}
+
+#[test]
+fn test_propagate_security_properties() {
+  let mut server = Asset::new("Server","SRV","Hardware",false);
+  server.add_environment("Default");
+  server.update_security_property("Default","confidentiality","Low","TBC");
+  let mut data = Asset::new("Data","DAT","Information",false);
+  data.add_environment("Default");
+  data.update_security_property("Default","confidentiality","High","TBC");
+  data.update_security_property("Default","integrity","Medium","TBC");
+  let mut keys = Asset::new("Keys","KEY","Information",false);
+  keys.add_environment("Default");
+  keys.update_security_property("Default","availability","Medium","TBC");
+  let mut site = Asset::new("Site","SIT","Hardware",false);
+  site.add_environment("Default");
+  site.update_security_property("Default","confidentiality","High","TBC");
+  site.update_security_property("Default","integrity","High","TBC");
+  site.update_security_property("Default","availability","High","TBC");
+
+  let mut server_data = AssetAssociation::new("Default","Server","Data");
+  server_data.head_adornment = Adornment::Composition;
+  let mut keys_data = AssetAssociation::new("Default","Data","Keys");
+  keys_data.head_adornment = Adornment::Aggregation;
+  let mut site_server = AssetAssociation::new("Default","Server","Site");
+  site_server.tail_adornment = Adornment::Aggregation;
+  let server_keys = AssetAssociation::new("Default","Server","Keys");
+
+  let findings = propagate_security_properties(&[server,data,keys,site],&[server_data,keys_data,site_server,server_keys]);
+  assert_eq!(findings.len(),4);
+  assert_eq!(findings[0],PropagationFinding { environment: "Default".to_string(), asset: "Server".to_string(), property: SecurityProperty::Confidentiality, declared: QualitativeValue::Low, inherited: QualitativeValue::High, source: "Data".to_string() });
+  assert_eq!(findings[1].property,SecurityProperty::Integrity);
+  assert_eq!(findings[1].inherited,QualitativeValue::Medium);
+  assert_eq!(findings[2].asset,"Server".to_string());
+  assert_eq!(findings[2].property,SecurityProperty::Availability);
+  assert_eq!(findings[2].source,"Keys".to_string());
+  assert_eq!(findings[3].asset,"Data".to_string());
+  assert_eq!(findings[3].property,SecurityProperty::Availability);
+}
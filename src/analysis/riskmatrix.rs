@@ -0,0 +1,121 @@
+use serde_json::json;
+use crate::dimensions::risk::Risk;
+use crate::dimensions::threat::{Threat,Likelihood};
+use crate::dimensions::vulnerability::{Vulnerability,Severity};
+
+const LIKELIHOODS : [Likelihood; 6] = [Likelihood::Incredible,Likelihood::Improbable,Likelihood::Remote,Likelihood::Occasional,Likelihood::Probable,Likelihood::Frequent];
+const SEVERITIES : [Severity; 4] = [Severity::Negligible,Severity::Marginal,Severity::Critical,Severity::Catastrophic];
+
+#[derive(Clone)]
+pub struct RiskMatrix {
+  pub environment : String,
+  pub cells : Vec<Vec<Vec<String>>>
+}
+
+impl RiskMatrix {
+  pub fn new(env_name : &str, risks : &[Risk], threats : &[Threat], vuls : &[Vulnerability]) -> RiskMatrix {
+    let mut rm = RiskMatrix { environment: env_name.to_string(), cells: vec![vec![Vec::<String>::new(); SEVERITIES.len()]; LIKELIHOODS.len()] };
+    for r in risks {
+      let lhood = threats.iter().filter(|t| t.name() == r.threat).flat_map(|t| t.environments.iter()).find(|te| te.name() == env_name).map(|te| te.likelihood().clone());
+      let sev = vuls.iter().filter(|v| v.name() == r.vulnerability).flat_map(|v| v.environments.iter()).find(|ve| ve.name() == env_name).map(|ve| ve.severity().clone());
+      if let (Some(l),Some(s)) = (lhood,sev) {
+        rm.cells[l as usize][s as usize].push(r.name().to_string());
+      }
+    }
+    rm
+  }
+
+  pub fn risks(&self, lhood : Likelihood, sev : Severity) -> &[String] {
+    &self.cells[lhood as usize][sev as usize]
+  }
+
+  pub fn score(lhood : usize, sev : usize) -> usize {
+    lhood + sev
+  }
+
+  pub fn to_json(&self) -> String {
+    let mut cells = Vec::<serde_json::Value>::new();
+    for (l,row) in self.cells.iter().enumerate() {
+      for (s,names) in row.iter().enumerate() {
+        cells.push(json!({
+          "likelihood": LIKELIHOODS[l].to_string(),
+          "severity": SEVERITIES[s].to_string(),
+          "score": RiskMatrix::score(l,s),
+          "risks": names
+        }));
+      }
+    }
+    json!({
+      "environment": self.environment,
+      "likelihoods": LIKELIHOODS.iter().map(|l| l.to_string()).collect::<Vec<String>>(),
+      "severities": SEVERITIES.iter().map(|s| s.to_string()).collect::<Vec<String>>(),
+      "cells": cells
+    }).to_string()
+  }
+
+  pub fn to_svg(&self) -> String {
+    let (cell_w,cell_h,label_w,label_h) = (150,60,110,40);
+    let width = label_w + cell_w * SEVERITIES.len();
+    let height = label_h + cell_h * LIKELIHOODS.len();
+    let colours = ["#1a9850","#66bd63","#a6d96a","#fee08b","#fdae61","#f46d43","#d73027","#a50026","#67001f"];
+    let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"sans-serif\" font-size=\"11\">\n",width,height);
+    svg.push_str(format!("<title>Risk matrix: {}</title>\n",crate::report::escape_html(&self.environment)).as_str());
+    for (s,sev) in SEVERITIES.iter().enumerate() {
+      svg.push_str(format!("<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",label_w + s * cell_w + cell_w / 2,label_h / 2,sev).as_str());
+    }
+    // Most likely threats are drawn on the top row, as in the CAIRIS risk matrix.
+    for (row,l) in (0..LIKELIHOODS.len()).rev().enumerate() {
+      let y = label_h + row * cell_h;
+      svg.push_str(format!("<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n",label_w - 5,y + cell_h / 2,LIKELIHOODS[l]).as_str());
+      for s in 0..SEVERITIES.len() {
+        let x = label_w + s * cell_w;
+        svg.push_str(format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"white\"/>\n",x,y,cell_w,cell_h,colours[RiskMatrix::score(l,s)]).as_str());
+        for (idx,r) in self.cells[l][s].iter().enumerate() {
+          svg.push_str(format!("<text x=\"{}\" y=\"{}\">{}</text>\n",x + 5,y + 15 + idx * 13,crate::report::escape_html(r)).as_str());
+        }
+      }
+    }
+    svg.push_str("</svg>\n");
+    svg
+  }
+}
+
+#[test]
+fn test_new_risk_matrix() {
+  use crate::dimensions::threat::ThreatEnvironment;
+  use crate::dimensions::vulnerability::VulnerabilityEnvironment;
+
+  let mut sqli = Threat::new("SQL injection","Software");
+  sqli.environments.push(ThreatEnvironment::new("Day","Probable"));
+  sqli.environments.push(ThreatEnvironment::new("Night","Remote"));
+  let mut phishing = Threat::new("Phishing","Social");
+  phishing.environments.push(ThreatEnvironment::new("Day","Frequent"));
+  let mut unval = Vulnerability::new("Unvalidated input","Implementation");
+  unval.environments.push(VulnerabilityEnvironment::new("Day","Critical"));
+  let mut naive = Vulnerability::new("Naive users","Social");
+  naive.environments.push(VulnerabilityEnvironment::new("Day","Marginal"));
+  let risks = [
+    Risk::new("Upload risk","SQL injection","Unvalidated input"),
+    Risk::new("Search risk","SQL injection","Unvalidated input"),
+    Risk::new("Credential theft","Phishing","Naive users")
+  ];
+  let rm = RiskMatrix::new("Day",&risks,&[sqli,phishing],&[unval,naive]);
+  assert_eq!(rm.environment,"Day".to_string());
+  assert_eq!(rm.risks(Likelihood::Probable,Severity::Critical),["Upload risk".to_string(),"Search risk".to_string()]);
+  assert_eq!(rm.risks(Likelihood::Frequent,Severity::Marginal),["Credential theft".to_string()]);
+  assert_eq!(rm.risks(Likelihood::Remote,Severity::Critical).len(),0);
+
+  let v : serde_json::Value = serde_json::from_str(&rm.to_json()).unwrap();
+  assert_eq!(v["environment"],"Day");
+  assert_eq!(v["cells"].as_array().unwrap().len(),24);
+  assert_eq!(v["cells"][4 * 4 + 2]["likelihood"],"Probable");
+  assert_eq!(v["cells"][4 * 4 + 2]["severity"],"Critical");
+  assert_eq!(v["cells"][4 * 4 + 2]["score"],6);
+  assert_eq!(v["cells"][4 * 4 + 2]["risks"],json!(["Upload risk","Search risk"]));
+
+  let svg = rm.to_svg();
+  assert!(svg.starts_with("<svg"));
+  assert_eq!(svg.matches("<rect").count(),24);
+  assert!(svg.contains(">Credential theft</text>"));
+  assert!(svg.ends_with("</svg>\n"));
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+use crate::dimensions::dataflow::{DataFlow,DataFlowComponentType};
+use crate::dimensions::securityproperty::SecurityProperty;
+use crate::dimensions::trustboundary::TrustBoundary;
+
+#[derive(Clone,PartialEq,Debug)]
+pub enum StrideCategory {
+  Spoofing,
+  Tampering,
+  Repudiation,
+  InformationDisclosure,
+  DenialOfService,
+  ElevationOfPrivilege
+}
+
+impl StrideCategory {
+  pub fn properties(&self) -> Vec<SecurityProperty> {
+    match self {
+      StrideCategory::Spoofing => vec![SecurityProperty::Accountability],
+      StrideCategory::Tampering => vec![SecurityProperty::Integrity],
+      StrideCategory::Repudiation => vec![SecurityProperty::Accountability],
+      StrideCategory::InformationDisclosure => vec![SecurityProperty::Confidentiality],
+      StrideCategory::DenialOfService => vec![SecurityProperty::Availability],
+      StrideCategory::ElevationOfPrivilege => vec![SecurityProperty::Integrity]
+    }
+  }
+}
+
+impl fmt::Display for StrideCategory {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      StrideCategory::Spoofing => write!(f,"Spoofing"),
+      StrideCategory::Tampering => write!(f,"Tampering"),
+      StrideCategory::Repudiation => write!(f,"Repudiation"),
+      StrideCategory::InformationDisclosure => write!(f,"Information disclosure"),
+      StrideCategory::DenialOfService => write!(f,"Denial of service"),
+      StrideCategory::ElevationOfPrivilege => write!(f,"Elevation of privilege")
+    }
+  }
+}
+
+#[derive(Clone,PartialEq,Debug)]
+pub enum StrideElement {
+  Component(DataFlowComponentType),
+  DataFlow
+}
+
+impl StrideElement {
+  pub fn categories(&self) -> Vec<StrideCategory> {
+    match self {
+      StrideElement::Component(DataFlowComponentType::Entity) => vec![StrideCategory::Spoofing,StrideCategory::Repudiation],
+      StrideElement::Component(DataFlowComponentType::Process) => vec![StrideCategory::Spoofing,StrideCategory::Tampering,StrideCategory::Repudiation,StrideCategory::InformationDisclosure,StrideCategory::DenialOfService,StrideCategory::ElevationOfPrivilege],
+      StrideElement::Component(DataFlowComponentType::Datastore) => vec![StrideCategory::Tampering,StrideCategory::Repudiation,StrideCategory::InformationDisclosure,StrideCategory::DenialOfService],
+      StrideElement::DataFlow => vec![StrideCategory::Tampering,StrideCategory::InformationDisclosure,StrideCategory::DenialOfService]
+    }
+  }
+}
+
+impl fmt::Display for StrideElement {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      StrideElement::Component(c) => write!(f,"{}",c),
+      StrideElement::DataFlow => write!(f,"dataflow")
+    }
+  }
+}
+
+#[derive(Clone,PartialEq,Debug)]
+pub struct ThreatCandidate {
+  pub name : String,
+  pub environment : String,
+  pub element : String,
+  pub element_type : StrideElement,
+  pub category : StrideCategory,
+  pub properties : Vec<SecurityProperty>,
+  pub assets : Vec<String>
+}
+
+impl fmt::Display for ThreatCandidate {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let props = &self.properties.iter().map(|p| format!("{:?}",p)).collect::<Vec<String>>().join(",");
+    write!(f,"Name: {}, Environment: {}, Element: {} ({}), Category: {}, Properties: {}, Assets: {}",self.name,self.environment,self.element,self.element_type,self.category,props,self.assets.join(","))
+  }
+}
+
+fn candidates(env_name : &str, el_name : &str, el_type : StrideElement, assets : &[String]) -> Vec<ThreatCandidate> {
+  el_type.categories().into_iter().map(|cat| {
+    ThreatCandidate {
+      name: format!("{} of {}",cat,el_name),
+      environment: env_name.to_string(),
+      element: el_name.to_string(),
+      element_type: el_type.clone(),
+      properties: cat.properties(),
+      category: cat,
+      assets: assets.to_vec()
+    }
+  }).collect()
+}
+
+fn component_assets(flows : &[DataFlow], env_name : &str, c_type : &DataFlowComponentType, c_name : &str) -> Vec<String> {
+  let mut assets = Vec::<String>::new();
+  for df in flows.iter().filter(|df| df.environment() == env_name) {
+    if (df.source_type == *c_type && df.source_name == c_name) || (df.target_type == *c_type && df.target_name == c_name) {
+      for a in &df.assets {
+        if !assets.contains(a) {
+          assets.push(a.clone());
+        }
+      }
+    }
+  }
+  assets
+}
+
+pub fn stride_threats(flows : &[DataFlow], boundaries : &[TrustBoundary]) -> Vec<ThreatCandidate> {
+  let mut seen = Vec::<(String,DataFlowComponentType,String)>::new();
+  let mut threats = Vec::<ThreatCandidate>::new();
+  for df in flows {
+    for (c_type,c_name) in [(&df.source_type,&df.source_name),(&df.target_type,&df.target_name)] {
+      let key = (df.environment().to_string(),c_type.clone(),c_name.clone());
+      if seen.contains(&key) {
+        continue;
+      }
+      let assets = component_assets(flows,df.environment(),c_type,c_name);
+      threats.append(&mut candidates(df.environment(),c_name,StrideElement::Component(c_type.clone()),&assets));
+      seen.push(key);
+    }
+    if boundaries.iter().any(|tb| tb.crossed_by(df)) {
+      threats.append(&mut candidates(df.environment(),df.name(),StrideElement::DataFlow,&df.assets));
+    }
+  }
+  threats
+}
+
+#[test]
+fn test_stride_threats() {
+  use crate::dimensions::trustboundary::TrustBoundaryEnvironment;
+
+  let mut upload = DataFlow::new("Upload","Default","Researcher","entity","Upload data","process");
+  upload.assets.push("Data".to_string());
+  let mut store = DataFlow::new("Store","Default","Upload data","process","Repository","datastore");
+  store.assets.push("Data".to_string());
+  store.assets.push("Metadata".to_string());
+
+  let mut tb = TrustBoundary::new("Server","Network");
+  let mut tbe = TrustBoundaryEnvironment::new("Default","Authenticated");
+  tbe.add_component("process","Upload data");
+  tbe.add_component("datastore","Repository");
+  tb.environments.push(tbe);
+
+  let threats = stride_threats(&[upload,store],&[tb]);
+  assert_eq!(threats.len(),2 + 6 + 3 + 4);
+
+  assert_eq!(threats[0].name,"Spoofing of Researcher".to_string());
+  assert_eq!(threats[0].element_type,StrideElement::Component(DataFlowComponentType::Entity));
+  assert_eq!(threats[0].properties,vec![SecurityProperty::Accountability]);
+  assert_eq!(threats[0].assets,vec!["Data".to_string()]);
+
+  let process_threats : Vec<&ThreatCandidate> = threats.iter().filter(|t| t.element == "Upload data").collect();
+  assert_eq!(process_threats.len(),6);
+  assert_eq!(process_threats[5].category,StrideCategory::ElevationOfPrivilege);
+  assert_eq!(process_threats[5].properties,vec![SecurityProperty::Integrity]);
+  assert_eq!(process_threats[0].assets,vec!["Data".to_string(),"Metadata".to_string()]);
+
+  let flow_threats : Vec<&ThreatCandidate> = threats.iter().filter(|t| t.element_type == StrideElement::DataFlow).collect();
+  assert_eq!(flow_threats.len(),3);
+  assert_eq!(flow_threats[0].element,"Upload".to_string());
+  assert_eq!(flow_threats[1].category,StrideCategory::InformationDisclosure);
+  assert_eq!(flow_threats[1].properties,vec![SecurityProperty::Confidentiality]);
+}
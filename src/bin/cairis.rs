@@ -0,0 +1,453 @@
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::{self,Write};
+use std::path::Path;
+use std::process;
+use cairis_core::analysis::riskmatrix::RiskMatrix;
+use cairis_core::db::mysql::{connect_db,MySQLDatabaseProxy};
+use cairis_core::dimensions::dimension::{Dimension,find_by_name,search};
+use cairis_core::dimensions::projectsettings::ProjectSettings;
+use cairis_core::dimensions::valuetype::ValueType;
+use cairis_core::exchange::csvfile::*;
+use cairis_core::exchange::modeldir::{ModelDirectory,DIMENSION_FILES};
+use cairis_core::model::Model;
+use cairis_core::model::diff::{self,diff};
+use cairis_core::report::dpia::Dpia;
+use cairis_core::report::specification::SpecificationBuilder;
+
+const USAGE : &str = "Usage: cairis (--model DIR | --db) COMMAND [ARGS]
+
+Commands:
+  list DIMENSION                 List the objects in a dimension
+  search TERM                    Find objects by name, short code or tag
+  show DIMENSION NAME            Show a single object
+  validate                       Check the model for bad rows and dangling references
+  import DIMENSION FILE          Import objects from a CSV file
+  export DIMENSION FILE          Export objects to a CSV file
+  risk [ENVIRONMENT]             Score every risk by likelihood and severity
+  diff DIR                       Show the changes needed to turn the model into the one in DIR
+  merge BASE THEIRS              Three-way merge THEIRS into the model, using BASE as the common ancestor
+  report (spec|dpia) [--format markdown|html|docbook] [--output FILE]
+
+Dimensions: environments, roles, assets, attackers, vulnerabilities, threats, risks, value_types
+
+With --db, only environments, assets and value_types can be listed, shown, searched and exported,
+only value_types can be imported, and show settings and report spec are available. validate, risk,
+diff, merge and report dpia need --model, as the database does not store the other dimensions.
+Model directories do not store project settings, so diff and merge do not compare them.
+The database connection is configured through DB_HOST, DB_PORT, DB_USER, DB_PASSWD and DB_NAME.";
+
+const VALUE_TYPE_DIMENSIONS : [&str; 13] = ["asset_type","threat_type","vulnerability_type","severity","likelihood","capability","motivation","asset_value","threat_value","risk_class","countermeasure_value","access_right","protocol"];
+
+enum Store {
+  Model(Box<ModelDirectory>),
+  Database(MySQLDatabaseProxy)
+}
+
+const DB_DIMENSIONS : [&str; 3] = ["environments","assets","value_types"];
+
+fn db_value_types(p : &mut MySQLDatabaseProxy) -> Vec<ValueType> {
+  VALUE_TYPE_DIMENSIONS.iter().flat_map(|dim| p.get_value_types(dim,"")).collect()
+}
+
+fn db_directory(p : &mut MySQLDatabaseProxy) -> ModelDirectory {
+  let ps = p.get_project_settings();
+  let mut md = ModelDirectory::new(Path::new(&ps.name));
+  md.settings = ps;
+  md.environments = p.get_environments();
+  md.assets = p.get_assets();
+  md.value_types = db_value_types(p);
+  md
+}
+
+fn db_supported(cmd : &str, args : &[String]) -> Result<(),String> {
+  let dim = args.first().map(|s| s.as_str()).unwrap_or("");
+  let supported = match cmd {
+    "list" | "export" => DB_DIMENSIONS.contains(&dim),
+    "show" => dim == "settings" || DB_DIMENSIONS.contains(&dim),
+    "import" => dim == "value_types",
+    "search" => true,
+    "report" => dim == "spec",
+    _ => false
+  };
+  if supported {
+    Ok(())
+  }
+  else {
+    Err(format!("{} is not supported with --db",[cmd,dim].join(" ").trim()))
+  }
+}
+
+fn objects<'a>(md : &'a ModelDirectory, dim : &str) -> Result<Vec<&'a dyn Dimension>,String> {
+  fn dyns<D : Dimension>(objts : &[D]) -> Vec<&dyn Dimension> {
+    objts.iter().map(|o| o as &dyn Dimension).collect()
+  }
+  Ok(match dim {
+    "environments" => dyns(&md.environments),
+    "roles" => dyns(&md.roles),
+    "assets" => dyns(&md.assets),
+    "attackers" => dyns(&md.attackers),
+    "vulnerabilities" => dyns(&md.vulnerabilities),
+    "threats" => dyns(&md.threats),
+    "risks" => dyns(&md.risks),
+    "value_types" => dyns(&md.value_types),
+    _ => { return Err(format!("{} is not a dimension",dim)); }
+  })
+}
+
+fn describe<D : Dimension + fmt::Display>(objts : &[D], name : &str) -> Option<String> {
+  find_by_name(objts,name).map(|o| o.to_string())
+}
+
+fn show(md : &ModelDirectory, dim : &str, name : &str) -> Result<String,String> {
+  let found = match dim {
+    "environments" => describe(&md.environments,name),
+    "roles" => describe(&md.roles,name),
+    "assets" => describe(&md.assets,name),
+    "attackers" => describe(&md.attackers,name),
+    "vulnerabilities" => describe(&md.vulnerabilities,name),
+    "threats" => describe(&md.threats,name),
+    "risks" => describe(&md.risks,name),
+    "value_types" => describe(&md.value_types,name),
+    _ => { return Err(format!("{} is not a dimension",dim)); }
+  };
+  found.ok_or(format!("No {} called {}",dim,name))
+}
+
+fn export(md : &ModelDirectory, dim : &str, out : File) -> Result<(),String> {
+  let res = match dim {
+    "environments" => write_environments(out,&md.environments),
+    "roles" => write_roles(out,&md.roles),
+    "assets" => write_assets(out,&md.assets),
+    "attackers" => write_attackers(out,&md.attackers),
+    "vulnerabilities" => write_vulnerabilities(out,&md.vulnerabilities),
+    "threats" => write_threats(out,&md.threats),
+    "risks" => write_risks(out,&md.risks),
+    "value_types" => write_value_types(out,&md.value_types),
+    _ => { return Err(format!("{} is not a dimension",dim)); }
+  };
+  res.map_err(|e| e.to_string())
+}
+
+fn merge<T>(existing : &mut Vec<T>, imported : Vec<T>, key : fn(&T) -> String) {
+  for o in imported {
+    match existing.iter().position(|e| key(e) == key(&o)) {
+      Some(idx) => existing[idx] = o,
+      None => existing.push(o)
+    }
+  }
+}
+
+fn import(md : &mut ModelDirectory, dim : &str, input : File) -> Result<usize,String> {
+  if !md.errors.is_empty() {
+    let unreadable = md.errors.iter().map(|(f,e)| format!("{}: {}",f,e)).collect::<Vec<String>>().join("\n");
+    return Err(format!("Cannot import into a model with unreadable rows, as saving would drop them:\n{}",unreadable));
+  }
+  let (count,errors) = match dim {
+    "environments" => { let (o,e) = read_environments(input); let n = o.len(); merge(&mut md.environments,o,|x| x.name.clone()); (n,e) },
+    "roles" => { let (o,e) = read_roles(input); let n = o.len(); merge(&mut md.roles,o,|x| x.name().to_string()); (n,e) },
+    "assets" => { let (o,e) = read_assets(input); let n = o.len(); merge(&mut md.assets,o,|x| x.name().to_string()); (n,e) },
+    "attackers" => { let (o,e) = read_attackers(input); let n = o.len(); merge(&mut md.attackers,o,|x| x.name().to_string()); (n,e) },
+    "vulnerabilities" => { let (o,e) = read_vulnerabilities(input); let n = o.len(); merge(&mut md.vulnerabilities,o,|x| x.name().to_string()); (n,e) },
+    "threats" => { let (o,e) = read_threats(input); let n = o.len(); merge(&mut md.threats,o,|x| x.name().to_string()); (n,e) },
+    "risks" => { let (o,e) = read_risks(input); let n = o.len(); merge(&mut md.risks,o,|x| x.name().to_string()); (n,e) },
+    "value_types" => { let (o,e) = read_value_types(input); let n = o.len(); merge(&mut md.value_types,o,|x| format!("{}/{}",x.vt_type,x.name)); (n,e) },
+    _ => { return Err(format!("{} is not a dimension",dim)); }
+  };
+  if !errors.is_empty() {
+    return Err(errors.iter().map(|e| e.to_string()).collect::<Vec<String>>().join("\n"));
+  }
+  md.save().map_err(|e| e.to_string())?;
+  Ok(count)
+}
+
+fn risk_scores(md : &ModelDirectory, env_filter : Option<&String>) -> Vec<String> {
+  let mut lines = vec!["Risk,Environment,Likelihood,Severity,Score".to_string()];
+  for env in md.environments.iter().filter(|e| env_filter.is_none_or(|f| *f == e.name)) {
+    for r in &md.risks {
+      let lhood = md.threats.iter().filter(|t| t.name() == r.threat).flat_map(|t| t.environments.iter()).find(|te| te.name() == env.name).map(|te| te.likelihood().clone());
+      let sev = md.vulnerabilities.iter().filter(|v| v.name() == r.vulnerability).flat_map(|v| v.environments.iter()).find(|ve| ve.name() == env.name).map(|ve| ve.severity().clone());
+      if let (Some(l),Some(s)) = (lhood,sev) {
+        lines.push(format!("{},{},{},{},{}",r.name(),env.name,l,s,RiskMatrix::score(l.clone() as usize,s.clone() as usize)));
+      }
+    }
+  }
+  lines
+}
+
+fn report(md : &ModelDirectory, kind : &str, format : &str) -> Result<String,String> {
+  match kind {
+    "spec" => {
+      let doc = SpecificationBuilder::new(&md.settings).environments(&md.environments).roles(&md.roles).assets(&md.assets).attackers(&md.attackers).vulnerabilities(&md.vulnerabilities).value_types(&md.value_types).build();
+      match format {
+        "markdown" => Ok(doc.to_markdown()),
+        "html" => Ok(doc.to_html()),
+        "docbook" => Ok(doc.to_docbook()),
+        _ => Err(format!("{} is not a specification format",format))
+      }
+    },
+    "dpia" => {
+      let dpia = Dpia::new(&md.settings.name,&md.environments,&md.roles,&md.assets,&md.threats,&[]);
+      match format {
+        "markdown" => Ok(dpia.to_markdown()),
+        "html" => Ok(dpia.to_html()),
+        _ => Err(format!("{} is not a DPIA format",format))
+      }
+    },
+    _ => Err(format!("{} is not a report",kind))
+  }
+}
+
+fn directory_model(md : &ModelDirectory) -> Result<Model,String> {
+  Model::from_directory(md).map_err(|problems| problems.iter().map(|p| format!("{}: {}",md.path.display(),p)).collect::<Vec<String>>().join("\n"))
+}
+
+const SETTINGS_NOTE : &str = "Project settings are not stored in model directories and were not compared";
+
+fn load_model(dir : &str, settings : &ProjectSettings) -> Result<Model,String> {
+  let md = ModelDirectory::load(Path::new(dir)).map_err(|e| e.to_string())?;
+  let mut m = directory_model(&md)?;
+  m.settings = settings.clone();
+  Ok(m)
+}
+
+fn run_db(p : &mut MySQLDatabaseProxy, cmd : &str, args : &[String], out : &mut dyn Write) -> Result<(),String> {
+  db_supported(cmd,args)?;
+  match (cmd,args.first().map(|s| s.as_str()).unwrap_or("")) {
+    ("show","settings") => writeln!(out,"{}",p.get_project_settings()).map_err(|e| e.to_string()),
+    ("import",_) => {
+      let f = File::open(args.get(1).ok_or(USAGE.to_string())?).map_err(|e| e.to_string())?;
+      let (vts,errors) = read_value_types(f);
+      if !errors.is_empty() {
+        return Err(errors.iter().map(|e| e.to_string()).collect::<Vec<String>>().join("\n"));
+      }
+      for vt in &vts {
+        p.add_value_type(vt);
+      }
+      writeln!(out,"Imported {} value_types",vts.len()).map_err(|e| e.to_string())
+    },
+    _ => run_model(&mut db_directory(p),cmd,args,out)
+  }
+}
+
+fn run_model(md : &mut ModelDirectory, cmd : &str, args : &[String], out : &mut dyn Write) -> Result<(),String> {
+  let arg = |idx : usize| args.get(idx).ok_or(USAGE.to_string());
+  let w = |out : &mut dyn Write, txt : &str| writeln!(out,"{}",txt).map_err(|e| e.to_string());
+  match cmd {
+    "list" => {
+      if arg(0)? == "value_types" {
+        for vt in &md.value_types {
+          w(out,&format!("{}: {}",vt.vt_type,vt.name))?;
+        }
+        return Ok(());
+      }
+      for o in objects(md,arg(0)?)? {
+        w(out,o.name())?;
+      }
+      Ok(())
+    },
+    "search" => {
+      let mut all = Vec::<&dyn Dimension>::new();
+      for (dim,_) in DIMENSION_FILES {
+        all.extend(objects(md,dim)?);
+      }
+      for o in search(&all,arg(0)?) {
+        w(out,&format!("{}: {}",o.kind(),o.name()))?;
+      }
+      Ok(())
+    },
+    "show" => w(out,&show(md,arg(0)?,arg(1)?)?),
+    "validate" => {
+      let problems = md.validate();
+      if problems.is_empty() {
+        w(out,"Model is valid")
+      }
+      else {
+        Err(problems.join("\n"))
+      }
+    },
+    "export" => export(md,arg(0)?,File::create(arg(1)?).map_err(|e| e.to_string())?),
+    "import" => {
+      let dim = arg(0)?;
+      if !DIMENSION_FILES.iter().any(|(d,_)| d == dim) {
+        return Err(format!("{} is not a dimension",dim));
+      }
+      let n = import(md,dim,File::open(arg(1)?).map_err(|e| e.to_string())?)?;
+      w(out,&format!("Imported {} {}",n,dim))
+    },
+    "risk" => {
+      for l in risk_scores(md,args.first()) {
+        w(out,&l)?;
+      }
+      Ok(())
+    },
+    "diff" => {
+      let ours = directory_model(md)?;
+      let cs = diff(&ours,&load_model(arg(0)?,&ours.settings)?);
+      w(out,&if cs.is_empty() { "No changes".to_string() } else { cs.to_string() })?;
+      w(out,SETTINGS_NOTE)
+    },
+    "merge" => {
+      let ours = directory_model(md)?;
+      let res = diff::merge(&load_model(arg(0)?,&ours.settings)?,&ours,&load_model(arg(1)?,&ours.settings)?);
+      if !res.is_clean() {
+        return Err(res.conflicts.iter().map(|c| c.to_string()).collect::<Vec<String>>().join("\n"));
+      }
+      let cs = diff(&ours,&res.model);
+      res.model.to_directory(&md.path)?.save().map_err(|e| e.to_string())?;
+      w(out,&if cs.is_empty() { "Already up to date".to_string() } else { cs.to_string() })?;
+      w(out,SETTINGS_NOTE)
+    },
+    "report" => {
+      let kind = arg(0)?;
+      let mut format = "markdown".to_string();
+      let mut output : Option<String> = None;
+      let mut idx = 1;
+      while idx < args.len() {
+        match args[idx].as_str() {
+          "--format" => { format = arg(idx + 1)?.clone(); idx += 2; },
+          "--output" => { output = Some(arg(idx + 1)?.clone()); idx += 2; },
+          _ => { return Err(USAGE.to_string()); }
+        }
+      }
+      let txt = report(md,kind,&format)?;
+      match output {
+        Some(f) => std::fs::write(f,txt).map_err(|e| e.to_string()),
+        None => write!(out,"{}",txt).map_err(|e| e.to_string())
+      }
+    },
+    _ => Err(USAGE.to_string())
+  }
+}
+
+fn run(args : &[String], out : &mut dyn Write) -> Result<(),String> {
+  let (mut store,rest) = match args.first().map(|s| s.as_str()) {
+    Some("--model") => {
+      let dir = args.get(1).ok_or(USAGE.to_string())?;
+      (Store::Model(Box::new(ModelDirectory::load(Path::new(dir)).map_err(|e| e.to_string())?)),&args[2..])
+    },
+    Some("--db") => (Store::Database(connect_db()),&args[1..]),
+    _ => { return Err(USAGE.to_string()); }
+  };
+  let cmd = rest.first().ok_or(USAGE.to_string())?;
+  match &mut store {
+    Store::Model(md) => run_model(md,cmd,&rest[1..],out),
+    Store::Database(p) => run_db(p,cmd,&rest[1..],out)
+  }
+}
+
+fn main() {
+  let args : Vec<String> = env::args().skip(1).collect();
+  if let Err(err) = run(&args,&mut io::stdout()) {
+    eprintln!("{}",err);
+    process::exit(1);
+  }
+}
+
+#[cfg(test)]
+fn run_str(args : &[&str]) -> Result<String,String> {
+  let mut out = Vec::<u8>::new();
+  run(&args.iter().map(|a| a.to_string()).collect::<Vec<String>>(),&mut out)?;
+  Ok(String::from_utf8(out).unwrap())
+}
+
+#[test]
+fn test_usage() {
+  assert_eq!(run_str(&[]),Err(USAGE.to_string()));
+  assert_eq!(run_str(&["list","assets"]),Err(USAGE.to_string()));
+}
+
+#[test]
+fn test_db_supported() {
+  let args = |a : &[&str]| a.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+  assert!(db_supported("list",&args(&["assets"])).is_ok());
+  assert!(db_supported("show",&args(&["settings"])).is_ok());
+  assert!(db_supported("report",&args(&["spec"])).is_ok());
+  assert_eq!(db_supported("list",&args(&["roles"])),Err("list roles is not supported with --db".to_string()));
+  assert_eq!(db_supported("import",&args(&["assets","a.csv"])),Err("import assets is not supported with --db".to_string()));
+  assert_eq!(db_supported("report",&args(&["dpia"])),Err("report dpia is not supported with --db".to_string()));
+  assert_eq!(db_supported("risk",&args(&[])),Err("risk is not supported with --db".to_string()));
+}
+
+#[test]
+fn test_import_into_unreadable_model() {
+  let dir = env::temp_dir().join(format!("cairis_cli_import_{}",process::id()));
+  let d = dir.to_str().unwrap();
+  std::fs::create_dir_all(&dir).unwrap();
+  std::fs::write(dir.join("assets.csv"),"name,short_code,type\nData,DAT,Information\nServer,,Hardware\n").unwrap();
+  let roles_csv = dir.join("import_roles.csv");
+  std::fs::write(&roles_csv,"name,type,short_code\nResearcher,Stakeholder,RES\n").unwrap();
+  assert_eq!(run_str(&["--model",d,"import","roles",roles_csv.to_str().unwrap()]),Err("Cannot import into a model with unreadable rows, as saving would drop them:\nassets.csv: Row 3: short_code is empty".to_string()));
+  assert!(std::fs::read_to_string(dir.join("assets.csv")).unwrap().contains("Server,,Hardware"));
+  std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_model_commands() {
+  let dir = env::temp_dir().join(format!("cairis_cli_{}",process::id()));
+  let d = dir.to_str().unwrap();
+  std::fs::create_dir_all(&dir).unwrap();
+  std::fs::write(dir.join("environments.csv"),"name,short_code\nDay,DAY\n").unwrap();
+  std::fs::write(dir.join("assets.csv"),"name,short_code,type,critical,Day/confidentiality\nData,DAT,Information,yes,High\n").unwrap();
+  std::fs::write(dir.join("vulnerabilities.csv"),"name,type,Day/severity,Day/assets\nUnvalidated input,Implementation,Critical,Data\n").unwrap();
+  std::fs::write(dir.join("threats.csv"),"name,type,Day/likelihood,Day/assets\nSQL injection,Software,Probable,Data\n").unwrap();
+  std::fs::write(dir.join("risks.csv"),"name,threat,vulnerability\nUpload risk,SQL injection,Unvalidated input\n").unwrap();
+  std::fs::write(dir.join("value_types.csv"),"name,type,score\nSoftware,threat_type,-1\nHigh,threat_value,3\n").unwrap();
+
+  assert_eq!(run_str(&["--model",d,"list","assets"]),Ok("Data\n".to_string()));
+  assert_eq!(run_str(&["--model",d,"list","value_types"]),Ok("threat_type: Software\nthreat_value: High\n".to_string()));
+  assert!(run_str(&["--model",d,"show","assets","Data"]).unwrap().starts_with("Name: Data, Short code: DAT"));
+  assert_eq!(run_str(&["--model",d,"show","assets","Server"]),Err("No assets called Server".to_string()));
+  assert_eq!(run_str(&["--model",d,"search","dat"]),Ok("asset: Data\nvulnerability: Unvalidated input\n".to_string()));
+  assert_eq!(run_str(&["--model",d,"search","day"]),Ok("environment: Day\n".to_string()));
+  assert_eq!(run_str(&["--model",d,"validate"]),Ok("Model is valid\n".to_string()));
+  assert_eq!(run_str(&["--model",d,"risk"]),Ok("Risk,Environment,Likelihood,Severity,Score\nUpload risk,Day,Probable,Critical,6\n".to_string()));
+  assert!(run_str(&["--model",d,"report","spec","--format","docbook"]).unwrap().contains("<title>Data</title>"));
+
+  let roles_csv = dir.join("import_roles.csv");
+  std::fs::write(&roles_csv,"name,type,short_code,description\nResearcher,Stakeholder,RES,A researcher\n").unwrap();
+  assert_eq!(run_str(&["--model",d,"import","roles",roles_csv.to_str().unwrap()]),Ok("Imported 1 roles\n".to_string()));
+  assert_eq!(run_str(&["--model",d,"list","roles"]),Ok("Researcher\n".to_string()));
+  std::fs::write(&roles_csv,"name,type,short_code\nAdmin,Superuser,ADM\n").unwrap();
+  assert_eq!(run_str(&["--model",d,"import","roles",roles_csv.to_str().unwrap()]),Err("Row 2: Superuser is not a valid type value".to_string()));
+
+  let other = dir.join("other");
+  std::fs::create_dir_all(&other).unwrap();
+  for f in ["environments.csv","assets.csv","vulnerabilities.csv","threats.csv","risks.csv","roles.csv","value_types.csv"] {
+    std::fs::copy(dir.join(f),other.join(f)).unwrap();
+  }
+  let o = other.to_str().unwrap();
+  assert_eq!(run_str(&["--model",d,"diff",o]),Ok(format!("No changes\n{}\n",SETTINGS_NOTE)));
+  std::fs::write(other.join("roles.csv"),"name,type,short_code,description\nResearcher,Stakeholder,RES,A neuroscientist\n").unwrap();
+  assert_eq!(run_str(&["--model",d,"diff",o]),Ok(format!("~ role Researcher\n    description: A researcher -> A neuroscientist\n{}\n",SETTINGS_NOTE)));
+  assert_eq!(run_str(&["--model",d,"merge",d,o]),Ok(format!("~ role Researcher\n    description: A researcher -> A neuroscientist\n{}\n",SETTINGS_NOTE)));
+  assert_eq!(run_str(&["--model",d,"diff",o]),Ok(format!("No changes\n{}\n",SETTINGS_NOTE)));
+  std::fs::write(other.join("roles.csv"),"name,type,short_code,description\nResearcher,Stakeholder,RES,A neuroscientist\nResearcher,Stakeholder,RSR,A duplicate\n").unwrap();
+  let problem = Err(format!("{}: Duplicate role Researcher",o));
+  assert_eq!(run_str(&["--model",d,"diff",o]),problem);
+  assert_eq!(run_str(&["--model",d,"merge",d,o]),problem);
+  assert_eq!(run_str(&["--model",o,"diff",d]),problem);
+  assert_eq!(run_str(&["--model",d,"list","roles"]),Ok("Researcher\n".to_string()));
+
+  let base = dir.join("base");
+  std::fs::create_dir_all(&base).unwrap();
+  for f in ["environments.csv","assets.csv","vulnerabilities.csv","threats.csv","risks.csv","roles.csv","value_types.csv"] {
+    std::fs::copy(dir.join(f),base.join(f)).unwrap();
+    std::fs::copy(dir.join(f),other.join(f)).unwrap();
+  }
+  let threats_csv = std::fs::read_to_string(dir.join("threats.csv")).unwrap();
+  let risks_csv = std::fs::read_to_string(dir.join("risks.csv")).unwrap();
+  std::fs::write(dir.join("threats.csv"),"name,type\n").unwrap();
+  std::fs::write(dir.join("risks.csv"),"name,threat,vulnerability\n").unwrap();
+  std::fs::write(other.join("risks.csv"),"name,threat,vulnerability\nUpload risk,SQL injection,Unvalidated input\nLogin risk,SQL injection,Unvalidated input\n").unwrap();
+  assert_eq!(run_str(&["--model",d,"merge",base.to_str().unwrap(),o]),Err("Risk Login risk refers to unknown threat SQL injection after merging".to_string()));
+  assert_eq!(run_str(&["--model",d,"list","risks"]),Ok("".to_string()));
+  std::fs::write(dir.join("threats.csv"),threats_csv).unwrap();
+  std::fs::write(dir.join("risks.csv"),risks_csv).unwrap();
+
+  let exported = dir.join("export_assets.csv");
+  run_str(&["--model",d,"export","assets",exported.to_str().unwrap()]).unwrap();
+  assert!(std::fs::read_to_string(&exported).unwrap().starts_with("name,short_code,type,critical"));
+  std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,31 @@
+use std::env;
+use std::path::Path;
+use std::process;
+use cairis_core::db::mysql::connect_db;
+use cairis_core::exchange::modeldir::ModelDirectory;
+use cairis_core::server::serve;
+
+const USAGE : &str = "Usage: cairis_server (--model DIR | --db) [--address HOST:PORT]";
+
+fn main() {
+  let args : Vec<String> = env::args().skip(1).collect();
+  let addr = match args.iter().position(|a| a == "--address") {
+    Some(idx) => args.get(idx + 1).cloned().unwrap_or_else(|| { eprintln!("{}",USAGE); process::exit(1); }),
+    None => "127.0.0.1:7071".to_string()
+  };
+  let res = match args.first().map(|s| s.as_str()) {
+    Some("--model") => {
+      let dir = args.get(1).unwrap_or_else(|| { eprintln!("{}",USAGE); process::exit(1); });
+      match ModelDirectory::load(Path::new(dir)) {
+        Ok(mut md) => serve(&addr,&mut md),
+        Err(err) => Err(err)
+      }
+    },
+    Some("--db") => serve(&addr,&mut connect_db()),
+    _ => { eprintln!("{}",USAGE); process::exit(1); }
+  };
+  if let Err(err) = res {
+    eprintln!("{}",err);
+    process::exit(1);
+  }
+}
@@ -0,0 +1,44 @@
+use mysql::*;
+use mysql::prelude::*;
+use crate::db::mysql::*;
+use crate::dimensions::asset::Asset;
+use crate::dimensions::dimension::Identified;
+use crate::dimensions::objectid::{ObjectId,AssetId};
+
+impl MySQLDatabaseProxy {
+
+  pub fn get_assets(&mut self) -> Vec<Asset> {
+    let res = self.conn.query_map("call getAssets(-1)",| (a_id, a_name, a_sc, a_desc, a_sig, a_type, a_crit, a_crit_rat) : (i128,String,String,String,String,String,i128,String) | {
+      let mut a = Asset::new(&a_name,&a_sc,&a_type,a_crit == 1);
+      a.set_id(AssetId::new(a_id));
+      a.description = a_desc;
+      a.significance = a_sig;
+      a.critical_rationale = a_crit_rat;
+      a
+    });
+    let mut assets = match res {
+      Ok(r) => r,
+      Err(err) => {panic!("MySQL error getting assets {:?}",err);}
+    };
+    for a in assets.iter_mut() {
+      let a_id = match a.id() {
+        Some(i) => i,
+        None => continue
+      };
+      a.tags = self.get_tags(a_id);
+      let prop_res = self.conn.exec_map("call assetEnvironmentProperties(:id)", params!{"id" => a_id.value()},| (env_name, p_name, p_value, p_rat) : (String,String,String,String) | (env_name,p_name,p_value,p_rat));
+      match prop_res {
+        Ok(rows) => {
+          for (env_name,p_name,p_value,p_rat) in rows {
+            if !a.environment_properties.contains_key(&env_name) {
+              a.add_environment(&env_name);
+            }
+            a.update_security_property(&env_name,&p_name.to_lowercase(),&p_value,&p_rat);
+          }
+        },
+        Err(err) => {panic!("MySQL error getting asset environment properties {:?}",err);}
+      }
+    }
+    assets
+  }
+}
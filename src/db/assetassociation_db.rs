@@ -0,0 +1,80 @@
+use mysql::*;
+use mysql::prelude::*;
+use crate::db::mysql::*;
+use crate::dimensions::assetassociation::{AssetAssociation,AssetModel,Adornment};
+use crate::dimensions::objectid::{ObjectId,AssetAssociationId};
+
+impl MySQLDatabaseProxy {
+
+  pub fn add_asset_association(&mut self, aa : &AssetAssociation) {
+    self.commit_asset_association(CommitAction::Add,aa);
+  }
+
+  pub fn update_asset_association(&mut self, aa : &AssetAssociation) {
+    self.commit_asset_association(CommitAction::Update,aa);
+  }
+
+  fn commit_asset_association(&mut self, action : CommitAction, aa : &AssetAssociation) {
+    let aa_id : AssetAssociationId = match (action,aa.id) {
+      (CommitAction::Add,_) => self.new_id(),
+      (CommitAction::Update,Some(i)) => i,
+      (CommitAction::Update,None) => {panic!("Cannot update unsaved asset association {}",aa.head_asset);}
+    };
+    let sql_txt = match action {
+      CommitAction::Add => "call addClassAssociation(:id,:env,:ha,:hd,:hn,:hadorn,:hm,:hr,:tr,:tm,:tadorn,:tn,:td,:ta,:rat)",
+      CommitAction::Update => "call updateClassAssociation(:id,:env,:ha,:hd,:hn,:hadorn,:hm,:hr,:tr,:tm,:tadorn,:tn,:td,:ta,:rat)"
+    };
+    let res : Result::<Option::<Row>> = self.conn.exec_first(sql_txt, params!{
+      "id" => aa_id.value(),
+      "env" => &aa.environment,
+      "ha" => &aa.head_asset,
+      "hd" => "asset",
+      "hn" => aa.head_navigation,
+      "hadorn" => aa.head_adornment.to_string(),
+      "hm" => &aa.head_multiplicity,
+      "hr" => &aa.head_role,
+      "tr" => &aa.tail_role,
+      "tm" => &aa.tail_multiplicity,
+      "tadorn" => aa.tail_adornment.to_string(),
+      "tn" => aa.tail_navigation,
+      "td" => "asset",
+      "ta" => &aa.tail_asset,
+      "rat" => &aa.rationale
+    });
+    match res {
+      Ok(_r) => {},
+      Err(err) => {panic!("MySQL error committing asset association {:?}",err);}
+    }
+  }
+
+  pub fn get_asset_associations(&mut self) -> Vec<AssetAssociation> {
+    let res : Result<Vec<Row>> = self.conn.query("call getClassAssociations(-1)");
+    match res {
+      Ok(rows) => {
+        rows.into_iter().map(|row| {
+          let mut aa = AssetAssociation::new(&row.get::<String,usize>(1).unwrap(),&row.get::<String,usize>(2).unwrap(),&row.get::<String,usize>(13).unwrap());
+          aa.id = Some(AssetAssociationId::new(row.get(0).unwrap()));
+          aa.head_navigation = row.get(4).unwrap();
+          aa.head_adornment = Adornment::new(&row.get::<String,usize>(5).unwrap());
+          aa.head_multiplicity = row.get(6).unwrap();
+          aa.head_role = row.get(7).unwrap();
+          aa.tail_role = row.get(8).unwrap();
+          aa.tail_multiplicity = row.get(9).unwrap();
+          aa.tail_adornment = Adornment::new(&row.get::<String,usize>(10).unwrap());
+          aa.tail_navigation = row.get(11).unwrap();
+          aa.rationale = row.get(14).unwrap();
+          aa
+        }).collect()
+      },
+      Err(err) => {panic!("MySQL error getting asset associations {:?}",err);}
+    }
+  }
+
+  pub fn get_asset_model(&mut self, env_name : &str) -> AssetModel {
+    AssetModel::new(env_name,&self.get_asset_associations())
+  }
+
+  pub fn delete_asset_association(&mut self, objt_id : AssetAssociationId) {
+    self.delete_object(objt_id);
+  }
+}
@@ -0,0 +1,90 @@
+use mysql::*;
+use mysql::prelude::*;
+use crate::db::mysql::*;
+use crate::dimensions::domainproperty::DomainProperty;
+use crate::dimensions::objectid::{ObjectId,DomainPropertyId};
+
+impl MySQLDatabaseProxy {
+
+  pub fn add_domain_property(&mut self, dp : &DomainProperty) {
+    self.commit_domain_property(CommitAction::Add,dp);
+  }
+
+  pub fn update_domain_property(&mut self, dp : &DomainProperty) {
+    self.commit_domain_property(CommitAction::Update,dp);
+  }
+
+  fn commit_domain_property(&mut self, action : CommitAction, dp : &DomainProperty) {
+    let dp_id : DomainPropertyId = match (action,dp.id) {
+      (CommitAction::Add,_) => self.new_id(),
+      (CommitAction::Update,Some(i)) => i,
+      (CommitAction::Update,None) => {panic!("Cannot update unsaved domain property {}",dp.name);}
+    };
+    let sql_txt = match action {
+      CommitAction::Add => "call addDomainProperty(:id,:name,:sc,:def,:type,:orig)",
+      CommitAction::Update => "call updateDomainProperty(:id,:name,:sc,:def,:type,:orig)"
+    };
+    let res : Result::<Option::<Row>> = self.conn.exec_first(sql_txt, params!{
+      "id" => dp_id.value(),
+      "name" => &dp.name,
+      "sc" => &dp.short_code,
+      "def" => &dp.definition,
+      "type" => dp.dp_type.to_string(),
+      "orig" => &dp.originator
+    });
+    match res {
+      Ok(_r) => {},
+      Err(err) => {panic!("MySQL error committing domain property {:?}",err);}
+    }
+    self.add_tags(dp_id,&dp.tags);
+  }
+
+  pub fn get_domain_properties(&mut self) -> Vec<DomainProperty> {
+    let res = self.conn.query_map("call getDomainProperties(-1)",| (dp_id, dp_name, dp_sc, dp_def, dp_type, dp_orig) : (i128,String,String,String,String,String) | {
+      let mut dp = DomainProperty::new(&dp_name,&dp_sc,&dp_type,&dp_orig);
+      dp.id = Some(DomainPropertyId::new(dp_id));
+      dp.definition = dp_def;
+      dp
+    });
+    let mut dps = match res {
+      Ok(r) => r,
+      Err(err) => {panic!("MySQL error getting domain properties {:?}",err);}
+    };
+    for dp in dps.iter_mut() {
+      if let Some(dp_id) = dp.id {
+        dp.tags = self.get_tags(dp_id);
+      }
+    }
+    dps
+  }
+
+  pub fn delete_domain_property(&mut self, objt_id : DomainPropertyId) {
+    self.delete_object(objt_id);
+  }
+}
+
+#[test]
+pub fn test_domain_properties() {
+  let mut p = initialise_db();
+
+  let mut no_dps = p.get_domain_properties();
+  assert_eq!(no_dps.len(),0);
+
+  let mut idp = DomainProperty::new("Users authenticate","DP-1","Hypothesis","Shamal Faily");
+  idp.definition = "Users always authenticate before using the system".to_string();
+  idp.tags.push(crate::dimensions::tag::Tag::new("auth"));
+  p.add_domain_property(&idp);
+
+  let odps = p.get_domain_properties();
+  let odp = &odps[0];
+  assert_eq!(idp.name,odp.name);
+  assert_eq!(idp.short_code,odp.short_code);
+  assert_eq!(idp.definition,odp.definition);
+  assert_eq!(idp.dp_type,odp.dp_type);
+  assert_eq!(idp.originator,odp.originator);
+  assert!(idp.tags == odp.tags);
+
+  p.delete_domain_property(odp.id.unwrap());
+  no_dps = p.get_domain_properties();
+  assert_eq!(no_dps.len(),0);
+}
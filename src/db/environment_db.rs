@@ -2,26 +2,31 @@ use mysql::*;
 use mysql::prelude::*;
 use crate::db::mysql::*;
 use crate::dimensions::environment::{Environment, CompositeEnvironments};
+use crate::dimensions::objectid::{ObjectId,EnvironmentId,id_value};
 
 impl MySQLDatabaseProxy {
 
   pub fn add_environment(&mut self, env : &Environment) {
-    self.commit_environment("add",env);
+    self.commit_environment(CommitAction::Add,env);
   }
 
-  fn commit_environment(&mut self, commit_prefix: &str, env : &Environment) {
+  fn commit_environment(&mut self, action : CommitAction, env : &Environment) {
 
-    let mut env_id = env.id;
-    let sql_txt = format!("call {}Environment(:id,:name,:sc,:desc)",commit_prefix);
-    if commit_prefix == "add" {
-      env_id = self.new_id();
-    }
-    else {
-      self.delete_environment_components(env_id);
-    }
+    let sql_txt = match action {
+      CommitAction::Add => "call addEnvironment(:id,:name,:sc,:desc)",
+      CommitAction::Update => "call updateEnvironment(:id,:name,:sc,:desc)"
+    };
+    let env_id : EnvironmentId = match (action,env.id) {
+      (CommitAction::Add,_) => self.new_id(),
+      (CommitAction::Update,Some(i)) => {
+        self.delete_environment_components(i);
+        i
+      },
+      (CommitAction::Update,None) => {panic!("Cannot update unsaved environment {}",env.name);}
+    };
 
-    let res : Result::<Option::<u128>> = self.conn.exec_first(sqlTxt, params!{
-      "id" => env_id,
+    let res : Result::<Option::<Row>> = self.conn.exec_first(sql_txt, params!{
+      "id" => env_id.value(),
       "name" => &env.name,
       "sc" => &env.short_code,
       "desc" => &env.definition
@@ -31,17 +36,17 @@ impl MySQLDatabaseProxy {
       Err(err) => {panic!("MySQL error adding environment {:?}",err);}
     }
     
-    if env.environments.len() > 0 {
+    if !env.environments.is_empty() {
       for ce in &env.environments.environments {
-        self.add_composite_environment(env.id,&ce);
+        self.add_composite_environment(env_id,ce);
       }
       self.add_composite_environment_properties(env_id,&env.environments);
     }
   }
 
-  fn delete_environment_components(&mut self, env_id: i128) {
-    let res : Result::<Option::<u128>> = self.conn.exec_first("call deleteEnvironmentComponents(:id)", params!{
-      "id" => env_id
+  fn delete_environment_components(&mut self, env_id : EnvironmentId) {
+    let res : Result::<Option::<Row>> = self.conn.exec_first("call deleteEnvironmentComponents(:id)", params!{
+      "id" => env_id.value()
     });
     match res {
       Ok(_r) => {},
@@ -49,9 +54,9 @@ impl MySQLDatabaseProxy {
     }
   }
 
-  fn add_composite_environment(&mut self, env_id : i128, ce: &String) {
-    let res : Result::<Option::<u128>> = self.conn.exec_first("call addCompositeEnvironment(:id,:c)", params!{
-      "id" => env_id,
+  fn add_composite_environment(&mut self, env_id : EnvironmentId, ce: &str) {
+    let res : Result::<Option::<Row>> = self.conn.exec_first("call addCompositeEnvironment(:id,:c)", params!{
+      "id" => env_id.value(),
       "c" => ce
     });
     match res {
@@ -60,9 +65,9 @@ impl MySQLDatabaseProxy {
     }
   }
 
-  fn add_composite_environment_properties(&mut self, env_id : i128, comp_env : &CompositeEnvironments) {
-    let res : Result::<Option::<u128>> = self.conn.exec_first("call addCompositeEnvironmentProperties(:id,:dp,:oe)", params!{
-      "id" => env_id,
+  fn add_composite_environment_properties(&mut self, env_id : EnvironmentId, comp_env : &CompositeEnvironments) {
+    let res : Result::<Option::<Row>> = self.conn.exec_first("call addCompositeEnvironmentProperties(:id,:dp,:oe)", params!{
+      "id" => env_id.value(),
       "dp" => comp_env.property.to_string(),
       "oe" => &comp_env.overriding_environment_name
     });
@@ -73,12 +78,46 @@ impl MySQLDatabaseProxy {
   }
 
   pub fn update_environment(&mut self, env: &Environment) {
-    self.commit_environment("update",env);
+    self.commit_environment(CommitAction::Update,env);
   }
 
-  pub fn delete_environment(&mut self, objt_id : i128) {
-    self.delete_object(objt_id,"environment");
+  pub fn delete_environment(&mut self, objt_id : EnvironmentId) {
+    self.delete_object(objt_id);
   }
 
-
-}
\ No newline at end of file
+  pub fn get_environments(&mut self) -> Vec<Environment> {
+    let res = self.conn.query_map("call getEnvironments(-1)",| (env_id, env_name, env_sc, env_desc) : (i128,String,String,String) | {
+      let mut env = Environment::new(&env_name,&env_sc);
+      env.id = Some(EnvironmentId::new(env_id));
+      env.definition = env_desc;
+      env
+    });
+    let mut envs = match res {
+      Ok(r) => r,
+      Err(err) => {panic!("MySQL error getting environments {:?}",err);}
+    };
+    for env in envs.iter_mut() {
+      let ce_res = self.conn.exec_map("call compositeEnvironmentNames(:id)", params!{"id" => id_value(env.id)},| ce_name : String | ce_name);
+      match ce_res {
+        Ok(ce_names) => {
+          for ce in &ce_names {
+            env.environments.add(ce);
+          }
+        },
+        Err(err) => {panic!("MySQL error getting composite environments {:?}",err);}
+      }
+      if !env.environments.is_empty() {
+        let cp_res : Result::<Option::<(String,String)>> = self.conn.exec_first("call compositeEnvironmentProperties(:id)", params!{"id" => id_value(env.id)});
+        match cp_res {
+          Ok(Some((dp,oe))) => {
+            env.environments.update_property(&dp);
+            env.environments.overriding_environment_name = oe;
+          },
+          Ok(None) => {},
+          Err(err) => {panic!("MySQL error getting composite environment properties {:?}",err);}
+        }
+      }
+    }
+    envs
+  }
+}
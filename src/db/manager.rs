@@ -0,0 +1,274 @@
+use mysql::*;
+use mysql::prelude::*;
+use std::collections::HashMap;
+use std::env;
+use crate::db::mysql::MySQLDatabaseProxy;
+
+const SYSTEM_DATABASES : [&str; 4] = ["information_schema","mysql","performance_schema","sys"];
+
+pub fn valid_database_name(db_name : &str) -> bool {
+  !db_name.is_empty() && db_name.len() <= 64 && db_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') && !SYSTEM_DATABASES.contains(&db_name)
+}
+
+fn name_hash(owner : &str, project : &str) -> u64 {
+  let mut h : u64 = 0xcbf29ce484222325;
+  for b in owner.bytes().chain(std::iter::once(0)).chain(project.bytes()) {
+    h ^= b as u64;
+    h = h.wrapping_mul(0x100000001b3);
+  }
+  h
+}
+
+pub fn model_database_name(owner : &str, project : &str) -> String {
+  let clean = |x : &str| x.to_lowercase().chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect::<String>();
+  let mut db_name = format!("cairis_{}_{}",clean(owner),clean(project));
+  db_name.truncate(47);
+  format!("{}_{:016x}",db_name,name_hash(owner,project))
+}
+
+fn check_name(db_name : &str) {
+  if !valid_database_name(db_name) {
+    panic!("{} is not a valid database name",db_name);
+  }
+}
+
+#[derive(Default)]
+pub struct Sessions {
+  bindings : HashMap<String,String>
+}
+
+impl Sessions {
+  pub fn new() -> Sessions {
+    Sessions{bindings : HashMap::<String,String>::new()}
+  }
+
+  pub fn bind(&mut self, session_id : &str, db_name : &str) {
+    self.bindings.insert(session_id.to_string(),db_name.to_string());
+  }
+
+  pub fn unbind(&mut self, session_id : &str) -> Option<String> {
+    self.bindings.remove(session_id)
+  }
+
+  pub fn database(&self, session_id : &str) -> Option<&str> {
+    self.bindings.get(session_id).map(|d| d.as_str())
+  }
+
+  pub fn sessions_for(&self, db_name : &str) -> Vec<String> {
+    let mut ids = self.bindings.iter().filter(|(_,d)| *d == db_name).map(|(s,_)| s.clone()).collect::<Vec<String>>();
+    ids.sort();
+    ids
+  }
+
+  pub fn rename_database(&mut self, old_name : &str, new_name : &str) {
+    for d in self.bindings.values_mut().filter(|d| *d == old_name) {
+      *d = new_name.to_string();
+    }
+  }
+
+  pub fn drop_database(&mut self, db_name : &str) {
+    self.bindings.retain(|_,d| d != db_name);
+  }
+}
+
+#[test]
+fn test_database_names() {
+  assert!(valid_database_name("cairis_alice_neurogrid"));
+  assert!(!valid_database_name("mysql"));
+  assert!(!valid_database_name("x`; drop database y"));
+  assert!(!valid_database_name(""));
+  assert!(model_database_name("Alice","NeuroGrid 2").starts_with("cairis_alice_neurogrid_2_"));
+  assert_eq!(model_database_name("Alice","NeuroGrid 2"),model_database_name("Alice","NeuroGrid 2"));
+  assert_ne!(model_database_name("Alice","neurogrid"),model_database_name("alice","neurogrid"));
+  assert_ne!(model_database_name("alice","neuro grid"),model_database_name("alice","neuro_grid"));
+  assert_ne!(model_database_name("alice_x","y"),model_database_name("alice","x_y"));
+  assert!(valid_database_name(&model_database_name(&"a".repeat(40),&"b".repeat(40))));
+  assert_ne!(model_database_name(&"a".repeat(40),&"b".repeat(40)),model_database_name(&"a".repeat(40),&"b".repeat(41)));
+}
+
+#[test]
+fn test_sessions() {
+  let mut s = Sessions::new();
+  s.bind("s1","cairis_alice_neurogrid");
+  s.bind("s2","cairis_alice_neurogrid");
+  s.bind("s3","cairis_bob_webinos");
+  assert_eq!(s.database("s1"),Some("cairis_alice_neurogrid"));
+  assert_eq!(s.sessions_for("cairis_alice_neurogrid"),vec!["s1".to_string(),"s2".to_string()]);
+  s.rename_database("cairis_alice_neurogrid","cairis_alice_ng");
+  assert_eq!(s.database("s2"),Some("cairis_alice_ng"));
+  s.drop_database("cairis_alice_ng");
+  assert_eq!(s.database("s1"),None);
+  assert_eq!(s.unbind("s3"),Some("cairis_bob_webinos".to_string()));
+  assert_eq!(s.database("s3"),None);
+}
+
+pub struct DatabaseManager {
+  host : String,
+  port : String,
+  user : String,
+  passwd : String,
+  conn : PooledConn,
+  pub sessions : Sessions
+}
+
+impl DatabaseManager {
+  pub fn new(db_host: &str, db_port: &str, db_user: &str, db_passwd : &str) -> DatabaseManager {
+    let url = format!("mysql://{}:{}@{}:{}",db_user,db_passwd,db_host,db_port);
+    let conn = match Pool::new(url.as_str()).and_then(|p| p.get_conn()) {
+      Ok(c) => c,
+      Err(err) => {panic!("{:?}",err);}
+    };
+    DatabaseManager{host : db_host.to_string(), port : db_port.to_string(), user : db_user.to_string(), passwd : db_passwd.to_string(), conn, sessions : Sessions::new()}
+  }
+
+  pub fn from_env() -> DatabaseManager {
+    dotenv::dotenv().ok();
+    DatabaseManager::new(&env::var("DB_HOST").unwrap(),&env::var("DB_PORT").unwrap(), &env::var("DB_USER").unwrap(), &env::var("DB_PASSWD").unwrap())
+  }
+
+  pub fn list_databases(&mut self) -> Vec<String> {
+    match self.conn.query::<String,&str>("SHOW DATABASES") {
+      Ok(dbs) => dbs.into_iter().filter(|d| !SYSTEM_DATABASES.contains(&d.as_str())).collect(),
+      Err(err) => {panic!("MySQL error listing databases {:?}",err);}
+    }
+  }
+
+  pub fn exists(&mut self, db_name : &str) -> bool {
+    self.list_databases().iter().any(|d| d == db_name)
+  }
+
+  pub fn create_database(&mut self, db_name : &str) {
+    check_name(db_name);
+    if let Err(err) = self.conn.query_drop(format!("CREATE DATABASE `{}` DEFAULT CHARACTER SET utf8mb4",db_name)) {
+      panic!("MySQL error creating database {} {:?}",db_name,err);
+    }
+  }
+
+  pub fn create_model_database(&mut self, db_name : &str) -> MySQLDatabaseProxy {
+    self.create_database(db_name);
+    let mut p = self.open_database(db_name);
+    p.migrate();
+    p
+  }
+
+  pub fn upgrade_database(&mut self, db_name : &str) -> Vec<u32> {
+    self.open_database(db_name).migrate()
+  }
+
+  pub fn open_database(&mut self, db_name : &str) -> MySQLDatabaseProxy {
+    check_name(db_name);
+    if !self.exists(db_name) {
+      panic!("Database {} does not exist",db_name);
+    }
+    MySQLDatabaseProxy::new(&self.host,&self.port,&self.user,&self.passwd,db_name)
+  }
+
+  fn tables(&mut self, db_name : &str) -> std::result::Result<Vec<String>,String> {
+    self.conn.exec::<String,&str,_>("SELECT table_name FROM information_schema.tables WHERE table_schema = :db AND table_type = 'BASE TABLE'", params!{"db" => db_name}).map_err(|err| format!("MySQL error listing tables in {} {:?}",db_name,err))
+  }
+
+  fn move_tables(&mut self, from_name : &str, to_name : &str, tables : &[String]) -> std::result::Result<(),String> {
+    if tables.is_empty() {
+      return Ok(());
+    }
+    let renames = tables.iter().map(|t| format!("`{}`.`{}` TO `{}`.`{}`",from_name,t,to_name,t)).collect::<Vec<String>>().join(", ");
+    self.conn.query_drop(format!("RENAME TABLE {}",renames)).map_err(|err| format!("MySQL error moving tables from {} to {} {:?}",from_name,to_name,err))
+  }
+
+  fn copy_definition(&mut self, kind : &str, old_name : &str, new_name : &str, objt_name : &str, col : usize) -> std::result::Result<(),String> {
+    let create_txt = match self.conn.query_first::<Row,String>(format!("SHOW CREATE {} `{}`.`{}`",kind,old_name,objt_name)) {
+      Ok(Some(row)) => row.get::<Option<String>,usize>(col).flatten(),
+      Ok(None) => None,
+      Err(err) => { return Err(format!("MySQL error reading {} {} {:?}",kind,objt_name,err)); }
+    };
+    let create_txt = create_txt.ok_or(format!("Insufficient privileges to copy {} {}",kind,objt_name))?;
+    let create_txt = create_txt.replace(&format!("`{}`.",old_name),&format!("`{}`.",new_name));
+    self.conn.query_drop(create_txt).map_err(|err| format!("MySQL error copying {} {} {:?}",kind,objt_name,err))
+  }
+
+  fn move_objects(&mut self, old_name : &str, new_name : &str, tables : &[String]) -> std::result::Result<(),String> {
+    self.move_tables(old_name,new_name,tables)?;
+    self.conn.query_drop(format!("USE `{}`",new_name)).map_err(|err| format!("MySQL error using {} {:?}",new_name,err))?;
+    let routines = self.conn.exec::<(String,String),&str,_>("SELECT routine_name, routine_type FROM information_schema.routines WHERE routine_schema = :db", params!{"db" => old_name}).map_err(|err| format!("MySQL error listing routines in {} {:?}",old_name,err))?;
+    for (r_name,r_type) in routines {
+      self.copy_definition(&r_type,old_name,new_name,&r_name,2)?;
+    }
+    let views = self.conn.exec::<String,&str,_>("SELECT table_name FROM information_schema.views WHERE table_schema = :db", params!{"db" => old_name}).map_err(|err| format!("MySQL error listing views in {} {:?}",old_name,err))?;
+    for v in views {
+      self.copy_definition("VIEW",old_name,new_name,&v,1)?;
+    }
+    Ok(())
+  }
+
+  pub fn rename_database(&mut self, old_name : &str, new_name : &str) {
+    check_name(old_name);
+    check_name(new_name);
+    if !self.exists(old_name) {
+      panic!("Database {} does not exist",old_name);
+    }
+    if self.exists(new_name) {
+      panic!("Database {} already exists",new_name);
+    }
+    let tables = match self.tables(old_name) {
+      Ok(t) => t,
+      Err(err) => {panic!("{}",err);}
+    };
+    self.create_database(new_name);
+    if let Err(err) = self.move_objects(old_name,new_name,&tables) {
+      let rollback = self.tables(new_name).and_then(|moved| self.move_tables(new_name,old_name,&moved));
+      if let Err(rb_err) = rollback {
+        panic!("{}; rolling back the rename of {} to {} also failed: {}",err,old_name,new_name,rb_err);
+      }
+      if let Err(drop_err) = self.conn.query_drop(format!("DROP DATABASE `{}`",new_name)) {
+        panic!("{}; rolled back the rename of {} but could not drop {} {:?}",err,old_name,new_name,drop_err);
+      }
+      panic!("{}; the rename of {} to {} was rolled back",err,old_name,new_name);
+    }
+    self.drop_database(old_name);
+    self.sessions.rename_database(old_name,new_name);
+  }
+
+  pub fn drop_database(&mut self, db_name : &str) {
+    check_name(db_name);
+    if let Err(err) = self.conn.query_drop(format!("DROP DATABASE `{}`",db_name)) {
+      panic!("MySQL error dropping database {} {:?}",db_name,err);
+    }
+    self.sessions.drop_database(db_name);
+  }
+
+  pub fn bind_session(&mut self, session_id : &str, db_name : &str) {
+    check_name(db_name);
+    if !self.exists(db_name) {
+      panic!("Database {} does not exist",db_name);
+    }
+    self.sessions.bind(session_id,db_name);
+  }
+
+  pub fn open_session(&mut self, session_id : &str) -> MySQLDatabaseProxy {
+    let db_name = match self.sessions.database(session_id) {
+      Some(d) => d.to_string(),
+      None => {panic!("Session {} is not bound to a database",session_id);}
+    };
+    self.open_database(&db_name)
+  }
+}
+
+#[test]
+pub fn test_database_manager() {
+  let mut m = DatabaseManager::from_env();
+  let db_name = model_database_name("test","manager");
+  if m.exists(&db_name) {
+    m.drop_database(&db_name);
+  }
+  m.create_model_database(&db_name);
+  assert!(m.list_databases().contains(&db_name));
+  assert_eq!(m.upgrade_database(&db_name),Vec::<u32>::new());
+  m.bind_session("s1",&db_name);
+  assert!(m.open_session("s1").ok());
+  let new_name = model_database_name("test","renamed");
+  m.rename_database(&db_name,&new_name);
+  assert_eq!(m.sessions.database("s1"),Some(new_name.as_str()));
+  m.drop_database(&new_name);
+  assert!(!m.exists(&new_name));
+  assert_eq!(m.sessions.database("s1"),None);
+}
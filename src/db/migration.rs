@@ -0,0 +1,115 @@
+use mysql::*;
+use mysql::prelude::*;
+use crate::db::mysql::*;
+
+pub struct Migration {
+  pub version : u32,
+  pub name : &'static str,
+  pub sql : &'static str
+}
+
+pub const MIGRATIONS : [Migration; 5] = [
+  Migration{version : 1, name : "base", sql : include_str!("schema/001_base.sql")},
+  Migration{version : 2, name : "environments", sql : include_str!("schema/002_environments.sql")},
+  Migration{version : 3, name : "assets", sql : include_str!("schema/003_assets.sql")},
+  Migration{version : 4, name : "domain_properties", sql : include_str!("schema/004_domain_properties.sql")},
+  Migration{version : 5, name : "scored_value_types", sql : include_str!("schema/005_scored_value_types.sql")}
+];
+
+pub fn latest_version() -> u32 {
+  MIGRATIONS.iter().map(|m| m.version).max().unwrap_or(0)
+}
+
+pub fn split_statements(sql : &str) -> Vec<String> {
+  let mut stmts = Vec::<String>::new();
+  let mut delimiter = ";".to_string();
+  let mut current = String::new();
+  for line in sql.lines() {
+    let trimmed = line.trim();
+    if current.is_empty() && (trimmed.is_empty() || trimmed.starts_with("--")) {
+      continue;
+    }
+    if let Some(d) = trimmed.strip_prefix("DELIMITER ") {
+      delimiter = d.trim().to_string();
+      continue;
+    }
+    if let Some(body) = trimmed.strip_suffix(delimiter.as_str()) {
+      current.push_str(body.trim_end());
+      stmts.push(current.trim().to_string());
+      current.clear();
+    }
+    else {
+      current.push_str(line);
+      current.push('\n');
+    }
+  }
+  if !current.trim().is_empty() {
+    stmts.push(current.trim().to_string());
+  }
+  stmts
+}
+
+impl MySQLDatabaseProxy {
+
+  pub fn schema_version(&mut self) -> u32 {
+    if let Err(err) = self.conn.query_drop("CREATE TABLE IF NOT EXISTS schema_version (version INT NOT NULL PRIMARY KEY, name VARCHAR(100) NOT NULL, applied_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP) ENGINE=InnoDB") {
+      panic!("MySQL error creating schema_version {:?}",err);
+    }
+    match self.conn.query_first::<Option<u32>,&str>("SELECT MAX(version) FROM schema_version") {
+      Ok(v) => v.flatten().unwrap_or(0),
+      Err(err) => {panic!("MySQL error getting schema version {:?}",err);}
+    }
+  }
+
+  pub fn migrate(&mut self) -> Vec<u32> {
+    self.migrate_to(latest_version())
+  }
+
+  pub fn migrate_to(&mut self, target : u32) -> Vec<u32> {
+    let current = self.schema_version();
+    let mut applied = Vec::<u32>::new();
+    for m in MIGRATIONS.iter().filter(|m| m.version > current && m.version <= target) {
+      for stmt in split_statements(m.sql) {
+        if let Err(err) = self.conn.query_drop(&stmt) {
+          panic!("MySQL error applying migration {} ({}) {:?}",m.version,m.name,err);
+        }
+      }
+      let res : Result::<Option::<Row>> = self.conn.exec_first("INSERT INTO schema_version (version,name) VALUES (:v,:n)", params!{
+        "v" => m.version,
+        "n" => m.name
+      });
+      if let Err(err) = res {
+        panic!("MySQL error recording migration {} {:?}",m.version,err);
+      }
+      applied.push(m.version);
+    }
+    applied
+  }
+}
+
+#[test]
+fn test_split_statements() {
+  let stmts = split_statements("-- A table\nCREATE TABLE x (\n  id INT\n);\n\nDELIMITER //\n\nCREATE PROCEDURE p()\nBEGIN\n  SELECT 1;\n  SELECT 2;\nEND //\n\nDELIMITER ;\nDROP TABLE x;\n");
+  assert_eq!(stmts.len(),3);
+  assert_eq!(stmts[0],"CREATE TABLE x (\n  id INT\n)");
+  assert_eq!(stmts[1],"CREATE PROCEDURE p()\nBEGIN\n  SELECT 1;\n  SELECT 2;\nEND");
+  assert_eq!(stmts[2],"DROP TABLE x");
+}
+
+#[test]
+fn test_migrations_are_ordered() {
+  for (idx,m) in MIGRATIONS.iter().enumerate() {
+    assert_eq!(m.version,idx as u32 + 1);
+    let stmts = split_statements(m.sql);
+    assert!(!stmts.is_empty());
+    assert!(stmts.iter().all(|s| !s.contains("DELIMITER")));
+  }
+  assert_eq!(latest_version(),5);
+}
+
+#[test]
+fn test_migrate() {
+  let mut p = initialise_db();
+  assert_eq!(p.schema_version(),latest_version());
+  assert_eq!(p.migrate(),Vec::<u32>::new());
+}
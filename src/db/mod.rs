@@ -1,4 +1,9 @@
 pub mod mysql;
+pub mod manager;
+pub mod migration;
 pub mod valuetype_db;
 pub mod projectsettings_db;
-pub mod environment_db;
\ No newline at end of file
+pub mod environment_db;
+pub mod asset_db;
+pub mod domainproperty_db;
+pub mod assetassociation_db;
@@ -1,8 +1,122 @@
 use mysql::*;
 use mysql::prelude::*;
 use dotenv;
-use std::process::Command;
 use std::env;
+use std::fmt;
+use crate::db::manager::DatabaseManager;
+use crate::dimensions::objectid::{ObjectId,AssetId,EnvironmentId,ValueTypeId,DomainPropertyId,AssetAssociationId};
+use crate::dimensions::tag::Tag;
+
+#[derive(Clone,Copy,PartialEq,Debug)]
+pub enum CommitAction {
+  Add,
+  Update
+}
+
+#[derive(Clone,Copy,PartialEq,Debug)]
+pub enum ObjectKind {
+  Environment,
+  VulnerabilityType,
+  DomainProperty,
+  ClassAssociation
+}
+
+impl ObjectKind {
+  pub fn new(kind : &str) -> ObjectKind {
+    match kind {
+      "environment" => ObjectKind::Environment,
+      "vulnerability_type" => ObjectKind::VulnerabilityType,
+      "domainproperty" => ObjectKind::DomainProperty,
+      "classassociation" => ObjectKind::ClassAssociation,
+      &_ => panic!("{} is not a deletable object kind",kind)
+    }
+  }
+
+  pub fn delete_procedure(&self) -> &'static str {
+    match self {
+      ObjectKind::Environment => "call delete_environment(:obj)",
+      ObjectKind::VulnerabilityType => "call delete_vulnerability_type(:obj)",
+      ObjectKind::DomainProperty => "call delete_domainproperty(:obj)",
+      ObjectKind::ClassAssociation => "call delete_classassociation(:obj)"
+    }
+  }
+}
+
+impl fmt::Display for ObjectKind {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f,"{}",
+      match self {
+        ObjectKind::Environment => "environment",
+        ObjectKind::VulnerabilityType => "vulnerability_type",
+        ObjectKind::DomainProperty => "domainproperty",
+        ObjectKind::ClassAssociation => "classassociation"
+      }
+    )
+  }
+}
+
+pub trait DeletableId : ObjectId {
+  const KIND : ObjectKind;
+}
+
+impl DeletableId for EnvironmentId {
+  const KIND : ObjectKind = ObjectKind::Environment;
+}
+
+impl DeletableId for ValueTypeId {
+  const KIND : ObjectKind = ObjectKind::VulnerabilityType;
+}
+
+impl DeletableId for DomainPropertyId {
+  const KIND : ObjectKind = ObjectKind::DomainProperty;
+}
+
+impl DeletableId for AssetAssociationId {
+  const KIND : ObjectKind = ObjectKind::ClassAssociation;
+}
+
+pub trait TaggedId : ObjectId {
+  const DIMENSION : &'static str;
+}
+
+impl TaggedId for AssetId {
+  const DIMENSION : &'static str = "asset";
+}
+
+impl TaggedId for DomainPropertyId {
+  const DIMENSION : &'static str = "domainproperty";
+}
+
+#[test]
+fn test_id_kinds() {
+  assert_eq!(EnvironmentId::KIND,ObjectKind::Environment);
+  assert_eq!(ValueTypeId::KIND,ObjectKind::VulnerabilityType);
+  assert_eq!(DomainPropertyId::KIND,ObjectKind::DomainProperty);
+  assert_eq!(AssetAssociationId::KIND,ObjectKind::ClassAssociation);
+  assert_eq!(AssetId::DIMENSION,"asset");
+  assert_eq!(DomainPropertyId::DIMENSION,"domainproperty");
+}
+
+#[test]
+fn test_object_kind() {
+  for kind in ["environment","vulnerability_type","domainproperty","classassociation"] {
+    let ok = ObjectKind::new(kind);
+    assert_eq!(ok.to_string(),kind);
+    assert_eq!(ok.delete_procedure(),format!("call delete_{}(:obj)",kind));
+  }
+}
+
+#[test]
+#[should_panic]
+fn test_object_kind_rejects_unknown_kind() {
+  ObjectKind::new("asset");
+}
+
+#[test]
+#[should_panic]
+fn test_object_kind_rejects_injection() {
+  ObjectKind::new("environment(1); drop table environment; call delete_environment");
+}
 
 pub struct MySQLDatabaseProxy {
   pub conn : PooledConn
@@ -10,58 +124,99 @@ pub struct MySQLDatabaseProxy {
 
 
 impl MySQLDatabaseProxy {
-  pub fn new(db_host: &String, db_port: &String, db_user: &String, db_passwd : &String, db_name : &String) -> MySQLDatabaseProxy {
+  pub fn new(db_host: &str, db_port: &str, db_user: &str, db_passwd : &str, db_name : &str) -> MySQLDatabaseProxy {
     let url = format!("mysql://{}:{}@{}:{}/{}",db_user,db_passwd,db_host,db_port,db_name);
     match Pool::new(url.as_str()) {
       Result::Ok(p) => {
         match p.get_conn() {
           Result::Ok(c) => {
-            return MySQLDatabaseProxy {conn: c};
+            MySQLDatabaseProxy {conn: c}
           },
           Result::Err(err) => {
             panic!("{:?}",err);
           }
-        };
+        }
       },
       Result::Err(err) => {
         panic!("{:?}",err);
       }
-    };
+    }
   }
 
   pub fn ok(&mut self) -> bool {
-    return self.conn.as_mut().ping();
+    self.conn.as_mut().ping().is_ok()
   }
 
 
-  pub fn new_id(&mut self) -> i128 {
-    let res = self.conn.query_map("call newId()",|nid| nid);
+  pub fn new_id<I : ObjectId>(&mut self) -> I {
+    let res = self.conn.query_map("call newId()",|nid : i128| I::new(nid));
     match res {
-      Ok(r) => { return r[0];}
+      Ok(r) => { r[0] }
       Err(err) => { panic!("{:?}",err);}
-    };
+    }
   }
 
-  pub fn delete_object(&mut self, objt_id : i128, table_name: &str) {
-    let sql_txt = format!("call delete_{}(:obj)",table_name);
-    let res : Result::<Option::<u128>> = self.conn.exec_first(sql_txt, params!{
-      "obj" => objt_id
+  pub fn delete_object<I : DeletableId>(&mut self, objt_id : I) {
+    let res : Result::<Option::<Row>> = self.conn.exec_first(I::KIND.delete_procedure(), params!{
+      "obj" => objt_id.value()
     });
     match res {
       Ok(_r) => {},
       Err(err) => {println!("{:?}",err);}
     }
   }
+
+  pub fn add_tags<I : TaggedId>(&mut self, objt_id : I, tags : &[Tag]) {
+    let dim_name = I::DIMENSION;
+    let res : Result::<Option::<Row>> = self.conn.exec_first("call deleteTags(:obj,:dim)", params!{
+      "obj" => objt_id.value(),
+      "dim" => dim_name
+    });
+    if let Err(err) = res {
+      panic!("MySQL error deleting {} tags {:?}",dim_name,err);
+    }
+    for t in tags {
+      let res : Result::<Option::<Row>> = self.conn.exec_first("call addTag(:obj,:tag,:dim)", params!{
+        "obj" => objt_id.value(),
+        "tag" => &t.name,
+        "dim" => dim_name
+      });
+      if let Err(err) = res {
+        panic!("MySQL error adding {} tag {:?}",dim_name,err);
+      }
+    }
+  }
+
+  pub fn get_tags<I : TaggedId>(&mut self, objt_id : I) -> Vec<Tag> {
+    let dim_name = I::DIMENSION;
+    let res = self.conn.exec_map("call getTags(:obj,:dim)", params!{
+      "obj" => objt_id.value(),
+      "dim" => dim_name
+    },| t_name : String | Tag::new(&t_name));
+    match res {
+      Ok(tags) => tags,
+      Err(err) => {panic!("MySQL error getting {} tags {:?}",dim_name,err);}
+    }
+  }
 }
   
-pub fn initialise_db() -> MySQLDatabaseProxy {
+pub fn connect_db() -> MySQLDatabaseProxy {
   dotenv::dotenv().ok();
-  Command::new(&env::var("RESET_SERVER").unwrap().as_str()).output().expect("Failed to initialise db");
-  MySQLDatabaseProxy::new(&env::var("DB_HOST").unwrap(),&env::var("DB_PORT").unwrap(), &env::var("DB_USER").unwrap(), &env::var("DB_PASSWD").unwrap(), &env::var("TEST_DB").unwrap())
+  let db_name = env::var("DB_NAME").or_else(|_| env::var("TEST_DB")).expect("Neither DB_NAME nor TEST_DB is set");
+  MySQLDatabaseProxy::new(&env::var("DB_HOST").unwrap(),&env::var("DB_PORT").unwrap(), &env::var("DB_USER").unwrap(), &env::var("DB_PASSWD").unwrap(), &db_name)
+}
+
+pub fn initialise_db() -> MySQLDatabaseProxy {
+  let mut m = DatabaseManager::from_env();
+  let db_name = env::var("TEST_DB").unwrap();
+  if m.exists(&db_name) {
+    m.drop_database(&db_name);
+  }
+  m.create_model_database(&db_name)
 }
 
 #[test]
 pub fn test_database_ping() {
   let mut p = initialise_db();
-  assert_eq!(p.ok(),true);
+  assert!(p.ok());
 }
\ No newline at end of file
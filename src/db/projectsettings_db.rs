@@ -8,7 +8,7 @@ impl MySQLDatabaseProxy {
   pub fn update_project_settings(&mut self, settings: &ProjectSettings) {
     let font_size = "7.5".to_string();
     let font_name = "Times New Roman".to_string();
-    let res : Result::<Option::<u128>> = self.conn.exec_first("call updateProjectSettings(:proj,:bg,:goals,:scope,:picture,:font_size,:font)", params!{
+    let res : Result::<Option::<Row>> = self.conn.exec_first("call updateProjectSettings(:proj,:bg,:goals,:scope,:picture,:font_size,:font)", params!{
       "proj" => &settings.name,
       "bg" => &settings.background,
       "goals" => &settings.strategic_goals,
@@ -22,7 +22,7 @@ impl MySQLDatabaseProxy {
       Err(err) => {panic!("MySQL error updating project settings {:?}",err);}
     }
  
-    if let Err(err) = self.conn.query_first::<u128,&str>("call deleteDictionary()") {
+    if let Err(err) = self.conn.query_first::<Row,&str>("call deleteDictionary()") {
       panic!("MySQL error deleting dictionary - {:?}",err);
     }
 
@@ -37,7 +37,7 @@ impl MySQLDatabaseProxy {
       }
     }
 
-    if let Err(err) = self.conn.query_first::<u128,&str>("call deleteContributors()") {
+    if let Err(err) = self.conn.query_first::<Row,&str>("call deleteContributors()") {
       panic!("MySQL error deleting contributors - {:?}",err);
     }
 
@@ -54,7 +54,7 @@ impl MySQLDatabaseProxy {
       }
     }
 
-    if let Err(err) = self.conn.query_first::<u128,&str>("call deleteRevisions()") {
+    if let Err(err) = self.conn.query_first::<Row,&str>("call deleteRevisions()") {
       panic!("MySQL error deleting revisions - {:?}",err);
     }
 
@@ -73,7 +73,7 @@ impl MySQLDatabaseProxy {
   }
 
   pub fn get_project_settings(&mut self) -> ProjectSettings {
-    let mut ps = ProjectSettings::new(&"".to_string());
+    let mut ps = ProjectSettings::new("");
     let res : Result<Vec<(String,String)>> = self.conn.query("call getProjectSettings()");
     match res {
       Ok(rows) => {
@@ -121,6 +121,6 @@ impl MySQLDatabaseProxy {
       },
       Err(err) => {panic!("MySQL error getting settings - {:?}",err);}
     }
-    return ps;
+    ps
   }
 }
@@ -0,0 +1,165 @@
+CREATE TABLE id_sequence (
+  id INT NOT NULL AUTO_INCREMENT PRIMARY KEY
+) ENGINE=InnoDB;
+
+CREATE TABLE project_setting (
+  name VARCHAR(100) NOT NULL PRIMARY KEY,
+  value LONGTEXT NOT NULL
+) ENGINE=InnoDB;
+
+INSERT INTO project_setting (name,value) VALUES
+  ('Project Name','New Project'),
+  ('Project Background',''),
+  ('Project Goals',''),
+  ('Project Scope',''),
+  ('Rich Picture',''),
+  ('Font Size','7.5'),
+  ('Font Name','Times New Roman');
+
+CREATE TABLE dictionary (
+  name VARCHAR(255) NOT NULL PRIMARY KEY,
+  value LONGTEXT NOT NULL
+) ENGINE=InnoDB;
+
+CREATE TABLE contributor (
+  first_name VARCHAR(255) NOT NULL,
+  surname VARCHAR(255) NOT NULL,
+  affiliation VARCHAR(255) NOT NULL,
+  role VARCHAR(255) NOT NULL
+) ENGINE=InnoDB;
+
+CREATE TABLE revision (
+  id VARCHAR(50) NOT NULL,
+  revision_date VARCHAR(50) NOT NULL,
+  description LONGTEXT NOT NULL
+) ENGINE=InnoDB;
+
+CREATE TABLE tag (
+  id INT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+  name VARCHAR(255) NOT NULL UNIQUE
+) ENGINE=InnoDB;
+
+CREATE TABLE object_tag (
+  object_id INT NOT NULL,
+  dimension VARCHAR(50) NOT NULL,
+  tag_id INT NOT NULL,
+  PRIMARY KEY (object_id,dimension,tag_id),
+  FOREIGN KEY (tag_id) REFERENCES tag(id)
+) ENGINE=InnoDB;
+
+CREATE TABLE value_type (
+  id INT NOT NULL PRIMARY KEY,
+  type VARCHAR(50) NOT NULL,
+  name VARCHAR(255) NOT NULL,
+  description LONGTEXT NOT NULL,
+  score INT NOT NULL DEFAULT 0,
+  rationale LONGTEXT NOT NULL,
+  UNIQUE (type,name)
+) ENGINE=InnoDB;
+
+DELIMITER //
+
+CREATE PROCEDURE newId()
+BEGIN
+  INSERT INTO id_sequence VALUES (NULL);
+  SELECT LAST_INSERT_ID();
+END //
+
+CREATE PROCEDURE updateProjectSettings(IN projName LONGTEXT, IN background LONGTEXT, IN goals LONGTEXT, IN scope LONGTEXT, IN picture LONGTEXT, IN fontSize LONGTEXT, IN fontName LONGTEXT)
+BEGIN
+  UPDATE project_setting SET value = projName WHERE name = 'Project Name';
+  UPDATE project_setting SET value = background WHERE name = 'Project Background';
+  UPDATE project_setting SET value = goals WHERE name = 'Project Goals';
+  UPDATE project_setting SET value = scope WHERE name = 'Project Scope';
+  UPDATE project_setting SET value = picture WHERE name = 'Rich Picture';
+  UPDATE project_setting SET value = fontSize WHERE name = 'Font Size';
+  UPDATE project_setting SET value = fontName WHERE name = 'Font Name';
+END //
+
+CREATE PROCEDURE getProjectSettings()
+BEGIN
+  SELECT name, value FROM project_setting;
+END //
+
+CREATE PROCEDURE deleteDictionary()
+BEGIN
+  DELETE FROM dictionary;
+END //
+
+CREATE PROCEDURE addDictionaryEntry(IN entryName VARCHAR(255), IN entryValue LONGTEXT)
+BEGIN
+  INSERT INTO dictionary (name,value) VALUES (entryName,entryValue);
+END //
+
+CREATE PROCEDURE getDictionary()
+BEGIN
+  SELECT name, value FROM dictionary ORDER BY name;
+END //
+
+CREATE PROCEDURE deleteContributors()
+BEGIN
+  DELETE FROM contributor;
+END //
+
+CREATE PROCEDURE addContributorEntry(IN firstName VARCHAR(255), IN surname VARCHAR(255), IN affiliation VARCHAR(255), IN contributorRole VARCHAR(255))
+BEGIN
+  INSERT INTO contributor (first_name,surname,affiliation,role) VALUES (firstName,surname,affiliation,contributorRole);
+END //
+
+CREATE PROCEDURE getContributors()
+BEGIN
+  SELECT first_name, surname, affiliation, role FROM contributor;
+END //
+
+CREATE PROCEDURE deleteRevisions()
+BEGIN
+  DELETE FROM revision;
+END //
+
+CREATE PROCEDURE addRevision(IN revId VARCHAR(50), IN revDate VARCHAR(50), IN revDesc LONGTEXT)
+BEGIN
+  INSERT INTO revision (id,revision_date,description) VALUES (revId,revDate,revDesc);
+END //
+
+CREATE PROCEDURE getRevisions()
+BEGIN
+  SELECT id, revision_date, description FROM revision;
+END //
+
+CREATE PROCEDURE deleteTags(IN objtId INT, IN dimName VARCHAR(50))
+BEGIN
+  DELETE FROM object_tag WHERE object_id = objtId AND dimension = dimName;
+END //
+
+CREATE PROCEDURE addTag(IN objtId INT, IN tagName VARCHAR(255), IN dimName VARCHAR(50))
+BEGIN
+  INSERT IGNORE INTO tag (name) VALUES (tagName);
+  INSERT IGNORE INTO object_tag (object_id,dimension,tag_id) SELECT objtId, dimName, id FROM tag WHERE name = tagName;
+END //
+
+CREATE PROCEDURE getTags(IN objtId INT, IN dimName VARCHAR(50))
+BEGIN
+  SELECT t.name FROM tag t, object_tag ot WHERE ot.tag_id = t.id AND ot.object_id = objtId AND ot.dimension = dimName ORDER BY t.name;
+END //
+
+CREATE PROCEDURE addValueType(IN vtId INT, IN vtName VARCHAR(255), IN vtDesc LONGTEXT, IN vtType VARCHAR(50), IN vtScore INT, IN vtRat LONGTEXT)
+BEGIN
+  INSERT INTO value_type (id,type,name,description,score,rationale) VALUES (vtId,vtType,vtName,vtDesc,vtScore,vtRat);
+END //
+
+CREATE PROCEDURE updateValueType(IN vtId INT, IN vtName VARCHAR(255), IN vtDesc LONGTEXT, IN vtType VARCHAR(50), IN vtScore INT, IN vtRat LONGTEXT)
+BEGIN
+  UPDATE value_type SET name = vtName, description = vtDesc, type = vtType, score = vtScore, rationale = vtRat WHERE id = vtId;
+END //
+
+CREATE PROCEDURE getCustomisableValues(IN dimName VARCHAR(50), IN envName VARCHAR(255))
+BEGIN
+  SELECT id, name, description, score, rationale FROM value_type WHERE type = dimName ORDER BY score, name;
+END //
+
+CREATE PROCEDURE delete_vulnerability_type(IN objtId INT)
+BEGIN
+  DELETE FROM value_type WHERE id = objtId AND type = 'vulnerability_type';
+END //
+
+DELIMITER ;
@@ -0,0 +1,72 @@
+CREATE TABLE environment (
+  id INT NOT NULL PRIMARY KEY,
+  name VARCHAR(255) NOT NULL UNIQUE,
+  short_code VARCHAR(50) NOT NULL,
+  description LONGTEXT NOT NULL
+) ENGINE=InnoDB;
+
+CREATE TABLE composite_environment (
+  composite_environment_id INT NOT NULL,
+  environment_id INT NOT NULL,
+  PRIMARY KEY (composite_environment_id,environment_id),
+  FOREIGN KEY (composite_environment_id) REFERENCES environment(id) ON DELETE CASCADE,
+  FOREIGN KEY (environment_id) REFERENCES environment(id) ON DELETE CASCADE
+) ENGINE=InnoDB;
+
+CREATE TABLE composite_environment_property (
+  environment_id INT NOT NULL PRIMARY KEY,
+  duplication_property VARCHAR(50) NOT NULL,
+  overriding_environment VARCHAR(255) NOT NULL,
+  FOREIGN KEY (environment_id) REFERENCES environment(id) ON DELETE CASCADE
+) ENGINE=InnoDB;
+
+DELIMITER //
+
+CREATE PROCEDURE addEnvironment(IN envId INT, IN envName VARCHAR(255), IN envShortCode VARCHAR(50), IN envDesc LONGTEXT)
+BEGIN
+  INSERT INTO environment (id,name,short_code,description) VALUES (envId,envName,envShortCode,envDesc);
+END //
+
+CREATE PROCEDURE updateEnvironment(IN envId INT, IN envName VARCHAR(255), IN envShortCode VARCHAR(50), IN envDesc LONGTEXT)
+BEGIN
+  UPDATE environment SET name = envName, short_code = envShortCode, description = envDesc WHERE id = envId;
+END //
+
+CREATE PROCEDURE deleteEnvironmentComponents(IN envId INT)
+BEGIN
+  DELETE FROM composite_environment WHERE composite_environment_id = envId;
+  DELETE FROM composite_environment_property WHERE environment_id = envId;
+END //
+
+CREATE PROCEDURE addCompositeEnvironment(IN envId INT, IN ceName VARCHAR(255))
+BEGIN
+  INSERT INTO composite_environment (composite_environment_id,environment_id) SELECT envId, id FROM environment WHERE name = ceName;
+END //
+
+CREATE PROCEDURE addCompositeEnvironmentProperties(IN envId INT, IN duplProperty VARCHAR(50), IN overridingEnv VARCHAR(255))
+BEGIN
+  INSERT INTO composite_environment_property (environment_id,duplication_property,overriding_environment) VALUES (envId,duplProperty,overridingEnv);
+END //
+
+CREATE PROCEDURE getEnvironments(IN constraintId INT)
+BEGIN
+  SELECT id, name, short_code, description FROM environment WHERE constraintId = -1 OR id = constraintId ORDER BY name;
+END //
+
+CREATE PROCEDURE compositeEnvironmentNames(IN envId INT)
+BEGIN
+  SELECT e.name FROM environment e, composite_environment ce WHERE ce.environment_id = e.id AND ce.composite_environment_id = envId ORDER BY e.name;
+END //
+
+CREATE PROCEDURE compositeEnvironmentProperties(IN envId INT)
+BEGIN
+  SELECT duplication_property, overriding_environment FROM composite_environment_property WHERE environment_id = envId;
+END //
+
+CREATE PROCEDURE delete_environment(IN objtId INT)
+BEGIN
+  DELETE FROM object_tag WHERE object_id = objtId AND dimension = 'environment';
+  DELETE FROM environment WHERE id = objtId;
+END //
+
+DELIMITER ;
@@ -0,0 +1,80 @@
+CREATE TABLE asset (
+  id INT NOT NULL PRIMARY KEY,
+  name VARCHAR(255) NOT NULL UNIQUE,
+  short_code VARCHAR(50) NOT NULL,
+  description LONGTEXT NOT NULL,
+  significance LONGTEXT NOT NULL,
+  asset_type VARCHAR(255) NOT NULL,
+  is_critical INT NOT NULL DEFAULT 0,
+  critical_rationale LONGTEXT NOT NULL
+) ENGINE=InnoDB;
+
+CREATE TABLE asset_property (
+  asset_id INT NOT NULL,
+  environment_id INT NOT NULL,
+  property VARCHAR(50) NOT NULL,
+  value VARCHAR(50) NOT NULL,
+  rationale LONGTEXT NOT NULL,
+  PRIMARY KEY (asset_id,environment_id,property),
+  FOREIGN KEY (asset_id) REFERENCES asset(id) ON DELETE CASCADE,
+  FOREIGN KEY (environment_id) REFERENCES environment(id) ON DELETE CASCADE
+) ENGINE=InnoDB;
+
+CREATE TABLE classassociation (
+  id INT NOT NULL PRIMARY KEY,
+  environment_id INT NOT NULL,
+  head_id INT NOT NULL,
+  head_dim VARCHAR(50) NOT NULL,
+  head_navigation INT NOT NULL DEFAULT 0,
+  head_adornment VARCHAR(50) NOT NULL,
+  head_multiplicity VARCHAR(10) NOT NULL,
+  head_role VARCHAR(255) NOT NULL,
+  tail_role VARCHAR(255) NOT NULL,
+  tail_multiplicity VARCHAR(10) NOT NULL,
+  tail_adornment VARCHAR(50) NOT NULL,
+  tail_navigation INT NOT NULL DEFAULT 0,
+  tail_dim VARCHAR(50) NOT NULL,
+  tail_id INT NOT NULL,
+  rationale LONGTEXT NOT NULL,
+  FOREIGN KEY (environment_id) REFERENCES environment(id) ON DELETE CASCADE,
+  FOREIGN KEY (head_id) REFERENCES asset(id) ON DELETE CASCADE,
+  FOREIGN KEY (tail_id) REFERENCES asset(id) ON DELETE CASCADE
+) ENGINE=InnoDB;
+
+DELIMITER //
+
+CREATE PROCEDURE getAssets(IN constraintId INT)
+BEGIN
+  SELECT id, name, short_code, description, significance, asset_type, is_critical, critical_rationale FROM asset WHERE constraintId = -1 OR id = constraintId ORDER BY name;
+END //
+
+CREATE PROCEDURE assetEnvironmentProperties(IN assetId INT)
+BEGIN
+  SELECT e.name, ap.property, ap.value, ap.rationale FROM asset_property ap, environment e WHERE ap.environment_id = e.id AND ap.asset_id = assetId ORDER BY e.name;
+END //
+
+CREATE PROCEDURE addClassAssociation(IN assocId INT, IN envName VARCHAR(255), IN headName VARCHAR(255), IN headDim VARCHAR(50), IN headNav INT, IN headAdorn VARCHAR(50), IN headMult VARCHAR(10), IN headRole VARCHAR(255), IN tailRole VARCHAR(255), IN tailMult VARCHAR(10), IN tailAdorn VARCHAR(50), IN tailNav INT, IN tailDim VARCHAR(50), IN tailName VARCHAR(255), IN assocRat LONGTEXT)
+BEGIN
+  INSERT INTO classassociation (id,environment_id,head_id,head_dim,head_navigation,head_adornment,head_multiplicity,head_role,tail_role,tail_multiplicity,tail_adornment,tail_navigation,tail_dim,tail_id,rationale)
+    SELECT assocId, e.id, h.id, headDim, headNav, headAdorn, headMult, headRole, tailRole, tailMult, tailAdorn, tailNav, tailDim, t.id, assocRat FROM environment e, asset h, asset t WHERE e.name = envName AND h.name = headName AND t.name = tailName;
+END //
+
+CREATE PROCEDURE updateClassAssociation(IN assocId INT, IN envName VARCHAR(255), IN headName VARCHAR(255), IN headDim VARCHAR(50), IN headNav INT, IN headAdorn VARCHAR(50), IN headMult VARCHAR(10), IN headRole VARCHAR(255), IN tailRole VARCHAR(255), IN tailMult VARCHAR(10), IN tailAdorn VARCHAR(50), IN tailNav INT, IN tailDim VARCHAR(50), IN tailName VARCHAR(255), IN assocRat LONGTEXT)
+BEGIN
+  DELETE FROM classassociation WHERE id = assocId;
+  CALL addClassAssociation(assocId,envName,headName,headDim,headNav,headAdorn,headMult,headRole,tailRole,tailMult,tailAdorn,tailNav,tailDim,tailName,assocRat);
+END //
+
+CREATE PROCEDURE getClassAssociations(IN constraintId INT)
+BEGIN
+  SELECT ca.id, e.name, h.name, ca.head_dim, ca.head_navigation, ca.head_adornment, ca.head_multiplicity, ca.head_role, ca.tail_role, ca.tail_multiplicity, ca.tail_adornment, ca.tail_navigation, ca.tail_dim, t.name, ca.rationale
+    FROM classassociation ca, environment e, asset h, asset t
+    WHERE ca.environment_id = e.id AND ca.head_id = h.id AND ca.tail_id = t.id AND (constraintId = -1 OR ca.id = constraintId);
+END //
+
+CREATE PROCEDURE delete_classassociation(IN objtId INT)
+BEGIN
+  DELETE FROM classassociation WHERE id = objtId;
+END //
+
+DELIMITER ;
@@ -0,0 +1,33 @@
+CREATE TABLE domainproperty (
+  id INT NOT NULL PRIMARY KEY,
+  name VARCHAR(255) NOT NULL UNIQUE,
+  short_code VARCHAR(50) NOT NULL,
+  definition LONGTEXT NOT NULL,
+  dp_type VARCHAR(50) NOT NULL,
+  originator VARCHAR(255) NOT NULL
+) ENGINE=InnoDB;
+
+DELIMITER //
+
+CREATE PROCEDURE addDomainProperty(IN dpId INT, IN dpName VARCHAR(255), IN dpShortCode VARCHAR(50), IN dpDef LONGTEXT, IN dpType VARCHAR(50), IN dpOrig VARCHAR(255))
+BEGIN
+  INSERT INTO domainproperty (id,name,short_code,definition,dp_type,originator) VALUES (dpId,dpName,dpShortCode,dpDef,dpType,dpOrig);
+END //
+
+CREATE PROCEDURE updateDomainProperty(IN dpId INT, IN dpName VARCHAR(255), IN dpShortCode VARCHAR(50), IN dpDef LONGTEXT, IN dpType VARCHAR(50), IN dpOrig VARCHAR(255))
+BEGIN
+  UPDATE domainproperty SET name = dpName, short_code = dpShortCode, definition = dpDef, dp_type = dpType, originator = dpOrig WHERE id = dpId;
+END //
+
+CREATE PROCEDURE getDomainProperties(IN constraintId INT)
+BEGIN
+  SELECT id, name, short_code, definition, dp_type, originator FROM domainproperty WHERE constraintId = -1 OR id = constraintId ORDER BY name;
+END //
+
+CREATE PROCEDURE delete_domainproperty(IN objtId INT)
+BEGIN
+  DELETE FROM object_tag WHERE object_id = objtId AND dimension = 'domainproperty';
+  DELETE FROM domainproperty WHERE id = objtId;
+END //
+
+DELIMITER ;
@@ -0,0 +1,30 @@
+CREATE TABLE value_type_dimension (
+  name VARCHAR(50) NOT NULL PRIMARY KEY,
+  is_scored BOOLEAN NOT NULL
+) ENGINE=InnoDB;
+
+INSERT INTO value_type_dimension (name,is_scored) VALUES
+  ('asset_type',FALSE),
+  ('threat_type',FALSE),
+  ('vulnerability_type',FALSE),
+  ('access_right',FALSE),
+  ('protocol',FALSE),
+  ('asset_value',TRUE),
+  ('threat_value',TRUE),
+  ('risk_class',TRUE),
+  ('countermeasure_value',TRUE),
+  ('severity',TRUE),
+  ('likelihood',TRUE),
+  ('capability',TRUE),
+  ('motivation',TRUE);
+
+DROP PROCEDURE IF EXISTS getCustomisableValues;
+
+DELIMITER //
+
+CREATE PROCEDURE getCustomisableValues(IN dimName VARCHAR(50), IN envName VARCHAR(255))
+BEGIN
+  SELECT v.id, v.name, v.description, CASE WHEN COALESCE(d.is_scored,FALSE) THEN v.score ELSE -1 END, v.rationale FROM value_type v LEFT JOIN value_type_dimension d ON d.name = v.type WHERE v.type = dimName ORDER BY v.score, v.name;
+END //
+
+DELIMITER ;
@@ -1,14 +1,15 @@
 use mysql::*;
 use mysql::prelude::*;
 use crate::db::mysql::*;
+use crate::dimensions::objectid::{ObjectId,ValueTypeId};
 use crate::dimensions::valuetype::ValueType;
 
 impl MySQLDatabaseProxy {
 
   pub fn add_value_type(&mut self, vt : &ValueType) {
-    let new_id = self.new_id();
-    let res : Result::<Option::<u128>> = self.conn.exec_first("call addValueType(:id,:name,:desc,:type,:score,:rat)", params!{
-      "id" => new_id,
+    let new_id : ValueTypeId = self.new_id();
+    let res : Result::<Option::<Row>> = self.conn.exec_first("call addValueType(:id,:name,:desc,:type,:score,:rat)", params!{
+      "id" => new_id.value(),
       "name" => &vt.name,
       "desc" => &vt.description,
       "type" => &vt.vt_type,
@@ -22,8 +23,12 @@ impl MySQLDatabaseProxy {
   }
 
   pub fn update_value_type(&mut self, vt : &ValueType) {
-    let res : Result::<Option::<u128>> = self.conn.exec_first("call updateValueType(:id,:name,:desc,:type,:score,:rat)", params!{
-      "id" => &vt.id,
+    let vt_id = match vt.id {
+      Some(i) => i,
+      None => {panic!("Cannot update unsaved value type {}",vt.name);}
+    };
+    let res : Result::<Option::<Row>> = self.conn.exec_first("call updateValueType(:id,:name,:desc,:type,:score,:rat)", params!{
+      "id" => vt_id.value(),
       "name" => &vt.name,
       "desc" => &vt.description,
       "type" => &vt.vt_type,
@@ -36,23 +41,23 @@ impl MySQLDatabaseProxy {
     }
   }
   
-  pub fn get_value_types(&mut self, dim_name : &String, env_name : &String) -> Vec<ValueType> {
+  pub fn get_value_types(&mut self, dim_name : &str, env_name : &str) -> Vec<ValueType> {
     let res = self.conn.exec_map("call getCustomisableValues(:dim,:env)", params!{
       "dim" => dim_name,
       "env" => env_name
     },| (type_id, type_name, type_desc, type_value, type_rat) : (i128,String,String,i128,String) | {
-      let mut vt = ValueType::new(&type_name,&type_desc,&"".to_string());
-      vt.id = type_id;
+      let mut vt = ValueType::new(&type_name,&type_desc,"");
+      vt.id = Some(ValueTypeId::new(type_id));
       vt.score = type_value;
       vt.rationale = type_rat;
-      vt.vt_type = dim_name.clone();
-      return vt;
+      vt.vt_type = dim_name.to_string();
+      vt
     });
-    return res.unwrap();
+    res.unwrap()
   }
 
-  pub fn delete_vulnerability_type(&mut self, objt_id : i128) {
-    self.delete_object(objt_id,"vulnerability_type");
+  pub fn delete_vulnerability_type(&mut self, objt_id : ValueTypeId) {
+    self.delete_object(objt_id);
   }
 
 }
@@ -61,13 +66,13 @@ impl MySQLDatabaseProxy {
 pub fn test_value_types() {
   let mut p = initialise_db();
 
-  let mut no_vts = p.get_value_types(&"vulnerability_type".to_string(),&"".to_string());
+  let mut no_vts = p.get_value_types("vulnerability_type","");
   assert_eq!(no_vts.len(),0);
 
-  let ivt = ValueType::new(&"AVT".to_string(),&"XXX".to_string(),&"vulnerability_type".to_string());
+  let ivt = ValueType::new("AVT","XXX","vulnerability_type");
   p.add_value_type(&ivt);
 
-  let ovts = p.get_value_types(&"vulnerability_type".to_string(),&"".to_string());
+  let ovts = p.get_value_types("vulnerability_type","");
   let ovt = &ovts[0]; 
   assert_eq!(ivt.name,ovt.name);
   assert_eq!(ivt.description,ovt.description);
@@ -76,7 +81,7 @@ pub fn test_value_types() {
   assert_eq!(ivt.rationale,ovt.rationale);
   assert_eq!(ivt.environment,ovt.environment);
 
-  p.delete_vulnerability_type(ovt.id);
-  no_vts = p.get_value_types(&"vulnerability_type".to_string(),&"".to_string());
+  p.delete_vulnerability_type(ovt.id.unwrap());
+  no_vts = p.get_value_types("vulnerability_type","");
   assert_eq!(no_vts.len(),0);
 }
\ No newline at end of file
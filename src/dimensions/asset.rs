@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::fmt;
+use crate::dimensions::dimension::{Dimension,Renamable,DimensionKind,Identified,Referencing,update_tags,value_type_name};
 use crate::dimensions::securityproperty::{SecurityProperty,SecurityPropertyValue,QualitativeValue};
+use crate::dimensions::objectid::AssetId;
 use crate::dimensions::tag::Tag;
 
 #[derive(Clone)]
@@ -25,29 +27,14 @@ impl AssetEnvironmentProperties {
       ]
     }
   }
+  pub fn properties(&self) -> &[SecurityPropertyValue] {
+    &self.properties
+  }
+
   pub fn update(&mut self, p_name : &str, p_value: &str, p_rationale : &str) {
-    let p_index =
-      match p_name {
-        "confidentiality" => SecurityProperty::Confidentiality as usize,
-        "integrity" => SecurityProperty::Integrity as usize,
-        "availability" => SecurityProperty::Availability as usize,
-        "accountability" => SecurityProperty::Accountability as usize,
-        "anonymity" => SecurityProperty::Anonymity as usize,
-        "pseudonymity" => SecurityProperty::Pseudonymity as usize,
-        "unlinkability" => SecurityProperty::Unlinkability as usize,
-        "unobservability" => SecurityProperty::Unobservability as usize,
-        &_ => panic!("{} is not a property value",p_name)
-      };
-    let mut prop = &mut self.properties[p_index];
-    
-    prop.value = 
-      match p_value {
-        "None" => QualitativeValue::None,
-        "Low" => QualitativeValue::Low,
-        "Medium" => QualitativeValue::Medium,
-        "High" => QualitativeValue::High,
-        &_ => panic!("{} is not a qualitative value",p_value)
-      };
+    let p_index = SecurityProperty::parse(p_name).unwrap_or_else(|e| panic!("{}",e)) as usize;
+    let prop = &mut self.properties[p_index];
+    prop.value = QualitativeValue::parse(p_value).unwrap_or_else(|e| panic!("{}",e));
     prop.rationale = p_rationale.to_string();
   }
 }
@@ -80,7 +67,7 @@ fn test_update_asset_environment_properties() {
 
 #[derive(Clone)]
 pub struct Asset {
-  id : i128,
+  id : Option<AssetId>,
   name : String,
   short_code : String,
   asset_type : String,
@@ -93,12 +80,12 @@ pub struct Asset {
 }
 
 impl Asset {
-  pub fn new(a_name : &String, s_code : &String, a_type : &String, i_c: bool) -> Asset {
+  pub fn new(a_name : &str, s_code : &str, a_type : &str, i_c: bool) -> Asset {
     Asset{
-      id : -1,
-      name : a_name.clone(), 
-      short_code : s_code.clone(), 
-      asset_type : a_type.clone(), 
+      id : None,
+      name : a_name.to_string(), 
+      short_code : s_code.to_string(), 
+      asset_type : a_type.to_string(), 
       is_critical : i_c, 
       critical_rationale : "".to_string(), 
       description : "".to_string(), 
@@ -107,11 +94,43 @@ impl Asset {
       environment_properties : HashMap::<String,AssetEnvironmentProperties>::new()}  
   }
 
-  pub fn add_environment(&mut self, env_name: &String) {
-    self.environment_properties.insert(env_name.clone(), AssetEnvironmentProperties::new(env_name));
+  pub fn set_id(&mut self, a_id : AssetId) {
+    self.id = Some(a_id);
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn short_code(&self) -> &str {
+    &self.short_code
   }
 
-  pub fn update_security_property(&mut self,env_name : &String, p_name: &str, p_value: &str, p_rationale : &str) {
+  pub fn set_short_code(&mut self, s_code : &str) {
+    self.short_code = s_code.to_string();
+  }
+
+  pub fn asset_type(&self) -> &str {
+    &self.asset_type
+  }
+
+  pub fn set_asset_type(&mut self, a_type : &str) {
+    self.asset_type = a_type.to_string();
+  }
+
+  pub fn is_critical(&self) -> bool {
+    self.is_critical
+  }
+
+  pub fn set_critical(&mut self, i_c : bool) {
+    self.is_critical = i_c;
+  }
+
+  pub fn add_environment(&mut self, env_name: &str) {
+    self.environment_properties.insert(env_name.to_string(), AssetEnvironmentProperties::new(env_name));
+  }
+
+  pub fn update_security_property(&mut self,env_name : &str, p_name: &str, p_value: &str, p_rationale : &str) {
     if let Some(x) = self.environment_properties.get_mut(env_name) {
       x.update(p_name,p_value,p_rationale);
     } 
@@ -126,13 +145,96 @@ impl fmt::Display for Asset {
   }
 }
 
+impl Dimension for Asset {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::Asset
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn short_code(&self) -> Option<&str> {
+    Some(&self.short_code)
+  }
+
+  fn tags(&self) -> &[Tag] {
+    &self.tags
+  }
+
+  fn environment_names(&self) -> Vec<String> {
+    let mut env_names = self.environment_properties.keys().cloned().collect::<Vec<String>>();
+    env_names.sort();
+    env_names
+  }
+}
+
+impl Renamable for Asset {
+  fn set_name(&mut self, new_name : &str) {
+    self.name = new_name.to_string();
+  }
+}
+
+impl Identified for Asset {
+  type Id = AssetId;
+
+  fn id(&self) -> Option<AssetId> {
+    self.id
+  }
+}
+
+impl Referencing for Asset {
+  fn defined_by_environments(&self) -> bool {
+    true
+  }
+
+  fn references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    match kind {
+      DimensionKind::Environment => usize::from(self.environment_properties.contains_key(objt_name)),
+      DimensionKind::ValueType => usize::from(value_type_name(objt_name,"asset_type") == Some(self.asset_type.as_str())),
+      DimensionKind::Tag => self.tags.iter().filter(|t| t.name == objt_name).count(),
+      _ => 0
+    }
+  }
+
+  fn requires(&self, kind : DimensionKind, objt_name : &str) -> bool {
+    kind == DimensionKind::ValueType && self.references(kind,objt_name) > 0
+  }
+
+  fn update_references(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    match kind {
+      DimensionKind::Environment => {
+        match self.environment_properties.remove(old_name) {
+          Some(mut aep) => {
+            if let Some(n) = new_name {
+              aep.name = n.to_string();
+              self.environment_properties.insert(n.to_string(),aep);
+            }
+            1
+          },
+          None => 0
+        }
+      },
+      DimensionKind::ValueType => {
+        if self.references(kind,old_name) == 0 {
+          return 0;
+        }
+        self.asset_type = new_name.and_then(|n| value_type_name(n,"asset_type")).unwrap_or("").to_string();
+        1
+      },
+      DimensionKind::Tag => update_tags(&mut self.tags,old_name,new_name),
+      _ => 0
+    }
+  }
+}
+
 #[test]
 fn test_create_asset() {
-  let a = Asset::new(&"An asset".to_string(),&"SC".to_string(),&"Information".to_string(),false);
+  let a = Asset::new("An asset","SC","Information",false);
   assert_eq!(a.name,"An asset".to_string());
   assert_eq!(a.short_code,"SC".to_string());
   assert_eq!(a.asset_type,"Information".to_string());
-  assert_eq!(a.is_critical,false);
+  assert!(!a.is_critical);
   assert_eq!(a.description,"".to_string());
   assert_eq!(a.significance,"".to_string());
   assert_eq!(a.critical_rationale,"".to_string());
@@ -140,10 +242,10 @@ fn test_create_asset() {
 
 #[test]
 fn test_asset_add_environment() {
-  let mut a = Asset::new(&"An asset".to_string(),&"SC".to_string(),&"Information".to_string(),false);
-  a.add_environment(&"Default".to_string());
-  assert_eq!(a.environment_properties.contains_key(&"Default".to_string()),true);
-  if let Some(x) = a.environment_properties.get_mut(&"Default".to_string()) {
+  let mut a = Asset::new("An asset","SC","Information",false);
+  a.add_environment("Default");
+  assert!(a.environment_properties.contains_key("Default"));
+  if let Some(x) = a.environment_properties.get_mut("Default") {
     assert_eq!(x.name,"Default".to_string());
     assert_eq!(x.properties[SecurityProperty::Confidentiality as usize].name,SecurityProperty::Confidentiality);
     assert_eq!(x.properties[SecurityProperty::Confidentiality as usize].value,QualitativeValue::None);
@@ -156,11 +258,11 @@ fn test_asset_add_environment() {
 
 #[test]
 fn test_asset_update_security_property() {
-  let mut a = Asset::new(&"An asset".to_string(),&"SC".to_string(),&"Information".to_string(),false);
-  a.add_environment(&"Default".to_string());
-  a.update_security_property(&"Default".to_string(), "confidentiality", "Low", "Low C TBC");
-  a.update_security_property(&"Default".to_string(), "integrity", "High", "High I TBC");
-  if let Some(x) = a.environment_properties.get_mut(&"Default".to_string()) {
+  let mut a = Asset::new("An asset","SC","Information",false);
+  a.add_environment("Default");
+  a.update_security_property("Default", "confidentiality", "Low", "Low C TBC");
+  a.update_security_property("Default", "integrity", "High", "High I TBC");
+  if let Some(x) = a.environment_properties.get_mut("Default") {
     assert_eq!(x.name,"Default".to_string());
     assert_eq!(x.properties[SecurityProperty::Confidentiality as usize].name,SecurityProperty::Confidentiality);
     assert_eq!(x.properties[SecurityProperty::Confidentiality as usize].value,QualitativeValue::Low);
@@ -170,3 +272,105 @@ fn test_asset_update_security_property() {
     assert_eq!(x.properties[SecurityProperty::Integrity as usize].rationale,"High I TBC".to_string());
   }
 }
+
+pub struct AssetBuilder {
+  asset : Asset,
+  environments : Vec<String>,
+  properties : Vec<(String,String,String,String)>
+}
+
+impl AssetBuilder {
+  pub fn new(a_name : &str) -> AssetBuilder {
+    AssetBuilder{asset : Asset::new(a_name,"","",false), environments : Vec::<String>::new(), properties : Vec::<(String,String,String,String)>::new()}
+  }
+
+  pub fn short_code(mut self, s_code : &str) -> AssetBuilder {
+    self.asset.short_code = s_code.to_string();
+    self
+  }
+
+  pub fn asset_type(mut self, a_type : &str) -> AssetBuilder {
+    self.asset.asset_type = a_type.to_string();
+    self
+  }
+
+  pub fn critical(mut self, rationale : &str) -> AssetBuilder {
+    self.asset.is_critical = true;
+    self.asset.critical_rationale = rationale.to_string();
+    self
+  }
+
+  pub fn description(mut self, desc : &str) -> AssetBuilder {
+    self.asset.description = desc.to_string();
+    self
+  }
+
+  pub fn significance(mut self, sig : &str) -> AssetBuilder {
+    self.asset.significance = sig.to_string();
+    self
+  }
+
+  pub fn tags(mut self, tag_names : &[&str]) -> AssetBuilder {
+    self.asset.tags.extend(tag_names.iter().map(|t| Tag::new(t)));
+    self
+  }
+
+  pub fn environment(mut self, env_name : &str) -> AssetBuilder {
+    self.environments.push(env_name.to_string());
+    self
+  }
+
+  pub fn property(mut self, env_name : &str, p_name : &str, p_value : &str, p_rationale : &str) -> AssetBuilder {
+    self.properties.push((env_name.to_string(),p_name.to_string(),p_value.to_string(),p_rationale.to_string()));
+    self
+  }
+
+  pub fn build(self) -> Result<Asset,String> {
+    let mut a = self.asset;
+    if a.name.is_empty() {
+      return Err("Asset has no name".to_string());
+    }
+    if a.short_code.is_empty() {
+      return Err(format!("Asset {} has no short code",a.name));
+    }
+    if a.asset_type.is_empty() {
+      return Err(format!("Asset {} has no type",a.name));
+    }
+    for env_name in &self.environments {
+      if a.environment_properties.contains_key(env_name) {
+        return Err(format!("Asset {} has environment {} more than once",a.name,env_name));
+      }
+      a.add_environment(env_name);
+    }
+    for (env_name,p_name,p_value,p_rationale) in &self.properties {
+      SecurityProperty::parse(p_name).map_err(|e| format!("Asset {}: {}",a.name,e))?;
+      QualitativeValue::parse(p_value).map_err(|e| format!("Asset {}: {}",a.name,e))?;
+      if !a.environment_properties.contains_key(env_name) {
+        a.add_environment(env_name);
+      }
+      a.update_security_property(env_name,p_name,p_value,p_rationale);
+    }
+    Ok(a)
+  }
+}
+
+#[test]
+fn test_asset_builder() {
+  let a = AssetBuilder::new("Clinical data").short_code("CD").asset_type("Information").critical("Patient safety").description("Data about patients").tags(&["pii","clinical"]).environment("Night").property("Day","confidentiality","High","Personal data").property("Day","integrity","Medium","Diagnoses").build().unwrap();
+  assert_eq!(a.name(),"Clinical data");
+  assert!(a.is_critical());
+  assert_eq!(a.critical_rationale,"Patient safety".to_string());
+  assert_eq!(a.tags.len(),2);
+  assert_eq!(a.environment_properties.len(),2);
+  assert_eq!(a.environment_properties["Night"].properties[0].value,QualitativeValue::None);
+  assert_eq!(a.environment_properties["Day"].properties[0].value,QualitativeValue::High);
+  assert_eq!(a.environment_properties["Day"].properties[1].value,QualitativeValue::Medium);
+}
+
+#[test]
+fn test_asset_builder_validates() {
+  assert_eq!(AssetBuilder::new("Data").asset_type("Information").build().err(),Some("Asset Data has no short code".to_string()));
+  assert_eq!(AssetBuilder::new("Data").short_code("D").asset_type("Information").property("Day","authenticity","High","").build().err(),Some("Asset Data: authenticity is not a security property".to_string()));
+  assert_eq!(AssetBuilder::new("Data").short_code("D").asset_type("Information").property("Day","integrity","Severe","").build().err(),Some("Asset Data: Severe is not a qualitative value".to_string()));
+  assert_eq!(AssetBuilder::new("Data").short_code("D").asset_type("Information").environment("Day").environment("Day").build().err(),Some("Asset Data has environment Day more than once".to_string()));
+}
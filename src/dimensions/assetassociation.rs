@@ -0,0 +1,209 @@
+use std::fmt;
+use crate::dimensions::dimension::{Dimension,DimensionKind,Identified,Referencing,update_name};
+use crate::dimensions::objectid::AssetAssociationId;
+
+#[derive(Clone,PartialEq,Debug)]
+pub enum Adornment {
+  Association,
+  Aggregation,
+  Composition,
+  Inheritance
+}
+
+impl Adornment {
+  pub fn new(a_name : &str) -> Adornment {
+    match a_name {
+      "Association" | "association" => Adornment::Association,
+      "Aggregation" | "aggregation" => Adornment::Aggregation,
+      "Composition" | "composition" => Adornment::Composition,
+      "Inheritance" | "inheritance" => Adornment::Inheritance,
+      &_ => panic!("{} is not an adornment",a_name)
+    }
+  }
+}
+
+impl fmt::Display for Adornment {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Adornment::Association => write!(f,"Association"),
+      Adornment::Aggregation => write!(f,"Aggregation"),
+      Adornment::Composition => write!(f,"Composition"),
+      Adornment::Inheritance => write!(f,"Inheritance")
+    }
+  }
+}
+
+#[test]
+fn test_new_adornment() {
+  assert_eq!(Adornment::new("Composition"),Adornment::Composition);
+  assert_eq!(Adornment::new("aggregation"),Adornment::Aggregation);
+}
+
+#[test]
+#[should_panic]
+fn test_new_adornment_panics() {
+  Adornment::new("Dependency");
+}
+
+#[derive(Clone,PartialEq,Debug)]
+pub struct AssetAssociation {
+  pub id : Option<AssetAssociationId>,
+  pub environment : String,
+  pub head_asset : String,
+  pub head_navigation : bool,
+  pub head_adornment : Adornment,
+  pub head_multiplicity : String,
+  pub head_role : String,
+  pub tail_role : String,
+  pub tail_multiplicity : String,
+  pub tail_adornment : Adornment,
+  pub tail_navigation : bool,
+  pub tail_asset : String,
+  pub rationale : String
+}
+
+impl AssetAssociation {
+  pub fn new(env_name : &str, head_name : &str, tail_name : &str) -> AssetAssociation {
+    AssetAssociation {
+      id: None,
+      environment: env_name.to_string(),
+      head_asset: head_name.to_string(),
+      head_navigation: false,
+      head_adornment: Adornment::Association,
+      head_multiplicity: "1".to_string(),
+      head_role: "".to_string(),
+      tail_role: "".to_string(),
+      tail_multiplicity: "1".to_string(),
+      tail_adornment: Adornment::Association,
+      tail_navigation: false,
+      tail_asset: tail_name.to_string(),
+      rationale: "".to_string()
+    }
+  }
+}
+
+impl fmt::Display for AssetAssociation {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f,"Environment: {}, Head: {} ({}, {}, {}, {}), Tail: {} ({}, {}, {}, {}), Rationale: {}",
+      self.environment,
+      self.head_asset,self.head_adornment,self.head_multiplicity,self.head_role,self.head_navigation,
+      self.tail_asset,self.tail_adornment,self.tail_multiplicity,self.tail_role,self.tail_navigation,
+      self.rationale)
+  }
+}
+
+impl Dimension for AssetAssociation {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::AssetAssociation
+  }
+
+  fn name(&self) -> &str {
+    &self.head_asset
+  }
+
+  fn environment_names(&self) -> Vec<String> {
+    vec![self.environment.clone()]
+  }
+}
+
+impl Identified for AssetAssociation {
+  type Id = AssetAssociationId;
+
+  fn id(&self) -> Option<AssetAssociationId> {
+    self.id
+  }
+}
+
+impl Referencing for AssetAssociation {
+  fn references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    match kind {
+      DimensionKind::Environment => usize::from(self.environment == objt_name),
+      DimensionKind::Asset => usize::from(self.head_asset == objt_name) + usize::from(self.tail_asset == objt_name),
+      _ => 0
+    }
+  }
+
+  fn requires(&self, kind : DimensionKind, objt_name : &str) -> bool {
+    self.references(kind,objt_name) > 0
+  }
+
+  fn update_references(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    match kind {
+      DimensionKind::Environment => update_name(&mut self.environment,old_name,new_name),
+      DimensionKind::Asset => update_name(&mut self.head_asset,old_name,new_name) + update_name(&mut self.tail_asset,old_name,new_name),
+      _ => 0
+    }
+  }
+}
+
+#[test]
+fn test_new_asset_association() {
+  let aa = AssetAssociation::new("Default","Server","Data");
+  assert_eq!(aa.id,None);
+  assert_eq!(aa.environment,"Default".to_string());
+  assert_eq!(aa.head_asset,"Server".to_string());
+  assert_eq!(aa.tail_asset,"Data".to_string());
+  assert_eq!(aa.head_adornment,Adornment::Association);
+  assert_eq!(aa.tail_adornment,Adornment::Association);
+  assert_eq!(aa.head_multiplicity,"1".to_string());
+  assert_eq!(aa.tail_multiplicity,"1".to_string());
+  assert!(!aa.head_navigation);
+  assert!(!aa.tail_navigation);
+}
+
+#[derive(Clone)]
+pub struct AssetModel {
+  pub environment : String,
+  pub assets : Vec<String>,
+  pub associations : Vec<AssetAssociation>
+}
+
+impl AssetModel {
+  pub fn new(env_name : &str, assocs : &[AssetAssociation]) -> AssetModel {
+    let mut am = AssetModel { environment: env_name.to_string(), assets: Vec::<String>::new(), associations: Vec::<AssetAssociation>::new() };
+    for aa in assocs.iter().filter(|aa| aa.environment == env_name) {
+      for a in [&aa.head_asset,&aa.tail_asset] {
+        if !am.assets.contains(a) {
+          am.assets.push(a.clone());
+        }
+      }
+      am.associations.push(aa.clone());
+    }
+    am
+  }
+
+  pub fn neighbours(&self, asset_name : &str) -> Vec<String> {
+    let mut ns = Vec::<String>::new();
+    for aa in &self.associations {
+      let n =
+        if aa.head_asset == asset_name { &aa.tail_asset }
+        else if aa.tail_asset == asset_name { &aa.head_asset }
+        else { continue };
+      if !ns.contains(n) {
+        ns.push(n.clone());
+      }
+    }
+    ns
+  }
+}
+
+impl fmt::Display for AssetModel {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let assocs = &self.associations.iter().map(|aa| aa.to_string()).collect::<Vec<String>>().join(",");
+    write!(f,"Environment: {}, Assets: {}, Associations: {}",self.environment,self.assets.join(","),assocs)
+  }
+}
+
+#[test]
+fn test_new_asset_model() {
+  let mut server_data = AssetAssociation::new("Day","Server","Data");
+  server_data.head_adornment = Adornment::Composition;
+  let server_logs = AssetAssociation::new("Day","Logs","Server");
+  let night_only = AssetAssociation::new("Night","Server","Backup");
+  let am = AssetModel::new("Day",&[server_data,server_logs,night_only]);
+  assert_eq!(am.environment,"Day".to_string());
+  assert_eq!(am.assets,vec!["Server".to_string(),"Data".to_string(),"Logs".to_string()]);
+  assert_eq!(am.associations.len(),2);
+  assert_eq!(am.neighbours("Server"),vec!["Data".to_string(),"Logs".to_string()]);
+  assert_eq!(am.neighbours("Backup").len(),0);
+}
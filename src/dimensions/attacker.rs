@@ -1,4 +1,5 @@
 use std::fmt;
+use crate::dimensions::dimension::{Dimension,Renamable,DimensionKind,Referencing,count_names,update_names,update_tags,value_type_name};
 use crate::dimensions::tag::Tag;
 
 #[derive(Clone,PartialEq)]
@@ -10,14 +11,18 @@ pub struct AttackerEnvironment {
 }
 
 impl AttackerEnvironment {
-  pub fn new(env_name : &String) -> AttackerEnvironment {
-    AttackerEnvironment { name: env_name.clone(), roles: Vec::<String>::new(), motivations: Vec::<String>::new(), capabilities: Vec::<String>::new() }
+  pub fn new(env_name : &str) -> AttackerEnvironment {
+    AttackerEnvironment { name: env_name.to_string(), roles: Vec::<String>::new(), motivations: Vec::<String>::new(), capabilities: Vec::<String>::new() }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
   }
 }
 
 #[test]
 fn test_new_attacker_environment() {
-  let ae = AttackerEnvironment::new(&"Default".to_string());
+  let ae = AttackerEnvironment::new("Default");
   assert_eq!(ae.name,"Default".to_string());
   assert_eq!(ae.roles.len(),0);
   assert_eq!(ae.motivations.len(),0);
@@ -40,20 +45,104 @@ pub struct Attacker {
 }
 
 impl Attacker {
-  pub fn new(attacker_name : &String, attacker_image : &String) -> Attacker {
+  pub fn new(attacker_name : &str, attacker_image : &str) -> Attacker {
     Attacker { 
-      name: attacker_name.clone(), 
-      image: attacker_image.clone(),
+      name: attacker_name.to_string(), 
+      image: attacker_image.to_string(),
       description: "".to_string(), 
       tags: Vec::<Tag>::new(), 
       environments: Vec::<AttackerEnvironment>::new() 
     }
   }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn image(&self) -> &str {
+    &self.image
+  }
+
+  pub fn set_image(&mut self, attacker_image : &str) {
+    self.image = attacker_image.to_string();
+  }
+}
+
+impl Dimension for Attacker {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::Attacker
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn tags(&self) -> &[Tag] {
+    &self.tags
+  }
+
+  fn environment_names(&self) -> Vec<String> {
+    self.environments.iter().map(|ae| ae.name.clone()).collect()
+  }
+}
+
+impl Renamable for Attacker {
+  fn set_name(&mut self, new_name : &str) {
+    self.name = new_name.to_string();
+  }
+}
+
+impl Referencing for Attacker {
+  fn defined_by_environments(&self) -> bool {
+    true
+  }
+
+  fn references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    match kind {
+      DimensionKind::Environment => self.environments.iter().filter(|ae| ae.name == objt_name).count(),
+      DimensionKind::Role => self.environments.iter().map(|ae| count_names(&ae.roles,objt_name)).sum(),
+      DimensionKind::ValueType => {
+        let motives = value_type_name(objt_name,"motivation").map(|m| self.environments.iter().map(|ae| count_names(&ae.motivations,m)).sum()).unwrap_or(0);
+        let caps = value_type_name(objt_name,"capability").map(|c| self.environments.iter().map(|ae| count_names(&ae.capabilities,c)).sum()).unwrap_or(0);
+        motives + caps
+      },
+      DimensionKind::Tag => self.tags.iter().filter(|t| t.name == objt_name).count(),
+      _ => 0
+    }
+  }
+
+  fn update_references(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    match kind {
+      DimensionKind::Environment => {
+        let count = self.references(kind,old_name);
+        match new_name {
+          Some(n) => self.environments.iter_mut().filter(|ae| ae.name == old_name).for_each(|ae| ae.name = n.to_string()),
+          None => self.environments.retain(|ae| ae.name != old_name)
+        }
+        count
+      },
+      DimensionKind::Role => self.environments.iter_mut().map(|ae| update_names(&mut ae.roles,old_name,new_name)).sum(),
+      DimensionKind::ValueType => {
+        let mut count = 0;
+        for ae in self.environments.iter_mut() {
+          if let Some(m) = value_type_name(old_name,"motivation") {
+            count += update_names(&mut ae.motivations,m,new_name.and_then(|n| value_type_name(n,"motivation")));
+          }
+          if let Some(c) = value_type_name(old_name,"capability") {
+            count += update_names(&mut ae.capabilities,c,new_name.and_then(|n| value_type_name(n,"capability")));
+          }
+        }
+        count
+      },
+      DimensionKind::Tag => update_tags(&mut self.tags,old_name,new_name),
+      _ => 0
+    }
+  }
 }
 
 #[test]
 fn test_new_attacker() {
-  let a = Attacker::new(&"Peppa".to_string(),&"".to_string());
+  let a = Attacker::new("Peppa","");
   assert_eq!(a.name,"Peppa".to_string());
   assert_eq!(a.image,"".to_string());
   assert_eq!(a.description,"".to_string());
@@ -68,3 +157,63 @@ impl fmt::Display for Attacker {
     write!(f,"Name: {}, Image: {}, Description: {}, Tags: {}, Environments: {}",self.name,self.image,self.description,tags,envs)
   }
 }
+
+pub struct AttackerBuilder {
+  attacker : Attacker
+}
+
+impl AttackerBuilder {
+  pub fn new(attacker_name : &str) -> AttackerBuilder {
+    AttackerBuilder{attacker : Attacker::new(attacker_name,"")}
+  }
+
+  pub fn image(mut self, attacker_image : &str) -> AttackerBuilder {
+    self.attacker.image = attacker_image.to_string();
+    self
+  }
+
+  pub fn description(mut self, desc : &str) -> AttackerBuilder {
+    self.attacker.description = desc.to_string();
+    self
+  }
+
+  pub fn tags(mut self, tag_names : &[&str]) -> AttackerBuilder {
+    self.attacker.tags.extend(tag_names.iter().map(|t| Tag::new(t)));
+    self
+  }
+
+  pub fn environment(mut self, env_name : &str, roles : &[&str], motivations : &[&str], capabilities : &[&str]) -> AttackerBuilder {
+    let mut ae = AttackerEnvironment::new(env_name);
+    ae.roles = roles.iter().map(|r| r.to_string()).collect();
+    ae.motivations = motivations.iter().map(|m| m.to_string()).collect();
+    ae.capabilities = capabilities.iter().map(|c| c.to_string()).collect();
+    self.attacker.environments.push(ae);
+    self
+  }
+
+  pub fn build(self) -> Result<Attacker,String> {
+    let a = self.attacker;
+    if a.name.is_empty() {
+      return Err("Attacker has no name".to_string());
+    }
+    for (idx,ae) in a.environments.iter().enumerate() {
+      if a.environments[..idx].iter().any(|x| x.name == ae.name) {
+        return Err(format!("Attacker {} has environment {} more than once",a.name,ae.name));
+      }
+      if ae.roles.is_empty() {
+        return Err(format!("Attacker {} has no roles in environment {}",a.name,ae.name));
+      }
+    }
+    Ok(a)
+  }
+}
+
+#[test]
+fn test_attacker_builder() {
+  let a = AttackerBuilder::new("Carol").image("carol.jpg").description("Disgruntled researcher").tags(&["insider"]).environment("Day",&["Researcher"],&["Revenge"],&["Knowledge/Methods"]).environment("Night",&["Researcher"],&[],&[]).build().unwrap();
+  assert_eq!(a.image(),"carol.jpg");
+  assert_eq!(a.environments.len(),2);
+  assert_eq!(a.environments[0].motivations,vec!["Revenge".to_string()]);
+  assert_eq!(AttackerBuilder::new("Carol").environment("Day",&[],&[],&[]).build().err(),Some("Attacker Carol has no roles in environment Day".to_string()));
+  assert_eq!(AttackerBuilder::new("Carol").environment("Day",&["R"],&[],&[]).environment("Day",&["R"],&[],&[]).build().err(),Some("Attacker Carol has environment Day more than once".to_string()));
+}
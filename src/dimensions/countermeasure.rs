@@ -0,0 +1,251 @@
+use std::fmt;
+use crate::dimensions::dimension::{Dimension,Renamable,DimensionKind,Referencing,count_names,update_names,update_tags};
+use crate::dimensions::securityproperty::{SecurityPropertyValue,QualitativeValue};
+use crate::dimensions::tag::Tag;
+
+#[derive(Clone,PartialEq)]
+pub struct CountermeasureTarget {
+  pub name : String,
+  pub effectiveness : QualitativeValue,
+  pub rationale : String
+}
+
+impl CountermeasureTarget {
+  pub fn new(t_name : &str, t_eff : &str, t_rat : &str) -> CountermeasureTarget {
+    CountermeasureTarget {
+      name: t_name.to_string(),
+      effectiveness: QualitativeValue::parse(t_eff).unwrap_or_else(|e| panic!("{}",e)),
+      rationale: t_rat.to_string()
+    }
+  }
+}
+
+impl fmt::Display for CountermeasureTarget {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f,"Target: {}, Effectiveness: {:?}, Rationale: {}",self.name,self.effectiveness,self.rationale)
+  }
+}
+
+#[test]
+fn test_new_countermeasure_target() {
+  let t = CountermeasureTarget::new("SQL injection","Medium","Inputs are sanitised");
+  assert_eq!(t.name,"SQL injection".to_string());
+  assert_eq!(t.effectiveness,QualitativeValue::Medium);
+  assert_eq!(t.rationale,"Inputs are sanitised".to_string());
+}
+
+#[test]
+#[should_panic]
+fn test_new_countermeasure_target_panics() {
+  CountermeasureTarget::new("SQL injection","Very high","TBC");
+}
+
+#[derive(Clone)]
+pub struct CountermeasureEnvironment {
+  name : String,
+  pub cost : QualitativeValue,
+  pub requirements : Vec<String>,
+  pub targets : Vec<CountermeasureTarget>,
+  pub properties : Vec<SecurityPropertyValue>,
+  pub roles : Vec<String>,
+  pub personas : Vec<String>
+}
+
+impl CountermeasureEnvironment {
+  pub fn new(env_name : &str) -> CountermeasureEnvironment {
+    CountermeasureEnvironment {
+      name: env_name.to_string(),
+      cost: QualitativeValue::None,
+      requirements: Vec::<String>::new(),
+      targets: Vec::<CountermeasureTarget>::new(),
+      properties: Vec::<SecurityPropertyValue>::new(),
+      roles: Vec::<String>::new(),
+      personas: Vec::<String>::new()
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn residual_risk(&self, target_name : &str, score : i128) -> i128 {
+    match self.targets.iter().find(|t| t.name == target_name) {
+      Some(t) => (score - t.effectiveness.clone() as i128).max(0),
+      None => score
+    }
+  }
+}
+
+#[test]
+fn test_new_countermeasure_environment() {
+  let ce = CountermeasureEnvironment::new("Default");
+  assert_eq!(ce.name,"Default".to_string());
+  assert_eq!(ce.cost,QualitativeValue::None);
+  assert_eq!(ce.requirements.len(),0);
+  assert_eq!(ce.targets.len(),0);
+  assert_eq!(ce.properties.len(),0);
+  assert_eq!(ce.roles.len(),0);
+  assert_eq!(ce.personas.len(),0);
+}
+
+#[test]
+fn test_countermeasure_environment_residual_risk() {
+  let mut ce = CountermeasureEnvironment::new("Default");
+  ce.targets.push(CountermeasureTarget::new("SQL injection","Medium","TBC"));
+  ce.targets.push(CountermeasureTarget::new("Weak passwords","High","TBC"));
+  assert_eq!(ce.residual_risk("SQL injection",3),1);
+  assert_eq!(ce.residual_risk("Weak passwords",2),0);
+  assert_eq!(ce.residual_risk("Phishing",3),3);
+}
+
+impl fmt::Display for CountermeasureEnvironment {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let targets = &self.targets.iter().map(|t| t.to_string()).collect::<Vec<String>>().join(",");
+    let props = &self.properties.iter().map(|p| p.to_string()).collect::<Vec<String>>().join(",");
+    write!(f,"Environment: {}, Cost: {:?}, Requirements: {}, Targets: {}, Properties: {}, Roles: {}, Personas: {}",self.name,self.cost,self.requirements.join(","),targets,props,self.roles.join(","),self.personas.join(","))
+  }
+}
+
+#[derive(Clone)]
+pub struct Countermeasure {
+  name : String,
+  countermeasure_type : String,
+  pub description : String,
+  pub tags : Vec<Tag>,
+  pub environments : Vec<CountermeasureEnvironment>
+}
+
+impl Countermeasure {
+  pub fn new(cm_name : &str, cm_type : &str) -> Countermeasure {
+    Countermeasure {
+      name: cm_name.to_string(),
+      countermeasure_type: cm_type.to_string(),
+      description: "".to_string(),
+      tags: Vec::<Tag>::new(),
+      environments: Vec::<CountermeasureEnvironment>::new()
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn countermeasure_type(&self) -> &str {
+    &self.countermeasure_type
+  }
+
+  pub fn set_countermeasure_type(&mut self, cm_type : &str) {
+    self.countermeasure_type = cm_type.to_string();
+  }
+
+  pub fn residual_risk(&self, env_name : &str, target_name : &str, score : i128) -> i128 {
+    match self.environments.iter().find(|e| e.name == env_name) {
+      Some(e) => e.residual_risk(target_name,score),
+      None => score
+    }
+  }
+}
+
+#[test]
+fn test_new_countermeasure() {
+  let cm = Countermeasure::new("Input validation","Software");
+  assert_eq!(cm.name,"Input validation".to_string());
+  assert_eq!(cm.countermeasure_type,"Software".to_string());
+  assert_eq!(cm.description,"".to_string());
+  assert_eq!(cm.tags.len(),0);
+  assert_eq!(cm.environments.len(),0);
+}
+
+#[test]
+fn test_countermeasure_residual_risk() {
+  let mut cm = Countermeasure::new("Input validation","Software");
+  let mut ce = CountermeasureEnvironment::new("Day");
+  ce.targets.push(CountermeasureTarget::new("SQL injection","Low","TBC"));
+  cm.environments.push(ce);
+  assert_eq!(cm.residual_risk("Day","SQL injection",3),2);
+  assert_eq!(cm.residual_risk("Night","SQL injection",3),3);
+}
+
+impl fmt::Display for Countermeasure {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let envs = &self.environments.iter().map(|env| env.to_string()).collect::<Vec<String>>().join(",");
+    let tags = &self.tags.iter().map(|t| t.name.clone()).collect::<Vec<String>>().join(",");
+    write!(f,"Name: {}, Type: {}, Description: {}, Tags: {}, Environments: {}",self.name,self.countermeasure_type,self.description,tags,envs)
+  }
+}
+
+impl Dimension for Countermeasure {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::Countermeasure
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn tags(&self) -> &[Tag] {
+    &self.tags
+  }
+
+  fn environment_names(&self) -> Vec<String> {
+    self.environments.iter().map(|ce| ce.name.clone()).collect()
+  }
+}
+
+impl Renamable for Countermeasure {
+  fn set_name(&mut self, new_name : &str) {
+    self.name = new_name.to_string();
+  }
+}
+
+impl Referencing for Countermeasure {
+  fn references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    match kind {
+      DimensionKind::Environment => self.environments.iter().filter(|ce| ce.name == objt_name).count(),
+      DimensionKind::Role => self.environments.iter().map(|ce| count_names(&ce.roles,objt_name)).sum(),
+      DimensionKind::Threat | DimensionKind::Vulnerability => self.environments.iter().map(|ce| ce.targets.iter().filter(|t| t.name == objt_name).count()).sum(),
+      DimensionKind::Tag => self.tags.iter().filter(|t| t.name == objt_name).count(),
+      _ => 0
+    }
+  }
+
+  fn update_references(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    match kind {
+      DimensionKind::Environment => {
+        let count = self.references(kind,old_name);
+        match new_name {
+          Some(n) => self.environments.iter_mut().filter(|ce| ce.name == old_name).for_each(|ce| ce.name = n.to_string()),
+          None => self.environments.retain(|ce| ce.name != old_name)
+        }
+        count
+      },
+      DimensionKind::Role => self.environments.iter_mut().map(|ce| update_names(&mut ce.roles,old_name,new_name)).sum(),
+      DimensionKind::Threat | DimensionKind::Vulnerability => {
+        let count = self.references(kind,old_name);
+        for ce in self.environments.iter_mut() {
+          match new_name {
+            Some(n) => ce.targets.iter_mut().filter(|t| t.name == old_name).for_each(|t| t.name = n.to_string()),
+            None => ce.targets.retain(|t| t.name != old_name)
+          }
+        }
+        count
+      },
+      DimensionKind::Tag => update_tags(&mut self.tags,old_name,new_name),
+      _ => 0
+    }
+  }
+}
+
+#[test]
+fn test_countermeasure_references() {
+  let mut cm = Countermeasure::new("Input validation","Software");
+  let mut ce = CountermeasureEnvironment::new("Day");
+  ce.targets.push(CountermeasureTarget::new("SQL injection","Low","TBC"));
+  ce.roles.push("Researcher".to_string());
+  cm.environments.push(ce);
+  assert_eq!(cm.references(DimensionKind::Threat,"SQL injection"),1);
+  assert_eq!(cm.update_references(DimensionKind::Threat,"SQL injection",Some("Injection")),1);
+  assert_eq!(cm.residual_risk("Day","Injection",3),2);
+  assert_eq!(cm.update_references(DimensionKind::Role,"Researcher",None),1);
+  assert!(cm.environments[0].roles.is_empty());
+}
@@ -0,0 +1,190 @@
+use std::fmt;
+use crate::dimensions::dimension::{Dimension,Renamable,DimensionKind,Referencing,count_names,update_name,update_names,update_tags};
+use crate::dimensions::tag::Tag;
+
+#[derive(Clone,PartialEq,Debug)]
+pub enum DataFlowComponentType {
+  Process,
+  Entity,
+  Datastore
+}
+
+impl DataFlowComponentType {
+  pub fn new(c_type : &str) -> DataFlowComponentType {
+    match c_type {
+      "process" | "Process" => DataFlowComponentType::Process,
+      "entity" | "Entity" => DataFlowComponentType::Entity,
+      "datastore" | "Datastore" => DataFlowComponentType::Datastore,
+      &_ => panic!("{} is not a data flow component type",c_type)
+    }
+  }
+
+  pub fn dimension(&self) -> DimensionKind {
+    match self {
+      DataFlowComponentType::Process => DimensionKind::UseCase,
+      DataFlowComponentType::Entity | DataFlowComponentType::Datastore => DimensionKind::Asset
+    }
+  }
+}
+
+impl fmt::Display for DataFlowComponentType {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      DataFlowComponentType::Process => write!(f,"process"),
+      DataFlowComponentType::Entity => write!(f,"entity"),
+      DataFlowComponentType::Datastore => write!(f,"datastore")
+    }
+  }
+}
+
+#[test]
+fn test_new_data_flow_component_type() {
+  assert_eq!(DataFlowComponentType::new("process"),DataFlowComponentType::Process);
+  assert_eq!(DataFlowComponentType::new("Datastore"),DataFlowComponentType::Datastore);
+}
+
+#[test]
+#[should_panic]
+fn test_new_data_flow_component_type_panics() {
+  DataFlowComponentType::new("asset");
+}
+
+#[derive(Clone,PartialEq)]
+pub struct DataFlow {
+  name : String,
+  environment : String,
+  pub source_name : String,
+  pub source_type : DataFlowComponentType,
+  pub target_name : String,
+  pub target_type : DataFlowComponentType,
+  pub assets : Vec<String>,
+  pub obstacles : Vec<String>,
+  pub tags : Vec<Tag>
+}
+
+impl DataFlow {
+  pub fn new(df_name : &str, env_name : &str, src_name : &str, src_type : &str, tgt_name : &str, tgt_type : &str) -> DataFlow {
+    DataFlow {
+      name: df_name.to_string(),
+      environment: env_name.to_string(),
+      source_name: src_name.to_string(),
+      source_type: DataFlowComponentType::new(src_type),
+      target_name: tgt_name.to_string(),
+      target_type: DataFlowComponentType::new(tgt_type),
+      assets: Vec::<String>::new(),
+      obstacles: Vec::<String>::new(),
+      tags: Vec::<Tag>::new()
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn environment(&self) -> &str {
+    &self.environment
+  }
+
+  pub fn set_environment(&mut self, env_name : &str) {
+    self.environment = env_name.to_string();
+  }
+
+  fn endpoint_references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    usize::from(self.source_type.dimension() == kind && self.source_name == objt_name) + usize::from(self.target_type.dimension() == kind && self.target_name == objt_name)
+  }
+}
+
+#[test]
+fn test_new_data_flow() {
+  let df = DataFlow::new("Upload","Default","Researcher","entity","Upload data","process");
+  assert_eq!(df.name,"Upload".to_string());
+  assert_eq!(df.environment,"Default".to_string());
+  assert_eq!(df.source_name,"Researcher".to_string());
+  assert_eq!(df.source_type,DataFlowComponentType::Entity);
+  assert_eq!(df.target_name,"Upload data".to_string());
+  assert_eq!(df.target_type,DataFlowComponentType::Process);
+  assert_eq!(df.assets.len(),0);
+  assert_eq!(df.obstacles.len(),0);
+  assert_eq!(df.tags.len(),0);
+}
+
+impl fmt::Display for DataFlow {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let tags = &self.tags.iter().map(|t| t.name.clone()).collect::<Vec<String>>().join(",");
+    write!(f,"Name: {}, Environment: {}, From: {} ({}), To: {} ({}), Assets: {}, Obstacles: {}, Tags: {}",self.name,self.environment,self.source_name,self.source_type,self.target_name,self.target_type,self.assets.join(","),self.obstacles.join(","),tags)
+  }
+}
+
+impl Dimension for DataFlow {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::DataFlow
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn tags(&self) -> &[Tag] {
+    &self.tags
+  }
+
+  fn environment_names(&self) -> Vec<String> {
+    vec![self.environment.clone()]
+  }
+}
+
+impl Renamable for DataFlow {
+  fn set_name(&mut self, new_name : &str) {
+    self.name = new_name.to_string();
+  }
+}
+
+impl Referencing for DataFlow {
+  fn references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    match kind {
+      DimensionKind::Environment => usize::from(self.environment == objt_name),
+      DimensionKind::Asset => self.endpoint_references(kind,objt_name) + count_names(&self.assets,objt_name),
+      DimensionKind::UseCase => self.endpoint_references(kind,objt_name),
+      DimensionKind::Tag => self.tags.iter().filter(|t| t.name == objt_name).count(),
+      _ => 0
+    }
+  }
+
+  fn requires(&self, kind : DimensionKind, objt_name : &str) -> bool {
+    (kind == DimensionKind::Environment && self.environment == objt_name) || self.endpoint_references(kind,objt_name) > 0
+  }
+
+  fn update_references(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    if kind == DimensionKind::Environment {
+      return update_name(&mut self.environment,old_name,new_name);
+    }
+    if kind == DimensionKind::Tag {
+      return update_tags(&mut self.tags,old_name,new_name);
+    }
+    let mut count = 0;
+    if self.source_type.dimension() == kind {
+      count += update_name(&mut self.source_name,old_name,new_name);
+    }
+    if self.target_type.dimension() == kind {
+      count += update_name(&mut self.target_name,old_name,new_name);
+    }
+    if kind == DimensionKind::Asset {
+      count += update_names(&mut self.assets,old_name,new_name);
+    }
+    count
+  }
+}
+
+#[test]
+fn test_data_flow_references() {
+  let mut df = DataFlow::new("Upload","Day","Researcher","entity","Upload data","process");
+  df.assets.push("Data".to_string());
+  assert_eq!(df.references(DimensionKind::Asset,"Researcher"),1);
+  assert_eq!(df.references(DimensionKind::UseCase,"Researcher"),0);
+  assert!(df.requires(DimensionKind::UseCase,"Upload data"));
+  assert!(!df.requires(DimensionKind::Asset,"Data"));
+  assert_eq!(df.update_references(DimensionKind::UseCase,"Upload data",Some("Share data")),1);
+  assert_eq!(df.target_name,"Share data".to_string());
+  assert_eq!(df.update_references(DimensionKind::Asset,"Data",None),1);
+  assert!(df.assets.is_empty());
+}
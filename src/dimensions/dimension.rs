@@ -0,0 +1,234 @@
+use std::fmt;
+use crate::dimensions::objectid::ObjectId;
+use crate::dimensions::tag::Tag;
+
+#[derive(Clone,Copy,PartialEq,Eq,Hash,Debug)]
+pub enum DimensionKind {
+  Asset,
+  Attacker,
+  Vulnerability,
+  Role,
+  Environment,
+  ValueType,
+  Tag,
+  Threat,
+  Risk,
+  UseCase,
+  MisuseCase,
+  Countermeasure,
+  Response,
+  DomainProperty,
+  AssetAssociation,
+  DataFlow,
+  TrustBoundary
+}
+
+impl DimensionKind {
+  pub fn new(kind : &str) -> DimensionKind {
+    match kind {
+      "asset" => DimensionKind::Asset,
+      "attacker" => DimensionKind::Attacker,
+      "vulnerability" => DimensionKind::Vulnerability,
+      "role" => DimensionKind::Role,
+      "environment" => DimensionKind::Environment,
+      "value_type" => DimensionKind::ValueType,
+      "tag" => DimensionKind::Tag,
+      "threat" => DimensionKind::Threat,
+      "risk" => DimensionKind::Risk,
+      "use_case" => DimensionKind::UseCase,
+      "misuse_case" => DimensionKind::MisuseCase,
+      "countermeasure" => DimensionKind::Countermeasure,
+      "response" => DimensionKind::Response,
+      "domain_property" => DimensionKind::DomainProperty,
+      "asset_association" => DimensionKind::AssetAssociation,
+      "data_flow" => DimensionKind::DataFlow,
+      "trust_boundary" => DimensionKind::TrustBoundary,
+      &_ => panic!("{} is not a dimension",kind)
+    }
+  }
+}
+
+impl fmt::Display for DimensionKind {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f,"{}",
+      match self {
+        DimensionKind::Asset => "asset",
+        DimensionKind::Attacker => "attacker",
+        DimensionKind::Vulnerability => "vulnerability",
+        DimensionKind::Role => "role",
+        DimensionKind::Environment => "environment",
+        DimensionKind::ValueType => "value_type",
+        DimensionKind::Tag => "tag",
+        DimensionKind::Threat => "threat",
+        DimensionKind::Risk => "risk",
+        DimensionKind::UseCase => "use_case",
+        DimensionKind::MisuseCase => "misuse_case",
+        DimensionKind::Countermeasure => "countermeasure",
+        DimensionKind::Response => "response",
+        DimensionKind::DomainProperty => "domain_property",
+        DimensionKind::AssetAssociation => "asset_association",
+        DimensionKind::DataFlow => "data_flow",
+        DimensionKind::TrustBoundary => "trust_boundary"
+      }
+    )
+  }
+}
+
+pub trait Dimension {
+  fn kind(&self) -> DimensionKind;
+  fn name(&self) -> &str;
+
+  fn short_code(&self) -> Option<&str> {
+    None
+  }
+
+  fn tags(&self) -> &[Tag] {
+    &[]
+  }
+
+  fn environment_names(&self) -> Vec<String> {
+    Vec::<String>::new()
+  }
+}
+
+pub(crate) trait Renamable : Dimension {
+  fn set_name(&mut self, new_name : &str);
+}
+
+pub trait Identified : Dimension {
+  type Id : ObjectId;
+  fn id(&self) -> Option<Self::Id>;
+}
+
+pub trait Referencing : Dimension {
+  fn references(&self, _kind : DimensionKind, _name : &str) -> usize {
+    0
+  }
+
+  fn requires(&self, _kind : DimensionKind, _name : &str) -> bool {
+    false
+  }
+
+  fn update_references(&mut self, _kind : DimensionKind, _old_name : &str, _new_name : Option<&str>) -> usize {
+    0
+  }
+
+  fn defined_by_environments(&self) -> bool {
+    false
+  }
+}
+
+pub fn count_names(names : &[String], objt_name : &str) -> usize {
+  names.iter().filter(|n| *n == objt_name).count()
+}
+
+pub fn update_name(value : &mut String, old_name : &str, new_name : Option<&str>) -> usize {
+  if value != old_name {
+    return 0;
+  }
+  *value = new_name.unwrap_or("").to_string();
+  1
+}
+
+pub fn update_names(names : &mut Vec<String>, old_name : &str, new_name : Option<&str>) -> usize {
+  let count = count_names(names,old_name);
+  match new_name {
+    Some(n) => names.iter_mut().filter(|x| *x == old_name).for_each(|x| *x = n.to_string()),
+    None => names.retain(|x| x != old_name)
+  }
+  count
+}
+
+pub fn update_tags(tags : &mut Vec<Tag>, old_name : &str, new_name : Option<&str>) -> usize {
+  let count = tags.iter().filter(|t| t.name == old_name).count();
+  match new_name {
+    Some(n) => tags.iter_mut().filter(|t| t.name == old_name).for_each(|t| t.name = n.to_string()),
+    None => tags.retain(|t| t.name != old_name)
+  }
+  count
+}
+
+pub fn value_type_key(vt_type : &str, vt_name : &str) -> String {
+  format!("{}/{}",vt_type,vt_name)
+}
+
+pub fn asset_association_key(env_name : &str, head_name : &str, tail_name : &str) -> String {
+  format!("{}/{}/{}",env_name,head_name,tail_name)
+}
+
+pub fn value_type_name<'a>(key : &'a str, vt_type : &str) -> Option<&'a str> {
+  key.strip_prefix(vt_type).and_then(|k| k.strip_prefix('/'))
+}
+
+pub fn find_by_name<'a, D : Dimension>(objts : &'a [D], objt_name : &str) -> Option<&'a D> {
+  objts.iter().find(|o| o.name() == objt_name)
+}
+
+pub fn in_environment<'a>(objts : &[&'a dyn Dimension], env_name : &str) -> Vec<&'a dyn Dimension> {
+  objts.iter().filter(|o| o.environment_names().iter().any(|e| e == env_name)).copied().collect()
+}
+
+pub fn search<'a>(objts : &[&'a dyn Dimension], term : &str) -> Vec<&'a dyn Dimension> {
+  let term = term.to_lowercase();
+  objts.iter().filter(|o| {
+    o.name().to_lowercase().contains(&term) ||
+    o.short_code().is_some_and(|sc| sc.to_lowercase() == term) ||
+    o.tags().iter().any(|t| t.name.to_lowercase() == term)
+  }).copied().collect()
+}
+
+#[cfg(test)]
+use crate::dimensions::{asset::AssetBuilder,attacker::AttackerBuilder,vulnerability::VulnerabilityBuilder,role::RoleBuilder,environment::EnvironmentBuilder,valuetype::ValueTypeBuilder};
+#[cfg(test)]
+use crate::dimensions::objectid::{AssetId,ValueTypeId};
+
+#[test]
+fn test_update_names() {
+  let mut names = vec!["Day".to_string(),"Night".to_string(),"Day".to_string()];
+  assert_eq!(update_names(&mut names,"Day",Some("Morning")),2);
+  assert_eq!(names,vec!["Morning".to_string(),"Night".to_string(),"Morning".to_string()]);
+  assert_eq!(update_names(&mut names,"Morning",None),2);
+  assert_eq!(names,vec!["Night".to_string()]);
+  assert_eq!(value_type_name("asset_type/Information","asset_type"),Some("Information"));
+  assert_eq!(value_type_name("asset_type/Information","threat_type"),None);
+}
+
+#[test]
+fn test_dimension_kind() {
+  assert_eq!(DimensionKind::new("value_type"),DimensionKind::ValueType);
+  assert_eq!(DimensionKind::ValueType.to_string(),"value_type");
+  assert_eq!(DimensionKind::new("trust_boundary"),DimensionKind::TrustBoundary);
+  assert_eq!(DimensionKind::AssetAssociation.to_string(),"asset_association");
+}
+
+#[test]
+#[should_panic]
+fn test_dimension_kind_panics() {
+  DimensionKind::new("persona");
+}
+
+#[test]
+fn test_dimensions() {
+  let asset = AssetBuilder::new("Clinical data").short_code("CD").asset_type("Information").tags(&["pii"]).property("Day","confidentiality","High","").build().unwrap();
+  let attacker = AttackerBuilder::new("Carol").environment("Night",&["Researcher"],&[],&[]).build().unwrap();
+  let vul = VulnerabilityBuilder::new("Unvalidated input").vulnerability_type("Implementation").tags(&["PII"]).environment("Day","Critical",&["Clinical data"]).build().unwrap();
+  let roles = vec![RoleBuilder::new("Researcher").role_type("Stakeholder").short_code("RES").build().unwrap()];
+  let env = EnvironmentBuilder::new("Day").short_code("DAY").build().unwrap();
+  let vt = ValueTypeBuilder::new("High","threat_value").environment("Day").build().unwrap();
+  let tag = Tag::new("pii");
+  let objts : Vec<&dyn Dimension> = vec![&asset,&attacker,&vul,&roles[0],&env,&vt,&tag];
+
+  assert_eq!(objts.iter().map(|o| o.kind().to_string()).collect::<Vec<String>>(),vec!["asset","attacker","vulnerability","role","environment","value_type","tag"]);
+  assert_eq!(Dimension::short_code(&roles[0]),Some("RES"));
+  assert_eq!(Dimension::short_code(&attacker),None);
+  assert_eq!(asset.id(),None);
+  let mut saved = asset.clone();
+  saved.set_id(AssetId::new(42));
+  assert_eq!(saved.id(),Some(AssetId::new(42)));
+  assert_eq!(Identified::id(&vt),None::<ValueTypeId>);
+  assert_eq!(in_environment(&objts,"Day").iter().map(|o| o.name()).collect::<Vec<&str>>(),vec!["Clinical data","Unvalidated input","Day","High"]);
+  assert_eq!(search(&objts,"pii").iter().map(|o| o.name()).collect::<Vec<&str>>(),vec!["Clinical data","Unvalidated input","pii"]);
+  assert_eq!(search(&objts,"res").iter().map(|o| o.name()).collect::<Vec<&str>>(),vec!["Researcher"]);
+  assert!(find_by_name(&roles,"Researcher").is_some());
+  assert!(find_by_name(&roles,"Carol").is_none());
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+use crate::dimensions::dimension::{Dimension,Renamable,DimensionKind,Identified,Referencing,update_tags};
+use crate::dimensions::objectid::DomainPropertyId;
+use crate::dimensions::tag::Tag;
+
+#[derive(Clone,PartialEq,Debug)]
+pub enum DomainPropertyType {
+  Hypothesis,
+  Invariant
+}
+
+impl DomainPropertyType {
+  pub fn parse(dp_type : &str) -> Result<DomainPropertyType,String> {
+    match dp_type {
+      "Hypothesis" => Ok(DomainPropertyType::Hypothesis),
+      "Invariant" => Ok(DomainPropertyType::Invariant),
+      &_ => Err(format!("{} is an invalid domain property type",dp_type))
+    }
+  }
+}
+
+impl fmt::Display for DomainPropertyType {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      DomainPropertyType::Hypothesis => write!(f,"Hypothesis"),
+      DomainPropertyType::Invariant => write!(f,"Invariant")
+    }
+  }
+}
+
+#[derive(Clone,PartialEq)]
+pub struct DomainProperty {
+  pub id : Option<DomainPropertyId>,
+  pub name : String,
+  pub short_code : String,
+  pub definition : String,
+  pub dp_type : DomainPropertyType,
+  pub originator : String,
+  pub tags : Vec<Tag>
+}
+
+impl DomainProperty {
+  pub fn new(dp_name : &str, s_code : &str, dp_type : &str, dp_orig : &str) -> DomainProperty {
+    DomainProperty {
+      id: None,
+      name: dp_name.to_string(),
+      short_code: s_code.to_string(),
+      definition: "".to_string(),
+      dp_type: DomainPropertyType::parse(dp_type).unwrap_or_else(|e| panic!("{}",e)),
+      originator: dp_orig.to_string(),
+      tags: Vec::<Tag>::new()
+    }
+  }
+}
+
+impl fmt::Display for DomainProperty {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let tags = &self.tags.iter().map(|t| t.name.clone()).collect::<Vec<String>>().join(",");
+    write!(f,"Name: {}, Short code: {}, Definition: {}, Type: {}, Originator: {}, Tags: {}",self.name,self.short_code,self.definition,self.dp_type,self.originator,tags)
+  }
+}
+
+impl Dimension for DomainProperty {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::DomainProperty
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn short_code(&self) -> Option<&str> {
+    Some(&self.short_code)
+  }
+
+  fn tags(&self) -> &[Tag] {
+    &self.tags
+  }
+}
+
+impl Renamable for DomainProperty {
+  fn set_name(&mut self, new_name : &str) {
+    self.name = new_name.to_string();
+  }
+}
+
+impl Identified for DomainProperty {
+  type Id = DomainPropertyId;
+
+  fn id(&self) -> Option<DomainPropertyId> {
+    self.id
+  }
+}
+
+impl Referencing for DomainProperty {
+  fn references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    match kind {
+      DimensionKind::Tag => self.tags.iter().filter(|t| t.name == objt_name).count(),
+      _ => 0
+    }
+  }
+
+  fn update_references(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    match kind {
+      DimensionKind::Tag => update_tags(&mut self.tags,old_name,new_name),
+      _ => 0
+    }
+  }
+}
+
+#[test]
+fn test_new_domain_property() {
+  let dp = DomainProperty::new("Users authenticate","DP-1","Hypothesis","Shamal Faily");
+  assert_eq!(dp.id,None);
+  assert_eq!(dp.name,"Users authenticate".to_string());
+  assert_eq!(dp.short_code,"DP-1".to_string());
+  assert_eq!(dp.definition,"".to_string());
+  assert_eq!(dp.dp_type,DomainPropertyType::Hypothesis);
+  assert_eq!(dp.originator,"Shamal Faily".to_string());
+  assert_eq!(dp.tags.len(),0);
+}
+
+#[test]
+#[should_panic]
+fn test_new_domain_property_panics() {
+  DomainProperty::new("Users authenticate","DP-1","Assumption","Shamal Faily");
+}
@@ -1,4 +1,6 @@
 use std::fmt;
+use crate::dimensions::dimension::{Dimension,Renamable,DimensionKind,Identified,Referencing,count_names,update_names};
+use crate::dimensions::objectid::EnvironmentId;
 
 #[derive(Clone,PartialEq,Debug)]
 pub enum CompositeProperty {
@@ -6,6 +8,16 @@ pub enum CompositeProperty {
   Maximise = 1
 }
 
+impl CompositeProperty {
+  pub fn parse(prop_str : &str) -> Result<CompositeProperty,String> {
+    match prop_str {
+      "Override" => Ok(CompositeProperty::Override),
+      "Maximise" => Ok(CompositeProperty::Maximise),
+      &_ => Err(format!("{} is not a duplication property",prop_str))
+    }
+  }
+}
+
 impl fmt::Display for CompositeProperty {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     write!(f,"{}",
@@ -28,31 +40,37 @@ impl CompositeEnvironments {
   pub fn new() -> CompositeEnvironments {
     CompositeEnvironments{ environments : Vec::<String>::new(), property : CompositeProperty::Maximise, overriding_environment_name : "".to_string()}
   }
-  pub fn add(&mut self, new_env : &String) {
-    self.environments.push(new_env.clone());
+  pub fn add(&mut self, new_env : &str) {
+    self.environments.push(new_env.to_string());
   }
 
-  pub fn update_property(&mut self, prop_str : &String) {
-    self.property = match prop_str.as_str() {
-      "Override" => CompositeProperty::Override,
-      "Maximise" => CompositeProperty::Maximise,
-      &_ => CompositeProperty::Override
-    };
+  pub fn update_property(&mut self, prop_str : &str) {
+    self.property = CompositeProperty::parse(prop_str).unwrap_or(CompositeProperty::Override);
   }
 
   pub fn len(&self) -> usize {
     self.environments.len()
   }
+
+  pub fn is_empty(&self) -> bool {
+    self.environments.is_empty()
+  }
+}
+
+impl Default for CompositeEnvironments {
+  fn default() -> Self {
+    CompositeEnvironments::new()
+  }
 }
 
 impl fmt::Display for CompositeEnvironments {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     let mut env_str = "".to_string();
     for e in &self.environments {
-      env_str.push_str(&e);
-      env_str.push_str(" ");
+      env_str.push_str(e);
+      env_str.push(' ');
     } 
-    write!(f,"Environments: {}, Property: {}, Overriding Environment: {}",&env_str,&self.property.to_string(),self.overriding_environment_name)
+    write!(f,"Environments: {}, Property: {}, Overriding Environment: {}",&env_str,&self.property,self.overriding_environment_name)
   }
 }
 
@@ -66,20 +84,20 @@ fn test_new_composite_environments() {
 #[test]
 fn test_add_composite_environments() {
   let mut ce = CompositeEnvironments::new();
-  ce.add(&"Foo".to_string());
+  ce.add("Foo");
   assert_eq!(ce.environments[0],"Foo".to_string());
 }
 
 #[test]
 fn test_update_duplication_property() {
   let mut ce = CompositeEnvironments::new();
-  ce.update_property(&"Override".to_string());
+  ce.update_property("Override");
   assert_eq!(ce.property,CompositeProperty::Override);
 }
 
 #[derive(Clone)]
 pub struct Environment {
-  pub id : i128,
+  pub id : Option<EnvironmentId>,
   pub name : String,
   pub short_code : String,
   pub definition : String,
@@ -87,11 +105,11 @@ pub struct Environment {
 }
 
 impl Environment {
-  pub fn new(env_name: &String, s_c : &String) -> Environment {
+  pub fn new(env_name: &str, s_c : &str) -> Environment {
     Environment{ 
-      id : -1,
-      name : env_name.clone(), 
-      short_code : s_c.clone(), 
+      id : None,
+      name : env_name.to_string(), 
+      short_code : s_c.to_string(), 
       definition : "".to_string(), 
       environments : CompositeEnvironments::new()
     }
@@ -101,14 +119,137 @@ impl Environment {
 
 impl fmt::Display for Environment {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    write!(f,"Name: {}, Short code: {}, Definition: {}, Environments: {}",self.name,self.short_code,self.definition,self.environments.to_string())
+    write!(f,"Name: {}, Short code: {}, Definition: {}, Environments: {}",self.name,self.short_code,self.definition,self.environments)
+  }
+}
+
+impl Dimension for Environment {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::Environment
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn short_code(&self) -> Option<&str> {
+    Some(&self.short_code)
+  }
+
+  fn environment_names(&self) -> Vec<String> {
+    vec![self.name.clone()]
+  }
+}
+
+impl Renamable for Environment {
+  fn set_name(&mut self, new_name : &str) {
+    self.name = new_name.to_string();
+  }
+}
+
+impl Identified for Environment {
+  type Id = EnvironmentId;
+
+  fn id(&self) -> Option<EnvironmentId> {
+    self.id
+  }
+}
+
+impl Referencing for Environment {
+  fn references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    if kind != DimensionKind::Environment {
+      return 0;
+    }
+    count_names(&self.environments.environments,objt_name) + usize::from(self.environments.overriding_environment_name == objt_name)
+  }
+
+  fn update_references(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    if kind != DimensionKind::Environment {
+      return 0;
+    }
+    let mut count = update_names(&mut self.environments.environments,old_name,new_name);
+    if self.environments.overriding_environment_name == old_name {
+      self.environments.overriding_environment_name = new_name.unwrap_or("").to_string();
+      count += 1;
+    }
+    count
   }
 }
 
 #[test]
 pub fn test_new_environment() {
-  let e = Environment::new(&"Default".to_string(), &"DEF".to_string());
+  let e = Environment::new("Default", "DEF");
   assert_eq!(e.name,"Default".to_string());
   assert_eq!(e.short_code,"DEF".to_string());
   assert_eq!(e.definition,"".to_string());
 }
+
+pub struct EnvironmentBuilder {
+  environment : Environment,
+  duplication_property : String
+}
+
+impl EnvironmentBuilder {
+  pub fn new(env_name : &str) -> EnvironmentBuilder {
+    EnvironmentBuilder{environment : Environment::new(env_name,""), duplication_property : "Override".to_string()}
+  }
+
+  pub fn short_code(mut self, s_c : &str) -> EnvironmentBuilder {
+    self.environment.short_code = s_c.to_string();
+    self
+  }
+
+  pub fn definition(mut self, def : &str) -> EnvironmentBuilder {
+    self.environment.definition = def.to_string();
+    self
+  }
+
+  pub fn composite(mut self, env_names : &[&str]) -> EnvironmentBuilder {
+    for e in env_names {
+      self.environment.environments.add(e);
+    }
+    self
+  }
+
+  pub fn duplication_property(mut self, prop_str : &str) -> EnvironmentBuilder {
+    self.duplication_property = prop_str.to_string();
+    self
+  }
+
+  pub fn overriding_environment(mut self, env_name : &str) -> EnvironmentBuilder {
+    self.environment.environments.overriding_environment_name = env_name.to_string();
+    self
+  }
+
+  pub fn build(self) -> Result<Environment,String> {
+    let mut env = self.environment;
+    if env.name.is_empty() {
+      return Err("Environment has no name".to_string());
+    }
+    if env.short_code.is_empty() {
+      return Err(format!("Environment {} has no short code",env.name));
+    }
+    env.environments.property = CompositeProperty::parse(&self.duplication_property).map_err(|e| format!("Environment {}: {}",env.name,e))?;
+    let members = &env.environments.environments;
+    if members.contains(&env.name) {
+      return Err(format!("Environment {} cannot contain itself",env.name));
+    }
+    let oe = &env.environments.overriding_environment_name;
+    if !oe.is_empty() && !members.contains(oe) {
+      return Err(format!("Environment {}: overriding environment {} is not one of its environments",env.name,oe));
+    }
+    Ok(env)
+  }
+}
+
+#[test]
+fn test_environment_builder() {
+  let env = EnvironmentBuilder::new("Complete").short_code("CMP").definition("Day and night").composite(&["Day","Night"]).duplication_property("Override").overriding_environment("Night").build().unwrap();
+  assert_eq!(env.environments.len(),2);
+  assert_eq!(env.environments.property,CompositeProperty::Override);
+  assert_eq!(env.environments.overriding_environment_name,"Night".to_string());
+  assert_eq!(EnvironmentBuilder::new("Complete").short_code("CMP").composite(&["Day"]).overriding_environment("Night").build().err(),Some("Environment Complete: overriding environment Night is not one of its environments".to_string()));
+  assert_eq!(EnvironmentBuilder::new("Complete").short_code("CMP").composite(&["Complete"]).build().err(),Some("Environment Complete cannot contain itself".to_string()));
+  assert_eq!(EnvironmentBuilder::new("Complete").short_code("CMP").duplication_property("Minimise").build().err(),Some("Environment Complete: Minimise is not a duplication property".to_string()));
+  assert_eq!(EnvironmentBuilder::new("Day").build().err(),Some("Environment Day has no short code".to_string()));
+}
@@ -0,0 +1,187 @@
+use std::fmt;
+use crate::dimensions::dimension::{Dimension,Renamable,DimensionKind,Referencing,count_names,update_names};
+
+#[derive(Clone,PartialEq)]
+pub struct MisuseCaseEnvironment {
+  name : String,
+  pub narrative : String,
+  pub attackers : Vec<String>,
+  pub assets : Vec<String>
+}
+
+impl MisuseCaseEnvironment {
+  pub fn new(env_name : &str) -> MisuseCaseEnvironment {
+    MisuseCaseEnvironment { name: env_name.to_string(), narrative: "".to_string(), attackers: Vec::<String>::new(), assets: Vec::<String>::new() }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+}
+
+#[test]
+fn test_new_misuse_case_environment() {
+  let mce = MisuseCaseEnvironment::new("Default");
+  assert_eq!(mce.name,"Default".to_string());
+  assert_eq!(mce.narrative,"".to_string());
+  assert_eq!(mce.attackers.len(),0);
+  assert_eq!(mce.assets.len(),0);
+}
+
+impl fmt::Display for MisuseCaseEnvironment {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f,"Environment: {}, Narrative: {}, Attackers: {}, Assets: {}",self.name,self.narrative,self.attackers.join(","),self.assets.join(","))
+  }
+}
+
+#[derive(Clone,PartialEq)]
+pub struct MisuseCase {
+  name : String,
+  risk : String,
+  pub environments : Vec<MisuseCaseEnvironment>
+}
+
+impl MisuseCase {
+  pub fn new(mc_name : &str, risk_name : &str) -> MisuseCase {
+    MisuseCase { name: mc_name.to_string(), risk: risk_name.to_string(), environments: Vec::<MisuseCaseEnvironment>::new() }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn risk(&self) -> &str {
+    &self.risk
+  }
+
+  pub fn set_risk(&mut self, risk_name : &str) {
+    self.risk = risk_name.to_string();
+  }
+
+  pub fn attackers(&self) -> Vec<String> {
+    let mut attackers = Vec::<String>::new();
+    for env in &self.environments {
+      for a in &env.attackers {
+        if !attackers.contains(a) {
+          attackers.push(a.clone());
+        }
+      }
+    }
+    attackers
+  }
+
+  pub fn assets(&self) -> Vec<String> {
+    let mut assets = Vec::<String>::new();
+    for env in &self.environments {
+      for a in &env.assets {
+        if !assets.contains(a) {
+          assets.push(a.clone());
+        }
+      }
+    }
+    assets
+  }
+}
+
+#[test]
+fn test_new_misuse_case() {
+  let mc = MisuseCase::new("Exploit upload","Upload risk");
+  assert_eq!(mc.name,"Exploit upload".to_string());
+  assert_eq!(mc.risk,"Upload risk".to_string());
+  assert_eq!(mc.environments.len(),0);
+}
+
+#[test]
+fn test_misuse_case_attackers_and_assets() {
+  let mut mc = MisuseCase::new("Exploit upload","Upload risk");
+  let mut day = MisuseCaseEnvironment::new("Day");
+  day.attackers.push("Peppa".to_string());
+  day.assets.push("Data".to_string());
+  let mut night = MisuseCaseEnvironment::new("Night");
+  night.attackers.push("Peppa".to_string());
+  night.attackers.push("George".to_string());
+  night.assets.push("Server".to_string());
+  mc.environments.push(day);
+  mc.environments.push(night);
+  assert_eq!(mc.attackers(),vec!["Peppa".to_string(),"George".to_string()]);
+  assert_eq!(mc.assets(),vec!["Data".to_string(),"Server".to_string()]);
+}
+
+impl fmt::Display for MisuseCase {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let envs = &self.environments.iter().map(|env| env.to_string()).collect::<Vec<String>>().join(",");
+    write!(f,"Name: {}, Risk: {}, Environments: {}",self.name,self.risk,envs)
+  }
+}
+
+impl Dimension for MisuseCase {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::MisuseCase
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn environment_names(&self) -> Vec<String> {
+    self.environments.iter().map(|mce| mce.name.clone()).collect()
+  }
+}
+
+impl Renamable for MisuseCase {
+  fn set_name(&mut self, new_name : &str) {
+    self.name = new_name.to_string();
+  }
+}
+
+impl Referencing for MisuseCase {
+  fn references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    match kind {
+      DimensionKind::Environment => self.environments.iter().filter(|mce| mce.name == objt_name).count(),
+      DimensionKind::Risk => usize::from(self.risk == objt_name),
+      DimensionKind::Attacker => self.environments.iter().map(|mce| count_names(&mce.attackers,objt_name)).sum(),
+      DimensionKind::Asset => self.environments.iter().map(|mce| count_names(&mce.assets,objt_name)).sum(),
+      _ => 0
+    }
+  }
+
+  fn requires(&self, kind : DimensionKind, objt_name : &str) -> bool {
+    kind == DimensionKind::Risk && self.references(kind,objt_name) > 0
+  }
+
+  fn update_references(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    match kind {
+      DimensionKind::Environment => {
+        let count = self.references(kind,old_name);
+        match new_name {
+          Some(n) => self.environments.iter_mut().filter(|mce| mce.name == old_name).for_each(|mce| mce.name = n.to_string()),
+          None => self.environments.retain(|mce| mce.name != old_name)
+        }
+        count
+      },
+      DimensionKind::Risk if self.risk == old_name => {
+        self.risk = new_name.unwrap_or("").to_string();
+        1
+      },
+      DimensionKind::Attacker => self.environments.iter_mut().map(|mce| update_names(&mut mce.attackers,old_name,new_name)).sum(),
+      DimensionKind::Asset => self.environments.iter_mut().map(|mce| update_names(&mut mce.assets,old_name,new_name)).sum(),
+      _ => 0
+    }
+  }
+}
+
+#[test]
+fn test_misuse_case_references() {
+  let mut mc = MisuseCase::new("Exploit upload","Upload risk");
+  let mut day = MisuseCaseEnvironment::new("Day");
+  day.attackers.push("Peppa".to_string());
+  day.assets.push("Data".to_string());
+  mc.environments.push(day);
+  assert!(mc.requires(DimensionKind::Risk,"Upload risk"));
+  assert_eq!(mc.update_references(DimensionKind::Risk,"Upload risk",Some("Injection risk")),1);
+  assert_eq!(mc.risk(),"Injection risk");
+  assert_eq!(mc.update_references(DimensionKind::Attacker,"Peppa",None),1);
+  assert_eq!(mc.update_references(DimensionKind::Asset,"Data",Some("Records")),1);
+  assert_eq!(mc.assets(),vec!["Records".to_string()]);
+  assert!(mc.attackers().is_empty());
+}
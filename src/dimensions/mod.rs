@@ -1,3 +1,5 @@
+pub mod objectid;
+pub mod dimension;
 pub mod valuetype;
 pub mod projectsettings;
 pub mod role;
@@ -7,3 +9,13 @@ pub mod tag;
 pub mod environment;
 pub mod vulnerability;
 pub mod attacker;
+pub mod usecase;
+pub mod misusecase;
+pub mod countermeasure;
+pub mod response;
+pub mod domainproperty;
+pub mod assetassociation;
+pub mod dataflow;
+pub mod trustboundary;
+pub mod threat;
+pub mod risk;
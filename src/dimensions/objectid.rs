@@ -0,0 +1,48 @@
+use std::fmt;
+
+pub trait ObjectId : Copy {
+  fn new(id : i128) -> Self;
+  fn value(&self) -> i128;
+}
+
+macro_rules! object_id {
+  ($name:ident) => {
+    #[derive(Clone,Copy,PartialEq,Eq,Hash,PartialOrd,Ord,Debug)]
+    pub struct $name(i128);
+
+    impl ObjectId for $name {
+      fn new(id : i128) -> $name {
+        $name(id)
+      }
+
+      fn value(&self) -> i128 {
+        self.0
+      }
+    }
+
+    impl fmt::Display for $name {
+      fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,"{}",self.0)
+      }
+    }
+  }
+}
+
+object_id!(AssetId);
+object_id!(EnvironmentId);
+object_id!(ValueTypeId);
+object_id!(DomainPropertyId);
+object_id!(AssetAssociationId);
+
+pub fn id_value<I : ObjectId>(id : Option<I>) -> i128 {
+  id.map(|i| i.value()).unwrap_or(-1)
+}
+
+#[test]
+fn test_object_ids() {
+  let a = AssetId::new(42);
+  assert_eq!(a.value(),42);
+  assert_eq!(a.to_string(),"42");
+  assert_eq!(id_value(Some(EnvironmentId::new(7))),7);
+  assert_eq!(id_value(None::<ValueTypeId>),-1);
+}
@@ -14,8 +14,8 @@ pub struct ProjectSettings {
 }
 
 impl ProjectSettings {
-  pub fn new(proj_name : &String) -> ProjectSettings {
-    ProjectSettings{name : proj_name.clone(), background : "".to_string(), strategic_goals : "".to_string(), scope : "".to_string(), naming_conventions : HashMap::new(), contributors : Vec::new(), revisions : Vec::new(), rich_picture : "".to_string()}
+  pub fn new(proj_name : &str) -> ProjectSettings {
+    ProjectSettings{name : proj_name.to_string(), background : "".to_string(), strategic_goals : "".to_string(), scope : "".to_string(), naming_conventions : HashMap::new(), contributors : Vec::new(), revisions : Vec::new(), rich_picture : "".to_string()}
   }
 }
 
@@ -34,3 +34,71 @@ impl fmt::Display for ProjectSettings {
     write!(f,"{}",x)
   }
 }
+
+pub struct ProjectSettingsBuilder {
+  settings : ProjectSettings
+}
+
+impl ProjectSettingsBuilder {
+  pub fn new(proj_name : &str) -> ProjectSettingsBuilder {
+    ProjectSettingsBuilder{settings : ProjectSettings::new(proj_name)}
+  }
+
+  pub fn background(mut self, txt : &str) -> ProjectSettingsBuilder {
+    self.settings.background = txt.to_string();
+    self
+  }
+
+  pub fn strategic_goals(mut self, txt : &str) -> ProjectSettingsBuilder {
+    self.settings.strategic_goals = txt.to_string();
+    self
+  }
+
+  pub fn scope(mut self, txt : &str) -> ProjectSettingsBuilder {
+    self.settings.scope = txt.to_string();
+    self
+  }
+
+  pub fn rich_picture(mut self, file_name : &str) -> ProjectSettingsBuilder {
+    self.settings.rich_picture = file_name.to_string();
+    self
+  }
+
+  pub fn naming_convention(mut self, name : &str, value : &str) -> ProjectSettingsBuilder {
+    self.settings.naming_conventions.insert(name.to_string(),value.to_string());
+    self
+  }
+
+  pub fn contributor(mut self, first_name : &str, surname : &str, affiliation : &str, role : &str) -> ProjectSettingsBuilder {
+    self.settings.contributors.push((first_name.to_string(),surname.to_string(),affiliation.to_string(),role.to_string()));
+    self
+  }
+
+  pub fn revision(mut self, rev_no : &str, rev_date : &str, remarks : &str) -> ProjectSettingsBuilder {
+    self.settings.revisions.push((rev_no.to_string(),rev_date.to_string(),remarks.to_string()));
+    self
+  }
+
+  pub fn build(self) -> Result<ProjectSettings,String> {
+    let ps = self.settings;
+    if ps.name.is_empty() {
+      return Err("Project has no name".to_string());
+    }
+    for (idx,rev) in ps.revisions.iter().enumerate() {
+      if ps.revisions[..idx].iter().any(|r| r.0 == rev.0) {
+        return Err(format!("Project {} has revision {} more than once",ps.name,rev.0));
+      }
+    }
+    Ok(ps)
+  }
+}
+
+#[test]
+fn test_project_settings_builder() {
+  let ps = ProjectSettingsBuilder::new("NeuroGrid").background("Clinical research").scope("Data sharing").naming_convention("PII","Personally identifiable information").contributor("Shamal","Faily","Bournemouth","Lead").revision("1","2024-01-01","Initial").build().unwrap();
+  assert_eq!(ps.scope,"Data sharing".to_string());
+  assert_eq!(ps.naming_conventions["PII"],"Personally identifiable information".to_string());
+  assert_eq!(ps.contributors.len(),1);
+  assert_eq!(ProjectSettingsBuilder::new("").build().err(),Some("Project has no name".to_string()));
+  assert_eq!(ProjectSettingsBuilder::new("NeuroGrid").revision("1","","").revision("1","","").build().err(),Some("Project NeuroGrid has revision 1 more than once".to_string()));
+}
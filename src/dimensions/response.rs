@@ -0,0 +1,270 @@
+use std::fmt;
+use crate::dimensions::dimension::{Dimension,Renamable,DimensionKind,Referencing,update_tags};
+use crate::dimensions::securityproperty::QualitativeValue;
+use crate::dimensions::role::Role;
+use crate::dimensions::tag::Tag;
+
+#[derive(Clone,PartialEq,Debug)]
+pub enum MitigationType {
+  Prevent,
+  Deter,
+  Detect,
+  React
+}
+
+impl MitigationType {
+  pub fn parse(m_type : &str) -> Result<MitigationType,String> {
+    match m_type {
+      "Prevent" => Ok(MitigationType::Prevent),
+      "Deter" => Ok(MitigationType::Deter),
+      "Detect" => Ok(MitigationType::Detect),
+      "React" => Ok(MitigationType::React),
+      &_ => Err(format!("{} is not a mitigation type",m_type))
+    }
+  }
+}
+
+impl fmt::Display for MitigationType {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      MitigationType::Prevent => write!(f,"Prevent"),
+      MitigationType::Deter => write!(f,"Deter"),
+      MitigationType::Detect => write!(f,"Detect"),
+      MitigationType::React => write!(f,"React")
+    }
+  }
+}
+
+#[derive(Clone,PartialEq,Debug)]
+pub enum ResponseType {
+  Accept { cost : QualitativeValue, description : String },
+  Transfer { description : String, roles : Vec<(String,QualitativeValue)> },
+  Mitigate { mitigation_type : MitigationType, detection_point : String }
+}
+
+impl fmt::Display for ResponseType {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ResponseType::Accept { cost, description } => write!(f,"Accept, Cost: {:?}, Description: {}",cost,description),
+      ResponseType::Transfer { description, roles } => {
+        let role_costs = roles.iter().map(|(r,c)| format!("{} ({:?})",r,c)).collect::<Vec<String>>().join(",");
+        write!(f,"Transfer, Description: {}, Roles: {}",description,role_costs)
+      },
+      ResponseType::Mitigate { mitigation_type, detection_point } => write!(f,"Mitigate, Type: {}, Detection point: {}",mitigation_type,detection_point)
+    }
+  }
+}
+
+#[derive(Clone,PartialEq)]
+pub struct ResponseEnvironment {
+  name : String,
+  pub response_type : ResponseType
+}
+
+impl ResponseEnvironment {
+  pub fn accept(env_name : &str, cost : &str, desc : &str) -> ResponseEnvironment {
+    ResponseEnvironment { name: env_name.to_string(), response_type: ResponseType::Accept { cost: QualitativeValue::parse(cost).unwrap_or_else(|e| panic!("{}",e)), description: desc.to_string() } }
+  }
+
+  pub fn transfer(env_name : &str, desc : &str) -> ResponseEnvironment {
+    ResponseEnvironment { name: env_name.to_string(), response_type: ResponseType::Transfer { description: desc.to_string(), roles: Vec::<(String,QualitativeValue)>::new() } }
+  }
+
+  pub fn mitigate(env_name : &str, m_type : &str, d_point : &str) -> ResponseEnvironment {
+    ResponseEnvironment {
+      name: env_name.to_string(),
+      response_type: ResponseType::Mitigate {
+        mitigation_type: MitigationType::parse(m_type).unwrap_or_else(|e| panic!("{}",e)),
+        detection_point: d_point.to_string()
+      }
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn add_role(&mut self, r : &Role, cost : &str) {
+    match &mut self.response_type {
+      ResponseType::Transfer { roles, .. } => roles.push((r.name().to_string(),QualitativeValue::parse(cost).unwrap_or_else(|e| panic!("{}",e)))),
+      _ => panic!("Roles can only be added to a transfer response")
+    }
+  }
+
+  fn roles(&self) -> Vec<&str> {
+    match &self.response_type {
+      ResponseType::Transfer { roles, .. } => roles.iter().map(|(r,_)| r.as_str()).collect(),
+      _ => Vec::<&str>::new()
+    }
+  }
+
+  fn update_roles(&mut self, old_name : &str, new_name : Option<&str>) -> usize {
+    match &mut self.response_type {
+      ResponseType::Transfer { roles, .. } => {
+        let count = roles.iter().filter(|(r,_)| r == old_name).count();
+        match new_name {
+          Some(n) => roles.iter_mut().filter(|(r,_)| r == old_name).for_each(|(r,_)| *r = n.to_string()),
+          None => roles.retain(|(r,_)| r != old_name)
+        }
+        count
+      },
+      _ => 0
+    }
+  }
+}
+
+#[test]
+fn test_accept_response_environment() {
+  let re = ResponseEnvironment::accept("Default","Low","Cheaper to live with");
+  assert_eq!(re.name,"Default".to_string());
+  assert_eq!(re.response_type,ResponseType::Accept { cost: QualitativeValue::Low, description: "Cheaper to live with".to_string() });
+}
+
+#[test]
+fn test_transfer_response_environment() {
+  let mut re = ResponseEnvironment::transfer("Default","Insure against it");
+  re.add_role(&Role::new("Insurer","Stakeholder","INS","An insurer"),"Medium");
+  assert_eq!(re.response_type,ResponseType::Transfer { description: "Insure against it".to_string(), roles: vec![("Insurer".to_string(),QualitativeValue::Medium)] });
+}
+
+#[test]
+fn test_mitigate_response_environment() {
+  let re = ResponseEnvironment::mitigate("Default","Detect","At");
+  assert_eq!(re.response_type,ResponseType::Mitigate { mitigation_type: MitigationType::Detect, detection_point: "At".to_string() });
+}
+
+#[test]
+#[should_panic]
+fn test_mitigate_response_environment_panics() {
+  ResponseEnvironment::mitigate("Default","Ignore","At");
+}
+
+#[test]
+#[should_panic]
+fn test_accept_response_add_role_panics() {
+  let mut re = ResponseEnvironment::accept("Default","Low","TBC");
+  re.add_role(&Role::new("Insurer","Stakeholder","INS","An insurer"),"Medium");
+}
+
+impl fmt::Display for ResponseEnvironment {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f,"Environment: {}, Response: {}",self.name,self.response_type)
+  }
+}
+
+#[derive(Clone,PartialEq)]
+pub struct Response {
+  name : String,
+  risk : String,
+  pub tags : Vec<Tag>,
+  pub environments : Vec<ResponseEnvironment>
+}
+
+impl Response {
+  pub fn new(resp_name : &str, risk_name : &str) -> Response {
+    Response { name: resp_name.to_string(), risk: risk_name.to_string(), tags: Vec::<Tag>::new(), environments: Vec::<ResponseEnvironment>::new() }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn risk(&self) -> &str {
+    &self.risk
+  }
+
+  pub fn set_risk(&mut self, risk_name : &str) {
+    self.risk = risk_name.to_string();
+  }
+}
+
+#[test]
+fn test_new_response() {
+  let r = Response::new("Accept Upload risk","Upload risk");
+  assert_eq!(r.name,"Accept Upload risk".to_string());
+  assert_eq!(r.risk,"Upload risk".to_string());
+  assert_eq!(r.tags.len(),0);
+  assert_eq!(r.environments.len(),0);
+}
+
+impl fmt::Display for Response {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let envs = &self.environments.iter().map(|env| env.to_string()).collect::<Vec<String>>().join(",");
+    let tags = &self.tags.iter().map(|t| t.name.clone()).collect::<Vec<String>>().join(",");
+    write!(f,"Name: {}, Risk: {}, Tags: {}, Environments: {}",self.name,self.risk,tags,envs)
+  }
+}
+
+impl Dimension for Response {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::Response
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn tags(&self) -> &[Tag] {
+    &self.tags
+  }
+
+  fn environment_names(&self) -> Vec<String> {
+    self.environments.iter().map(|re| re.name.clone()).collect()
+  }
+}
+
+impl Renamable for Response {
+  fn set_name(&mut self, new_name : &str) {
+    self.name = new_name.to_string();
+  }
+}
+
+impl Referencing for Response {
+  fn references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    match kind {
+      DimensionKind::Environment => self.environments.iter().filter(|re| re.name == objt_name).count(),
+      DimensionKind::Risk => usize::from(self.risk == objt_name),
+      DimensionKind::Role => self.environments.iter().map(|re| re.roles().iter().filter(|r| **r == objt_name).count()).sum(),
+      DimensionKind::Tag => self.tags.iter().filter(|t| t.name == objt_name).count(),
+      _ => 0
+    }
+  }
+
+  fn requires(&self, kind : DimensionKind, objt_name : &str) -> bool {
+    kind == DimensionKind::Risk && self.references(kind,objt_name) > 0
+  }
+
+  fn update_references(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    match kind {
+      DimensionKind::Environment => {
+        let count = self.references(kind,old_name);
+        match new_name {
+          Some(n) => self.environments.iter_mut().filter(|re| re.name == old_name).for_each(|re| re.name = n.to_string()),
+          None => self.environments.retain(|re| re.name != old_name)
+        }
+        count
+      },
+      DimensionKind::Risk if self.risk == old_name => {
+        self.risk = new_name.unwrap_or("").to_string();
+        1
+      },
+      DimensionKind::Role => self.environments.iter_mut().map(|re| re.update_roles(old_name,new_name)).sum(),
+      DimensionKind::Tag => update_tags(&mut self.tags,old_name,new_name),
+      _ => 0
+    }
+  }
+}
+
+#[test]
+fn test_response_references() {
+  let mut r = Response::new("Insure Upload risk","Upload risk");
+  let mut re = ResponseEnvironment::transfer("Day","Insure against it");
+  re.add_role(&Role::new("Insurer","Stakeholder","INS","An insurer"),"Medium");
+  r.environments.push(re);
+  assert!(r.requires(DimensionKind::Risk,"Upload risk"));
+  assert_eq!(r.references(DimensionKind::Role,"Insurer"),1);
+  assert_eq!(r.update_references(DimensionKind::Role,"Insurer",Some("Underwriter")),1);
+  assert_eq!(r.environments[0].response_type,ResponseType::Transfer { description: "Insure against it".to_string(), roles: vec![("Underwriter".to_string(),QualitativeValue::Medium)] });
+  assert_eq!(r.update_references(DimensionKind::Risk,"Upload risk",Some("Injection risk")),1);
+  assert_eq!(r.risk(),"Injection risk");
+}
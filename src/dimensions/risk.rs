@@ -0,0 +1,87 @@
+use std::fmt;
+use crate::dimensions::dimension::{Dimension,Renamable,DimensionKind,Referencing,update_tags};
+use crate::dimensions::tag::Tag;
+
+#[derive(Clone,PartialEq)]
+pub struct Risk {
+  name : String,
+  pub threat : String,
+  pub vulnerability : String,
+  pub tags : Vec<Tag>
+}
+
+impl Risk {
+  pub fn new(risk_name : &str, threat_name : &str, vul_name : &str) -> Risk {
+    Risk { name: risk_name.to_string(), threat: threat_name.to_string(), vulnerability: vul_name.to_string(), tags: Vec::<Tag>::new() }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+}
+
+#[test]
+fn test_new_risk() {
+  let r = Risk::new("Upload risk","SQL injection","Unvalidated input");
+  assert_eq!(r.name,"Upload risk".to_string());
+  assert_eq!(r.threat,"SQL injection".to_string());
+  assert_eq!(r.vulnerability,"Unvalidated input".to_string());
+  assert_eq!(r.tags.len(),0);
+}
+
+impl fmt::Display for Risk {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let tags = &self.tags.iter().map(|t| t.name.clone()).collect::<Vec<String>>().join(",");
+    write!(f,"Name: {}, Threat: {}, Vulnerability: {}, Tags: {}",self.name,self.threat,self.vulnerability,tags)
+  }
+}
+
+impl Dimension for Risk {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::Risk
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn tags(&self) -> &[Tag] {
+    &self.tags
+  }
+}
+
+impl Renamable for Risk {
+  fn set_name(&mut self, new_name : &str) {
+    self.name = new_name.to_string();
+  }
+}
+
+impl Referencing for Risk {
+  fn references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    match kind {
+      DimensionKind::Threat => usize::from(self.threat == objt_name),
+      DimensionKind::Vulnerability => usize::from(self.vulnerability == objt_name),
+      DimensionKind::Tag => self.tags.iter().filter(|t| t.name == objt_name).count(),
+      _ => 0
+    }
+  }
+
+  fn requires(&self, kind : DimensionKind, objt_name : &str) -> bool {
+    kind != DimensionKind::Tag && self.references(kind,objt_name) > 0
+  }
+
+  fn update_references(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    match kind {
+      DimensionKind::Threat if self.threat == old_name => {
+        self.threat = new_name.unwrap_or("").to_string();
+        1
+      },
+      DimensionKind::Vulnerability if self.vulnerability == old_name => {
+        self.vulnerability = new_name.unwrap_or("").to_string();
+        1
+      },
+      DimensionKind::Tag => update_tags(&mut self.tags,old_name,new_name),
+      _ => 0
+    }
+  }
+}
@@ -1,7 +1,8 @@
 use std::fmt;
+use crate::dimensions::dimension::{Dimension,Renamable,DimensionKind,Referencing};
 
-#[derive(Clone)]
-enum RoleType {
+#[derive(Clone,PartialEq,Debug)]
+pub enum RoleType {
   Stakeholder,
   Attacker,
   DataController,
@@ -10,6 +11,20 @@ enum RoleType {
   Machine
 }
 
+impl RoleType {
+  pub fn parse(r_type : &str) -> Result<RoleType,String> {
+    match r_type {
+      "Stakeholder" => Ok(RoleType::Stakeholder),
+      "Attacker" => Ok(RoleType::Attacker),
+      "Data Controller" => Ok(RoleType::DataController),
+      "Data Processor" => Ok(RoleType::DataProcessor),
+      "Data Subject" => Ok(RoleType::DataSubject),
+      "Machine" => Ok(RoleType::Machine),
+      _ => Err(format!("{} is an invalid role type",r_type))
+    }
+  }
+}
+
 impl fmt::Display for RoleType {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     match self {
@@ -32,32 +47,114 @@ pub struct Role {
 }
 
 impl Role {
-  pub fn new(role_name: &String, r_type: &String, s_code: &String, r_desc: &String) -> Role {
+  pub fn new(role_name: &str, r_type: &str, s_code: &str, r_desc: &str) -> Role {
     Role{
-      name : role_name.clone(), 
-      role_type : 
-        match r_type.as_str() {
-          "Stakeholder" => RoleType::Stakeholder,
-          "Attacker" => RoleType::Attacker,
-          "Data Controller" => RoleType::DataController,
-          "Data Processor" => RoleType::DataProcessor,
-          "Data Subject" => RoleType::DataSubject,
-          "Machine" => RoleType::Machine,
-          _ => panic!("{} is an invalid role type",r_type)
-        }, 
-      short_code : s_code.clone(), 
-      description : r_desc.clone()}
+      name : role_name.to_string(), 
+      role_type : RoleType::parse(r_type).unwrap_or_else(|e| panic!("{}",e)),
+      short_code : s_code.to_string(), 
+      description : r_desc.to_string()}
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn role_type(&self) -> &RoleType {
+    &self.role_type
+  }
+
+  pub fn set_role_type(&mut self, r_type : &str) {
+    self.role_type = RoleType::parse(r_type).unwrap_or_else(|e| panic!("{}",e));
+  }
+
+  pub fn short_code(&self) -> &str {
+    &self.short_code
+  }
+
+  pub fn set_short_code(&mut self, s_code : &str) {
+    self.short_code = s_code.to_string();
   }
 }
 
 impl fmt::Display for Role {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    write!(f,"Name: {}, Type: {}, Short code: {}, Description: {}",self.name,self.role_type.to_string(),self.short_code,self.description)
+    write!(f,"Name: {}, Type: {}, Short code: {}, Description: {}",self.name,self.role_type,self.short_code,self.description)
+  }
+}
+
+impl Dimension for Role {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::Role
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn short_code(&self) -> Option<&str> {
+    Some(&self.short_code)
+  }
+}
+
+impl Renamable for Role {
+  fn set_name(&mut self, new_name : &str) {
+    self.name = new_name.to_string();
   }
 }
 
+impl Referencing for Role {}
+
 #[test]
 pub fn test_new_role() {
-  let r = Role::new(&"A role".to_string(),&"Stakeholder".to_string(),&"AR".to_string(),&"A role description".to_string());
+  let r = Role::new("A role","Stakeholder","AR","A role description");
   assert_eq!(r.name,"A role".to_string()); 
 }
+
+pub struct RoleBuilder {
+  name : String,
+  role_type : String,
+  short_code : String,
+  description : String
+}
+
+impl RoleBuilder {
+  pub fn new(role_name : &str) -> RoleBuilder {
+    RoleBuilder{name : role_name.to_string(), role_type : "".to_string(), short_code : "".to_string(), description : "".to_string()}
+  }
+
+  pub fn role_type(mut self, r_type : &str) -> RoleBuilder {
+    self.role_type = r_type.to_string();
+    self
+  }
+
+  pub fn short_code(mut self, s_code : &str) -> RoleBuilder {
+    self.short_code = s_code.to_string();
+    self
+  }
+
+  pub fn description(mut self, r_desc : &str) -> RoleBuilder {
+    self.description = r_desc.to_string();
+    self
+  }
+
+  pub fn build(self) -> Result<Role,String> {
+    if self.name.is_empty() {
+      return Err("Role has no name".to_string());
+    }
+    if self.short_code.is_empty() {
+      return Err(format!("Role {} has no short code",self.name));
+    }
+    let role_type = RoleType::parse(&self.role_type).map_err(|e| format!("Role {}: {}",self.name,e))?;
+    Ok(Role{name : self.name, role_type, short_code : self.short_code, description : self.description})
+  }
+}
+
+#[test]
+fn test_role_builder() {
+  let r = RoleBuilder::new("Researcher").role_type("Data Processor").short_code("RES").description("Analyses data").build().unwrap();
+  assert_eq!(r.name(),"Researcher");
+  assert_eq!(*r.role_type(),RoleType::DataProcessor);
+  assert_eq!(r.description,"Analyses data".to_string());
+  assert_eq!(RoleBuilder::new("Researcher").short_code("RES").build().err(),Some("Role Researcher:  is an invalid role type".to_string()));
+  assert_eq!(RoleBuilder::new("Researcher").role_type("Hacker").short_code("RES").build().err(),Some("Role Researcher: Hacker is an invalid role type".to_string()));
+}
@@ -12,7 +12,7 @@ pub enum SecurityProperty {
   Unobservability = 7
 }
 
-#[derive(Clone,PartialEq,Debug)]
+#[derive(Clone,PartialEq,PartialOrd,Debug)]
 pub enum QualitativeValue {
   None = 0,
   Low = 1,
@@ -20,6 +20,34 @@ pub enum QualitativeValue {
   High = 3
 }
 
+impl SecurityProperty {
+  pub fn parse(sp : &str) -> Result<SecurityProperty,String> {
+    match sp {
+      "confidentiality" => Ok(SecurityProperty::Confidentiality),
+      "integrity" => Ok(SecurityProperty::Integrity),
+      "availability" => Ok(SecurityProperty::Availability),
+      "accountability" => Ok(SecurityProperty::Accountability),
+      "anonymity" => Ok(SecurityProperty::Anonymity),
+      "pseudonymity" => Ok(SecurityProperty::Pseudonymity),
+      "unlinkability" => Ok(SecurityProperty::Unlinkability),
+      "unobservability" => Ok(SecurityProperty::Unobservability),
+      &_ => Err(format!("{} is not a security property",sp))
+    }
+  }
+}
+
+impl QualitativeValue {
+  pub fn parse(v : &str) -> Result<QualitativeValue,String> {
+    match v {
+      "None" => Ok(QualitativeValue::None),
+      "Low" => Ok(QualitativeValue::Low),
+      "Medium" => Ok(QualitativeValue::Medium),
+      "High" => Ok(QualitativeValue::High),
+      &_ => Err(format!("{} is not a qualitative value",v))
+    }
+  }
+}
+
 #[derive(Clone)]
 pub struct SecurityPropertyValue {
   pub name : SecurityProperty,
@@ -31,26 +59,8 @@ impl SecurityPropertyValue {
 
   pub fn new(sp : &str, v : &str, r: &str) -> SecurityPropertyValue {
     SecurityPropertyValue{ 
-      name : 
-        match sp {
-          "confidentiality" => SecurityProperty::Confidentiality,
-          "integrity" => SecurityProperty::Integrity,
-          "availability" => SecurityProperty::Availability,
-          "accountability" => SecurityProperty::Accountability,
-          "anonymity" => SecurityProperty::Anonymity,
-          "pseudonymity" => SecurityProperty::Pseudonymity,
-          "unlinkability" => SecurityProperty::Unlinkability,
-          "unobservability" => SecurityProperty::Unobservability,
-          &_ => panic!("{} is not a property value",sp)
-        },
-      value : 
-        match v {
-          "None" => QualitativeValue::None,
-          "Low" => QualitativeValue::Low,
-          "Medium" => QualitativeValue::Medium,
-          "High" => QualitativeValue::High,
-          &_ => QualitativeValue::None
-        },
+      name : SecurityProperty::parse(sp).unwrap_or_else(|e| panic!("{}",e)),
+      value : QualitativeValue::parse(v).unwrap_or(QualitativeValue::None),
       rationale: r.to_string()
     }
   }
@@ -91,3 +101,11 @@ fn test_new_security_property_panics() {
   SecurityPropertyValue::new("foo","None","None");
 }
  
+
+#[test]
+fn test_parse_security_property() {
+  assert_eq!(SecurityProperty::parse("anonymity"),Ok(SecurityProperty::Anonymity));
+  assert_eq!(SecurityProperty::parse("secrecy"),Err("secrecy is not a security property".to_string()));
+  assert_eq!(QualitativeValue::parse("Medium"),Ok(QualitativeValue::Medium));
+  assert_eq!(QualitativeValue::parse("Severe"),Err("Severe is not a qualitative value".to_string()));
+}
@@ -1,4 +1,5 @@
 use std::fmt;
+use crate::dimensions::dimension::{Dimension,DimensionKind,Referencing};
 
 #[derive(Clone,PartialEq)]
 pub struct Tag {
@@ -6,8 +7,8 @@ pub struct Tag {
 }
 
 impl Tag {
-  pub fn new(t_name: &String) -> Tag {
-    Tag{ name : t_name.clone()}
+  pub fn new(t_name: &str) -> Tag {
+    Tag{ name : t_name.to_string()}
   }
 }
 
@@ -16,3 +17,15 @@ impl fmt::Display for Tag {
     write!(f,"Tag: {}",self.name)
   }
 }
+
+impl Dimension for Tag {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::Tag
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+}
+
+impl Referencing for Tag {}
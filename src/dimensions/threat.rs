@@ -0,0 +1,211 @@
+use std::fmt;
+use crate::dimensions::dimension::{Dimension,Renamable,DimensionKind,Referencing,count_names,update_names,update_tags,value_type_name};
+use crate::dimensions::securityproperty::SecurityPropertyValue;
+use crate::dimensions::tag::Tag;
+
+#[derive(Clone,PartialEq,Debug)]
+pub enum Likelihood {
+  Incredible = 0,
+  Improbable = 1,
+  Remote = 2,
+  Occasional = 3,
+  Probable = 4,
+  Frequent = 5
+}
+
+impl fmt::Display for Likelihood {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Likelihood::Incredible => write!(f,"Incredible"),
+      Likelihood::Improbable => write!(f,"Improbable"),
+      Likelihood::Remote => write!(f,"Remote"),
+      Likelihood::Occasional => write!(f,"Occasional"),
+      Likelihood::Probable => write!(f,"Probable"),
+      Likelihood::Frequent => write!(f,"Frequent")
+    }
+  }
+}
+
+impl Likelihood {
+  pub fn parse(lhood : &str) -> Result<Likelihood,String> {
+    match lhood {
+      "Incredible" => Ok(Likelihood::Incredible),
+      "Improbable" => Ok(Likelihood::Improbable),
+      "Remote" => Ok(Likelihood::Remote),
+      "Occasional" => Ok(Likelihood::Occasional),
+      "Probable" => Ok(Likelihood::Probable),
+      "Frequent" => Ok(Likelihood::Frequent),
+      _ => Err(format!("{} is an invalid likelihood value",lhood))
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct ThreatEnvironment {
+  name : String,
+  likelihood : Likelihood,
+  pub assets : Vec<String>,
+  pub attackers : Vec<String>,
+  pub properties : Vec<SecurityPropertyValue>
+}
+
+impl ThreatEnvironment {
+  pub fn new(env_name : &str, lhood : &str) -> ThreatEnvironment {
+    ThreatEnvironment {
+      name: env_name.to_string(),
+      likelihood: Likelihood::parse(lhood).unwrap_or_else(|e| panic!("{}",e)),
+      assets: Vec::<String>::new(),
+      attackers: Vec::<String>::new(),
+      properties: Vec::<SecurityPropertyValue>::new()
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn likelihood(&self) -> &Likelihood {
+    &self.likelihood
+  }
+
+  pub fn set_likelihood(&mut self, lhood : &str) {
+    self.likelihood = Likelihood::parse(lhood).unwrap_or_else(|e| panic!("{}",e));
+  }
+}
+
+#[test]
+fn test_new_threat_environment() {
+  let te = ThreatEnvironment::new("Default","Remote");
+  assert_eq!(te.name,"Default".to_string());
+  assert_eq!(te.likelihood,Likelihood::Remote);
+  assert_eq!(te.assets.len(),0);
+  assert_eq!(te.attackers.len(),0);
+  assert_eq!(te.properties.len(),0);
+}
+
+#[test]
+#[should_panic]
+fn test_new_threat_environment_panics() {
+  ThreatEnvironment::new("Default","Sometimes");
+}
+
+impl fmt::Display for ThreatEnvironment {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let props = &self.properties.iter().map(|p| p.to_string()).collect::<Vec<String>>().join(",");
+    write!(f,"Environment: {}, Likelihood: {}, Assets: {}, Attackers: {}, Properties: {}",self.name,self.likelihood,self.assets.join(","),self.attackers.join(","),props)
+  }
+}
+
+#[derive(Clone)]
+pub struct Threat {
+  name : String,
+  threat_type : String,
+  pub method : String,
+  pub tags : Vec<Tag>,
+  pub environments : Vec<ThreatEnvironment>
+}
+
+impl Threat {
+  pub fn new(threat_name : &str, threat_type : &str) -> Threat {
+    Threat { name: threat_name.to_string(), threat_type: threat_type.to_string(), method: "".to_string(), tags: Vec::<Tag>::new(), environments: Vec::<ThreatEnvironment>::new() }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn threat_type(&self) -> &str {
+    &self.threat_type
+  }
+
+  pub fn set_threat_type(&mut self, t_type : &str) {
+    self.threat_type = t_type.to_string();
+  }
+}
+
+#[test]
+fn test_new_threat() {
+  let t = Threat::new("SQL injection","Software");
+  assert_eq!(t.name,"SQL injection".to_string());
+  assert_eq!(t.threat_type,"Software".to_string());
+  assert_eq!(t.method,"".to_string());
+  assert_eq!(t.tags.len(),0);
+  assert_eq!(t.environments.len(),0);
+}
+
+impl fmt::Display for Threat {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let envs = &self.environments.iter().map(|env| env.to_string()).collect::<Vec<String>>().join(",");
+    let tags = &self.tags.iter().map(|t| t.name.clone()).collect::<Vec<String>>().join(",");
+    write!(f,"Name: {}, Type: {}, Method: {}, Tags: {}, Environments: {}",self.name,self.threat_type,self.method,tags,envs)
+  }
+}
+
+impl Dimension for Threat {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::Threat
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn tags(&self) -> &[Tag] {
+    &self.tags
+  }
+
+  fn environment_names(&self) -> Vec<String> {
+    self.environments.iter().map(|te| te.name.clone()).collect()
+  }
+}
+
+impl Renamable for Threat {
+  fn set_name(&mut self, new_name : &str) {
+    self.name = new_name.to_string();
+  }
+}
+
+impl Referencing for Threat {
+  fn defined_by_environments(&self) -> bool {
+    true
+  }
+
+  fn references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    match kind {
+      DimensionKind::Environment => self.environments.iter().filter(|te| te.name == objt_name).count(),
+      DimensionKind::Asset => self.environments.iter().map(|te| count_names(&te.assets,objt_name)).sum(),
+      DimensionKind::Attacker => self.environments.iter().map(|te| count_names(&te.attackers,objt_name)).sum(),
+      DimensionKind::ValueType => usize::from(value_type_name(objt_name,"threat_type") == Some(self.threat_type.as_str())),
+      DimensionKind::Tag => self.tags.iter().filter(|t| t.name == objt_name).count(),
+      _ => 0
+    }
+  }
+
+  fn requires(&self, kind : DimensionKind, objt_name : &str) -> bool {
+    kind == DimensionKind::ValueType && self.references(kind,objt_name) > 0
+  }
+
+  fn update_references(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    match kind {
+      DimensionKind::Environment => {
+        let count = self.references(kind,old_name);
+        match new_name {
+          Some(n) => self.environments.iter_mut().filter(|te| te.name == old_name).for_each(|te| te.name = n.to_string()),
+          None => self.environments.retain(|te| te.name != old_name)
+        }
+        count
+      },
+      DimensionKind::Asset => self.environments.iter_mut().map(|te| update_names(&mut te.assets,old_name,new_name)).sum(),
+      DimensionKind::Attacker => self.environments.iter_mut().map(|te| update_names(&mut te.attackers,old_name,new_name)).sum(),
+      DimensionKind::ValueType => {
+        if self.references(kind,old_name) == 0 {
+          return 0;
+        }
+        self.threat_type = new_name.and_then(|n| value_type_name(n,"threat_type")).unwrap_or("").to_string();
+        1
+      },
+      DimensionKind::Tag => update_tags(&mut self.tags,old_name,new_name),
+      _ => 0
+    }
+  }
+}
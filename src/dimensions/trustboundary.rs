@@ -0,0 +1,188 @@
+use std::fmt;
+use crate::dimensions::dimension::{Dimension,Renamable,DimensionKind,Referencing,update_tags};
+use crate::dimensions::dataflow::{DataFlow,DataFlowComponentType};
+use crate::dimensions::tag::Tag;
+
+#[derive(Clone,PartialEq)]
+pub struct TrustBoundaryEnvironment {
+  name : String,
+  pub privilege : String,
+  pub components : Vec<(DataFlowComponentType,String)>
+}
+
+impl TrustBoundaryEnvironment {
+  pub fn new(env_name : &str, priv_level : &str) -> TrustBoundaryEnvironment {
+    TrustBoundaryEnvironment { name: env_name.to_string(), privilege: priv_level.to_string(), components: Vec::<(DataFlowComponentType,String)>::new() }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn add_component(&mut self, c_type : &str, c_name : &str) {
+    self.components.push((DataFlowComponentType::new(c_type),c_name.to_string()));
+  }
+
+  pub fn contains(&self, c_type : &DataFlowComponentType, c_name : &str) -> bool {
+    self.components.iter().any(|(t,n)| t == c_type && n == c_name)
+  }
+
+  fn component_references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    self.components.iter().filter(|(t,n)| t.dimension() == kind && n == objt_name).count()
+  }
+
+  fn update_components(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    let count = self.component_references(kind,old_name);
+    match new_name {
+      Some(nn) => self.components.iter_mut().filter(|(t,n)| t.dimension() == kind && n == old_name).for_each(|(_,n)| *n = nn.to_string()),
+      None => self.components.retain(|(t,n)| t.dimension() != kind || n != old_name)
+    }
+    count
+  }
+}
+
+#[test]
+fn test_new_trust_boundary_environment() {
+  let mut tbe = TrustBoundaryEnvironment::new("Default","Administrator");
+  assert_eq!(tbe.name,"Default".to_string());
+  assert_eq!(tbe.privilege,"Administrator".to_string());
+  assert_eq!(tbe.components.len(),0);
+  tbe.add_component("process","Upload data");
+  assert!(tbe.contains(&DataFlowComponentType::Process,"Upload data"));
+  assert!(!tbe.contains(&DataFlowComponentType::Datastore,"Upload data"));
+}
+
+impl fmt::Display for TrustBoundaryEnvironment {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let comps = &self.components.iter().map(|(t,n)| format!("{} ({})",n,t)).collect::<Vec<String>>().join(",");
+    write!(f,"Environment: {}, Privilege: {}, Components: {}",self.name,self.privilege,comps)
+  }
+}
+
+#[derive(Clone,PartialEq)]
+pub struct TrustBoundary {
+  name : String,
+  boundary_type : String,
+  pub description : String,
+  pub tags : Vec<Tag>,
+  pub environments : Vec<TrustBoundaryEnvironment>
+}
+
+impl TrustBoundary {
+  pub fn new(tb_name : &str, tb_type : &str) -> TrustBoundary {
+    TrustBoundary { name: tb_name.to_string(), boundary_type: tb_type.to_string(), description: "".to_string(), tags: Vec::<Tag>::new(), environments: Vec::<TrustBoundaryEnvironment>::new() }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn boundary_type(&self) -> &str {
+    &self.boundary_type
+  }
+
+  pub fn set_boundary_type(&mut self, tb_type : &str) {
+    self.boundary_type = tb_type.to_string();
+  }
+
+  pub fn crossed_by(&self, df : &DataFlow) -> bool {
+    self.environments.iter().any(|tbe| {
+      tbe.name == df.environment() && tbe.contains(&df.source_type,&df.source_name) != tbe.contains(&df.target_type,&df.target_name)
+    })
+  }
+}
+
+#[test]
+fn test_new_trust_boundary() {
+  let tb = TrustBoundary::new("Server room","Physical");
+  assert_eq!(tb.name,"Server room".to_string());
+  assert_eq!(tb.boundary_type,"Physical".to_string());
+  assert_eq!(tb.description,"".to_string());
+  assert_eq!(tb.tags.len(),0);
+  assert_eq!(tb.environments.len(),0);
+}
+
+#[test]
+fn test_trust_boundary_crossed_by() {
+  let mut tb = TrustBoundary::new("Server room","Physical");
+  let mut tbe = TrustBoundaryEnvironment::new("Default","Administrator");
+  tbe.add_component("process","Upload data");
+  tb.environments.push(tbe);
+  assert!(tb.crossed_by(&DataFlow::new("Upload","Default","Researcher","entity","Upload data","process")));
+  assert!(!tb.crossed_by(&DataFlow::new("Upload","Night","Researcher","entity","Upload data","process")));
+  assert!(!tb.crossed_by(&DataFlow::new("Download","Default","Repository","datastore","Researcher","entity")));
+}
+
+impl fmt::Display for TrustBoundary {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let envs = &self.environments.iter().map(|env| env.to_string()).collect::<Vec<String>>().join(",");
+    let tags = &self.tags.iter().map(|t| t.name.clone()).collect::<Vec<String>>().join(",");
+    write!(f,"Name: {}, Type: {}, Description: {}, Tags: {}, Environments: {}",self.name,self.boundary_type,self.description,tags,envs)
+  }
+}
+
+impl Dimension for TrustBoundary {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::TrustBoundary
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn tags(&self) -> &[Tag] {
+    &self.tags
+  }
+
+  fn environment_names(&self) -> Vec<String> {
+    self.environments.iter().map(|tbe| tbe.name.clone()).collect()
+  }
+}
+
+impl Renamable for TrustBoundary {
+  fn set_name(&mut self, new_name : &str) {
+    self.name = new_name.to_string();
+  }
+}
+
+impl Referencing for TrustBoundary {
+  fn references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    match kind {
+      DimensionKind::Environment => self.environments.iter().filter(|tbe| tbe.name == objt_name).count(),
+      DimensionKind::Asset | DimensionKind::UseCase => self.environments.iter().map(|tbe| tbe.component_references(kind,objt_name)).sum(),
+      DimensionKind::Tag => self.tags.iter().filter(|t| t.name == objt_name).count(),
+      _ => 0
+    }
+  }
+
+  fn update_references(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    match kind {
+      DimensionKind::Environment => {
+        let count = self.references(kind,old_name);
+        match new_name {
+          Some(n) => self.environments.iter_mut().filter(|tbe| tbe.name == old_name).for_each(|tbe| tbe.name = n.to_string()),
+          None => self.environments.retain(|tbe| tbe.name != old_name)
+        }
+        count
+      },
+      DimensionKind::Asset | DimensionKind::UseCase => self.environments.iter_mut().map(|tbe| tbe.update_components(kind,old_name,new_name)).sum(),
+      DimensionKind::Tag => update_tags(&mut self.tags,old_name,new_name),
+      _ => 0
+    }
+  }
+}
+
+#[test]
+fn test_trust_boundary_references() {
+  let mut tb = TrustBoundary::new("Server room","Physical");
+  let mut tbe = TrustBoundaryEnvironment::new("Day","Administrator");
+  tbe.add_component("process","Upload data");
+  tbe.add_component("datastore","Data");
+  tb.environments.push(tbe);
+  assert_eq!(tb.references(DimensionKind::UseCase,"Upload data"),1);
+  assert_eq!(tb.references(DimensionKind::Asset,"Upload data"),0);
+  assert_eq!(tb.update_references(DimensionKind::Asset,"Data",Some("Records")),1);
+  assert!(tb.environments[0].contains(&DataFlowComponentType::Datastore,"Records"));
+  assert_eq!(tb.update_references(DimensionKind::UseCase,"Upload data",None),1);
+  assert_eq!(tb.environments[0].components.len(),1);
+}
@@ -0,0 +1,245 @@
+use std::fmt;
+use crate::dimensions::dimension::{Dimension,Renamable,DimensionKind,Referencing,count_names,update_names,update_tags};
+use crate::dimensions::tag::Tag;
+use crate::dimensions::role::Role;
+
+#[derive(Clone,PartialEq)]
+pub struct StepException {
+  pub name : String,
+  pub category : String,
+  pub definition : String
+}
+
+impl StepException {
+  pub fn new(exc_name : &str, exc_cat : &str, exc_def : &str) -> StepException {
+    StepException { name: exc_name.to_string(), category: exc_cat.to_string(), definition: exc_def.to_string() }
+  }
+}
+
+impl fmt::Display for StepException {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f,"Exception: {}, Category: {}, Definition: {}",self.name,self.category,self.definition)
+  }
+}
+
+#[derive(Clone,PartialEq)]
+pub struct Step {
+  pub text : String,
+  pub exceptions : Vec<StepException>
+}
+
+impl Step {
+  pub fn new(step_txt : &str) -> Step {
+    Step { text: step_txt.to_string(), exceptions: Vec::<StepException>::new() }
+  }
+}
+
+#[derive(Clone,PartialEq)]
+pub struct UseCaseEnvironment {
+  name : String,
+  pub preconditions : String,
+  pub postconditions : String,
+  pub steps : Vec<Step>
+}
+
+impl UseCaseEnvironment {
+  pub fn new(env_name : &str) -> UseCaseEnvironment {
+    UseCaseEnvironment { name: env_name.to_string(), preconditions: "".to_string(), postconditions: "".to_string(), steps: Vec::<Step>::new() }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn add_step(&mut self, step_txt : &str) -> usize {
+    self.steps.push(Step::new(step_txt));
+    self.steps.len()
+  }
+
+  pub fn add_exception(&mut self, step_no : usize, exc : StepException) {
+    match self.steps.get_mut(step_no.wrapping_sub(1)) {
+      Some(s) => s.exceptions.push(exc),
+      None => panic!("{} is not a valid step number",step_no)
+    }
+  }
+}
+
+#[test]
+fn test_new_use_case_environment() {
+  let uce = UseCaseEnvironment::new("Default");
+  assert_eq!(uce.name,"Default".to_string());
+  assert_eq!(uce.preconditions,"".to_string());
+  assert_eq!(uce.postconditions,"".to_string());
+  assert_eq!(uce.steps.len(),0);
+}
+
+#[test]
+fn test_use_case_environment_steps() {
+  let mut uce = UseCaseEnvironment::new("Default");
+  assert_eq!(uce.add_step("User logs in"),1);
+  assert_eq!(uce.add_step("User uploads data"),2);
+  uce.add_exception(2,StepException::new("Upload fails","Availability threat","Network is unavailable"));
+  assert_eq!(uce.steps[0].exceptions.len(),0);
+  assert_eq!(uce.steps[1].exceptions[0].name,"Upload fails".to_string());
+}
+
+#[test]
+#[should_panic]
+fn test_use_case_environment_bad_step_panics() {
+  let mut uce = UseCaseEnvironment::new("Default");
+  uce.add_step("User logs in");
+  uce.add_exception(0,StepException::new("Login fails","Integrity threat","Wrong password"));
+}
+
+impl fmt::Display for UseCaseEnvironment {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let mut steps = "".to_string();
+    for (idx,s) in self.steps.iter().enumerate() {
+      steps.push_str(format!("{}. {}",idx + 1,s.text).as_str());
+      for exc in &s.exceptions {
+        steps.push_str(format!(" [{}]",exc).as_str());
+      }
+      steps.push(' ');
+    }
+    write!(f,"Environment: {}, Preconditions: {}, Steps: {}, Postconditions: {}",self.name,self.preconditions,steps,self.postconditions)
+  }
+}
+
+#[derive(Clone,PartialEq)]
+pub struct UseCase {
+  name : String,
+  short_code : String,
+  pub author : String,
+  pub actors : Vec<String>,
+  pub description : String,
+  pub tags : Vec<Tag>,
+  pub environments : Vec<UseCaseEnvironment>
+}
+
+impl UseCase {
+  pub fn new(uc_name : &str, s_code : &str) -> UseCase {
+    UseCase {
+      name: uc_name.to_string(),
+      short_code: s_code.to_string(),
+      author: "".to_string(),
+      actors: Vec::<String>::new(),
+      description: "".to_string(),
+      tags: Vec::<Tag>::new(),
+      environments: Vec::<UseCaseEnvironment>::new()
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn short_code(&self) -> &str {
+    &self.short_code
+  }
+
+  pub fn set_short_code(&mut self, s_code : &str) {
+    self.short_code = s_code.to_string();
+  }
+
+  pub fn add_actor(&mut self, r : &Role) {
+    if !self.actors.iter().any(|a| a == r.name()) {
+      self.actors.push(r.name().to_string());
+    }
+  }
+}
+
+#[test]
+fn test_new_use_case() {
+  let uc = UseCase::new("Upload data","UC-1");
+  assert_eq!(uc.name,"Upload data".to_string());
+  assert_eq!(uc.short_code,"UC-1".to_string());
+  assert_eq!(uc.author,"".to_string());
+  assert_eq!(uc.actors.len(),0);
+  assert_eq!(uc.description,"".to_string());
+  assert_eq!(uc.tags.len(),0);
+  assert_eq!(uc.environments.len(),0);
+}
+
+#[test]
+fn test_use_case_add_actor() {
+  let mut uc = UseCase::new("Upload data","UC-1");
+  let r = Role::new("Researcher","Stakeholder","RES","A researcher");
+  uc.add_actor(&r);
+  uc.add_actor(&r);
+  assert_eq!(uc.actors,vec!["Researcher".to_string()]);
+}
+
+impl fmt::Display for UseCase {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let envs = &self.environments.iter().map(|env| env.to_string()).collect::<Vec<String>>().join(",");
+    let tags = &self.tags.iter().map(|t| t.name.clone()).collect::<Vec<String>>().join(",");
+    write!(f,"Name: {}, Short code: {}, Author: {}, Actors: {}, Description: {}, Tags: {}, Environments: {}",self.name,self.short_code,self.author,self.actors.join(","),self.description,tags,envs)
+  }
+}
+
+impl Dimension for UseCase {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::UseCase
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn short_code(&self) -> Option<&str> {
+    Some(&self.short_code)
+  }
+
+  fn tags(&self) -> &[Tag] {
+    &self.tags
+  }
+
+  fn environment_names(&self) -> Vec<String> {
+    self.environments.iter().map(|uce| uce.name.clone()).collect()
+  }
+}
+
+impl Renamable for UseCase {
+  fn set_name(&mut self, new_name : &str) {
+    self.name = new_name.to_string();
+  }
+}
+
+impl Referencing for UseCase {
+  fn references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    match kind {
+      DimensionKind::Environment => self.environments.iter().filter(|uce| uce.name == objt_name).count(),
+      DimensionKind::Role => count_names(&self.actors,objt_name),
+      DimensionKind::Tag => self.tags.iter().filter(|t| t.name == objt_name).count(),
+      _ => 0
+    }
+  }
+
+  fn update_references(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    match kind {
+      DimensionKind::Environment => {
+        let count = self.references(kind,old_name);
+        match new_name {
+          Some(n) => self.environments.iter_mut().filter(|uce| uce.name == old_name).for_each(|uce| uce.name = n.to_string()),
+          None => self.environments.retain(|uce| uce.name != old_name)
+        }
+        count
+      },
+      DimensionKind::Role => update_names(&mut self.actors,old_name,new_name),
+      DimensionKind::Tag => update_tags(&mut self.tags,old_name,new_name),
+      _ => 0
+    }
+  }
+}
+
+#[test]
+fn test_use_case_references() {
+  let mut uc = UseCase::new("Upload data","UC-1");
+  uc.add_actor(&Role::new("Researcher","Stakeholder","RES","A researcher"));
+  uc.environments.push(UseCaseEnvironment::new("Day"));
+  assert_eq!(uc.references(DimensionKind::Role,"Researcher"),1);
+  assert_eq!(uc.update_references(DimensionKind::Role,"Researcher",Some("Scientist")),1);
+  assert_eq!(uc.actors,vec!["Scientist".to_string()]);
+  assert_eq!(uc.update_references(DimensionKind::Environment,"Day",None),1);
+  assert!(uc.environment_names().is_empty());
+}
@@ -1,9 +1,10 @@
 use std::fmt;
-use crate::db::mysql::initialise_db;
+use crate::dimensions::dimension::{Dimension,Renamable,DimensionKind,Identified,Referencing};
+use crate::dimensions::objectid::ValueTypeId;
 
 #[derive(Clone,PartialEq)]
 pub struct ValueType {
-  pub id : i128,
+  pub id : Option<ValueTypeId>,
   pub name : String,
   pub description: String,
   pub vt_type : String,
@@ -13,8 +14,8 @@ pub struct ValueType {
 }
 
 impl ValueType {
-  pub fn new(vt_name : &String, vt_desc : &String,v_t : &String) -> ValueType {
-    ValueType{ id: -1, name : vt_name.clone(), description: vt_desc.clone(), vt_type : v_t.clone(), score : 0, rationale : "".to_string(), environment : "".to_string()}
+  pub fn new(vt_name : &str, vt_desc : &str,v_t : &str) -> ValueType {
+    ValueType{ id: None, name : vt_name.to_string(), description: vt_desc.to_string(), vt_type : v_t.to_string(), score : 0, rationale : "".to_string(), environment : "".to_string()}
   }
 }
 
@@ -24,9 +25,51 @@ impl fmt::Display for ValueType {
   }
 }
 
+impl Dimension for ValueType {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::ValueType
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn environment_names(&self) -> Vec<String> {
+    if self.environment.is_empty() { Vec::<String>::new() } else { vec![self.environment.clone()] }
+  }
+}
+
+impl Renamable for ValueType {
+  fn set_name(&mut self, new_name : &str) {
+    self.name = new_name.to_string();
+  }
+}
+
+impl Identified for ValueType {
+  type Id = ValueTypeId;
+
+  fn id(&self) -> Option<ValueTypeId> {
+    self.id
+  }
+}
+
+impl Referencing for ValueType {
+  fn references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    usize::from(kind == DimensionKind::Environment && self.environment == objt_name)
+  }
+
+  fn update_references(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    if self.references(kind,old_name) == 0 {
+      return 0;
+    }
+    self.environment = new_name.unwrap_or("").to_string();
+    1
+  }
+}
+
 #[test]
 fn test_new_value_type() {
-  let vt = ValueType::new(&"AVT".to_string(),&"XXX".to_string(),&"vulnerability_type".to_string());
+  let vt = ValueType::new("AVT","XXX","vulnerability_type");
   assert_eq!(vt.name,"AVT".to_string());
   assert_eq!(vt.description,"XXX".to_string());
   assert_eq!(vt.description,"XXX".to_string());
@@ -34,4 +77,60 @@ fn test_new_value_type() {
   assert_eq!(vt.score,0);
   assert_eq!(vt.rationale,"".to_string());
   assert_eq!(vt.environment,"".to_string());
-}
\ No newline at end of file
+  assert_eq!(vt.id,None);
+}
+
+pub struct ValueTypeBuilder {
+  value_type : ValueType
+}
+
+impl ValueTypeBuilder {
+  pub fn new(vt_name : &str, v_t : &str) -> ValueTypeBuilder {
+    ValueTypeBuilder{value_type : ValueType::new(vt_name,"",v_t)}
+  }
+
+  pub fn description(mut self, vt_desc : &str) -> ValueTypeBuilder {
+    self.value_type.description = vt_desc.to_string();
+    self
+  }
+
+  pub fn score(mut self, vt_score : i128) -> ValueTypeBuilder {
+    self.value_type.score = vt_score;
+    self
+  }
+
+  pub fn rationale(mut self, vt_rat : &str) -> ValueTypeBuilder {
+    self.value_type.rationale = vt_rat.to_string();
+    self
+  }
+
+  pub fn environment(mut self, env_name : &str) -> ValueTypeBuilder {
+    self.value_type.environment = env_name.to_string();
+    self
+  }
+
+  pub fn build(self) -> Result<ValueType,String> {
+    let vt = self.value_type;
+    if vt.name.is_empty() {
+      return Err("Value type has no name".to_string());
+    }
+    if vt.vt_type.is_empty() {
+      return Err(format!("Value type {} has no type",vt.name));
+    }
+    if vt.score < -1 {
+      return Err(format!("Value type {} has an invalid score {}",vt.name,vt.score));
+    }
+    Ok(vt)
+  }
+}
+
+#[test]
+fn test_value_type_builder() {
+  let vt = ValueTypeBuilder::new("High","threat_value").description("Significant harm").score(3).rationale("Agreed").environment("Day").build().unwrap();
+  assert_eq!(vt.vt_type,"threat_value".to_string());
+  assert_eq!(vt.score,3);
+  assert_eq!(vt.environment,"Day".to_string());
+  assert_eq!(ValueTypeBuilder::new("High","").build().err(),Some("Value type High has no type".to_string()));
+  assert_eq!(ValueTypeBuilder::new("Software","threat_type").score(-1).build().unwrap().score,-1);
+  assert_eq!(ValueTypeBuilder::new("High","threat_value").score(-2).build().err(),Some("Value type High has an invalid score -2".to_string()));
+}
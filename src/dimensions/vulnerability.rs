@@ -1,14 +1,27 @@
 use std::fmt;
+use crate::dimensions::dimension::{Dimension,Renamable,DimensionKind,Referencing,count_names,update_names,update_tags,value_type_name};
 use crate::dimensions::tag::Tag;
 
 #[derive(Clone,PartialEq,Debug)]
-enum Severity {
+pub enum Severity {
   Negligible = 0,
   Marginal = 1,
   Critical = 2,
   Catastrophic = 3
 }
 
+impl Severity {
+  pub fn parse(sev : &str) -> Result<Severity,String> {
+    match sev {
+      "Negligible" => Ok(Severity::Negligible),
+      "Marginal" => Ok(Severity::Marginal),
+      "Critical" => Ok(Severity::Critical),
+      "Catastrophic" => Ok(Severity::Catastrophic),
+      _ => Err(format!("{} is an invalid severity value",sev))
+    }
+  }
+}
+
 #[derive(Clone,PartialEq)]
 pub struct VulnerabilityEnvironment {
   name : String,
@@ -17,40 +30,49 @@ pub struct VulnerabilityEnvironment {
 }
 
 impl VulnerabilityEnvironment {
-  pub fn new(env_name: &String, sev: &String) -> VulnerabilityEnvironment {
+  pub fn new(env_name: &str, sev: &str) -> VulnerabilityEnvironment {
     VulnerabilityEnvironment { 
-      name: env_name.clone(),
-      severity  : match sev.as_str() {
-        "Negligible" => Severity::Negligible,
-        "Marginal" => Severity::Marginal,
-        "Critical" => Severity::Critical,
-        "Catastrophic" => Severity::Catastrophic,
-        _ => panic!("{} is an invalid severity value", sev)
-      }, 
+      name: env_name.to_string(),
+      severity : Severity::parse(sev).unwrap_or_else(|e| panic!("{}",e)),
       assets: Vec::<String>::new() 
     }
   }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn severity(&self) -> &Severity {
+    &self.severity
+  }
+
+  pub fn set_severity(&mut self, sev : &str) {
+    self.severity = Severity::parse(sev).unwrap_or_else(|e| panic!("{}",e));
+  }
 }
 
 #[test]
 fn test_new_vulnerability_environment() {
-  let ve = VulnerabilityEnvironment::new(&"Default".to_string(),&"Marginal".to_string());
+  let ve = VulnerabilityEnvironment::new("Default","Marginal");
   assert_eq!(ve.name,"Default".to_string());
   assert_eq!(ve.severity,Severity::Marginal);
   assert_eq!(ve.assets.len(),0);
 }
 
+impl fmt::Display for Severity {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Severity::Negligible => write!(f,"Negligible"),
+      Severity::Marginal => write!(f,"Marginal"),
+      Severity::Critical => write!(f,"Critical"),
+      Severity::Catastrophic => write!(f,"Catastrophic")
+    }
+  }
+}
+
 impl fmt::Display for VulnerabilityEnvironment {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    write!(f,"Environment: {}, Severity: {}, Assets: {}",
-      self.name,
-      match self.severity {
-        Severity::Negligible => "Negligible",
-        Severity::Marginal => "Marginal",
-        Severity::Critical => "Critical",
-        Severity::Catastrophic => "Catastrophic" 
-      },
-      self.assets.join(","))
+    write!(f,"Environment: {}, Severity: {}, Assets: {}",self.name,self.severity,self.assets.join(","))
   }
 }
 
@@ -64,20 +86,99 @@ pub struct Vulnerability {
 }
 
 impl Vulnerability {
-  pub fn new(vul_name : &String, vul_type : &String) -> Vulnerability {
+  pub fn new(vul_name : &str, vul_type : &str) -> Vulnerability {
     Vulnerability { 
-      name: vul_name.clone(), 
-      vulnerability_type: vul_type.clone(),
+      name: vul_name.to_string(), 
+      vulnerability_type: vul_type.to_string(),
       description: "".to_string(), 
       tags: Vec::<Tag>::new(), 
       environments: Vec::<VulnerabilityEnvironment>::new() 
     }
   }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn vulnerability_type(&self) -> &str {
+    &self.vulnerability_type
+  }
+
+  pub fn set_vulnerability_type(&mut self, vul_type : &str) {
+    self.vulnerability_type = vul_type.to_string();
+  }
+}
+
+impl Dimension for Vulnerability {
+  fn kind(&self) -> DimensionKind {
+    DimensionKind::Vulnerability
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn tags(&self) -> &[Tag] {
+    &self.tags
+  }
+
+  fn environment_names(&self) -> Vec<String> {
+    self.environments.iter().map(|ve| ve.name.clone()).collect()
+  }
+}
+
+impl Renamable for Vulnerability {
+  fn set_name(&mut self, new_name : &str) {
+    self.name = new_name.to_string();
+  }
+}
+
+impl Referencing for Vulnerability {
+  fn defined_by_environments(&self) -> bool {
+    true
+  }
+
+  fn references(&self, kind : DimensionKind, objt_name : &str) -> usize {
+    match kind {
+      DimensionKind::Environment => self.environments.iter().filter(|ve| ve.name == objt_name).count(),
+      DimensionKind::Asset => self.environments.iter().map(|ve| count_names(&ve.assets,objt_name)).sum(),
+      DimensionKind::ValueType => usize::from(value_type_name(objt_name,"vulnerability_type") == Some(self.vulnerability_type.as_str())),
+      DimensionKind::Tag => self.tags.iter().filter(|t| t.name == objt_name).count(),
+      _ => 0
+    }
+  }
+
+  fn requires(&self, kind : DimensionKind, objt_name : &str) -> bool {
+    kind == DimensionKind::ValueType && self.references(kind,objt_name) > 0
+  }
+
+  fn update_references(&mut self, kind : DimensionKind, old_name : &str, new_name : Option<&str>) -> usize {
+    match kind {
+      DimensionKind::Environment => {
+        let count = self.references(kind,old_name);
+        match new_name {
+          Some(n) => self.environments.iter_mut().filter(|ve| ve.name == old_name).for_each(|ve| ve.name = n.to_string()),
+          None => self.environments.retain(|ve| ve.name != old_name)
+        }
+        count
+      },
+      DimensionKind::Asset => self.environments.iter_mut().map(|ve| update_names(&mut ve.assets,old_name,new_name)).sum(),
+      DimensionKind::ValueType => {
+        if self.references(kind,old_name) == 0 {
+          return 0;
+        }
+        self.vulnerability_type = new_name.and_then(|n| value_type_name(n,"vulnerability_type")).unwrap_or("").to_string();
+        1
+      },
+      DimensionKind::Tag => update_tags(&mut self.tags,old_name,new_name),
+      _ => 0
+    }
+  }
 }
 
 #[test]
 fn test_new_vulnerability() {
-  let v = Vulnerability::new(&"Some vulnerability".to_string(),&"Some type".to_string());
+  let v = Vulnerability::new("Some vulnerability","Some type");
   assert_eq!(v.name,"Some vulnerability".to_string());
   assert_eq!(v.vulnerability_type,"Some type".to_string());
   assert_eq!(v.description,"".to_string());
@@ -92,3 +193,62 @@ impl fmt::Display for Vulnerability {
     write!(f,"Name: {}, Type: {}, Description: {}, Tags: {}, Environments: {}",self.name,self.vulnerability_type,self.description,tags,envs)
   }
 }
+
+pub struct VulnerabilityBuilder {
+  vulnerability : Vulnerability,
+  environments : Vec<(String,String,Vec<String>)>
+}
+
+impl VulnerabilityBuilder {
+  pub fn new(vul_name : &str) -> VulnerabilityBuilder {
+    VulnerabilityBuilder{vulnerability : Vulnerability::new(vul_name,""), environments : Vec::<(String,String,Vec<String>)>::new()}
+  }
+
+  pub fn vulnerability_type(mut self, vul_type : &str) -> VulnerabilityBuilder {
+    self.vulnerability.vulnerability_type = vul_type.to_string();
+    self
+  }
+
+  pub fn description(mut self, desc : &str) -> VulnerabilityBuilder {
+    self.vulnerability.description = desc.to_string();
+    self
+  }
+
+  pub fn tags(mut self, tag_names : &[&str]) -> VulnerabilityBuilder {
+    self.vulnerability.tags.extend(tag_names.iter().map(|t| Tag::new(t)));
+    self
+  }
+
+  pub fn environment(mut self, env_name : &str, sev : &str, assets : &[&str]) -> VulnerabilityBuilder {
+    self.environments.push((env_name.to_string(),sev.to_string(),assets.iter().map(|a| a.to_string()).collect()));
+    self
+  }
+
+  pub fn build(self) -> Result<Vulnerability,String> {
+    let mut v = self.vulnerability;
+    if v.name.is_empty() {
+      return Err("Vulnerability has no name".to_string());
+    }
+    if v.vulnerability_type.is_empty() {
+      return Err(format!("Vulnerability {} has no type",v.name));
+    }
+    for (env_name,sev,assets) in self.environments {
+      let severity = Severity::parse(&sev).map_err(|e| format!("Vulnerability {}: {}",v.name,e))?;
+      if v.environments.iter().any(|ve| ve.name == env_name) {
+        return Err(format!("Vulnerability {} has environment {} more than once",v.name,env_name));
+      }
+      v.environments.push(VulnerabilityEnvironment{name : env_name, severity, assets});
+    }
+    Ok(v)
+  }
+}
+
+#[test]
+fn test_vulnerability_builder() {
+  let v = VulnerabilityBuilder::new("Unvalidated input").vulnerability_type("Implementation").description("Input is not checked").tags(&["owasp"]).environment("Day","Critical",&["Portal","Data"]).build().unwrap();
+  assert_eq!(v.vulnerability_type(),"Implementation");
+  assert_eq!(*v.environments[0].severity(),Severity::Critical);
+  assert_eq!(v.environments[0].assets.len(),2);
+  assert_eq!(VulnerabilityBuilder::new("Unvalidated input").build().err(),Some("Vulnerability Unvalidated input has no type".to_string()));
+  assert_eq!(VulnerabilityBuilder::new("Unvalidated input").vulnerability_type("Implementation").environment("Day","Severe",&[]).build().err(),Some("Vulnerability Unvalidated input: Severe is an invalid severity value".to_string()));
+}
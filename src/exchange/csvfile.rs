@@ -0,0 +1,584 @@
+use std::fmt;
+use std::io::{Read,Write};
+use csv::{Reader,StringRecord,Writer};
+use crate::dimensions::asset::{Asset,AssetEnvironmentProperties};
+use crate::dimensions::attacker::{Attacker,AttackerEnvironment};
+use crate::dimensions::environment::{CompositeProperty,Environment};
+use crate::dimensions::risk::Risk;
+use crate::dimensions::role::{Role,RoleType};
+use crate::dimensions::tag::Tag;
+use crate::dimensions::threat::{Likelihood,Threat,ThreatEnvironment};
+use crate::dimensions::valuetype::ValueType;
+use crate::dimensions::securityproperty::QualitativeValue;
+use crate::dimensions::vulnerability::{Severity,Vulnerability,VulnerabilityEnvironment};
+
+const PROPERTIES : [&str; 8] = ["confidentiality","integrity","availability","accountability","anonymity","pseudonymity","unlinkability","unobservability"];
+
+#[derive(Clone,PartialEq,Debug)]
+pub struct CsvRowError {
+  pub row : u64,
+  pub message : String
+}
+
+impl fmt::Display for CsvRowError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f,"Row {}: {}",self.row,self.message)
+  }
+}
+
+fn split_list(txt : &str) -> Vec<String> {
+  let mut items = Vec::<String>::new();
+  let mut item = String::new();
+  let mut chars = txt.chars();
+  while let Some(c) = chars.next() {
+    match c {
+      '\\' => item.push(chars.next().unwrap_or('\\')),
+      ';' => items.push(std::mem::take(&mut item)),
+      _ => item.push(c)
+    }
+  }
+  items.push(item);
+  items.iter().map(|s| s.trim()).filter(|s| !s.is_empty()).map(|s| s.to_string()).collect()
+}
+
+fn join_list(items : &[String]) -> String {
+  items.iter().map(|i| i.replace('\\',"\\\\").replace(';',"\\;")).collect::<Vec<String>>().join(";")
+}
+
+fn tags_cell(tags : &[Tag]) -> String {
+  join_list(&tags.iter().map(|t| t.name.clone()).collect::<Vec<String>>())
+}
+
+fn field<'r>(headers : &StringRecord, rec : &'r StringRecord, col : &str) -> &'r str {
+  match headers.iter().position(|h| h == col) {
+    Some(idx) => rec.get(idx).unwrap_or("").trim(),
+    None => ""
+  }
+}
+
+fn required<'r>(headers : &StringRecord, rec : &'r StringRecord, col : &str) -> Result<&'r str,String> {
+  if !headers.iter().any(|h| h == col) {
+    return Err(format!("missing {} column",col));
+  }
+  let v = field(headers,rec,col);
+  if v.is_empty() {
+    return Err(format!("{} is empty",col));
+  }
+  Ok(v)
+}
+
+fn parsed<'r,T>(v : &'r str, parse : fn(&str) -> Result<T,String>, col : &str) -> Result<&'r str,String> {
+  match parse(v) {
+    Ok(_) => Ok(v),
+    Err(_) => Err(format!("{} is not a valid {} value",v,col))
+  }
+}
+
+fn environment_names(headers : &StringRecord, suffixes : &[&str]) -> Vec<String> {
+  let mut envs = Vec::<String>::new();
+  for h in headers.iter() {
+    let h = h.strip_suffix("/rationale").unwrap_or(h);
+    if let Some((env,col)) = h.rsplit_once('/') {
+      if suffixes.contains(&col) && !envs.iter().any(|e| e == env) {
+        envs.push(env.to_string());
+      }
+    }
+  }
+  envs
+}
+
+fn read_rows<R : Read, T, F : Fn(&StringRecord,&StringRecord) -> Result<T,String>>(rdr : R, parse : F) -> (Vec<T>,Vec<CsvRowError>) {
+  let mut reader = Reader::from_reader(rdr);
+  let mut objts = Vec::<T>::new();
+  let mut errors = Vec::<CsvRowError>::new();
+  let headers = match reader.headers() {
+    Ok(h) => h.clone(),
+    Err(err) => {
+      errors.push(CsvRowError { row: 1, message: err.to_string() });
+      return (objts,errors);
+    }
+  };
+  for rec in reader.records() {
+    match rec {
+      Ok(r) => {
+        let row = r.position().map(|p| p.line()).unwrap_or(0);
+        match parse(&headers,&r) {
+          Ok(o) => objts.push(o),
+          Err(message) => errors.push(CsvRowError { row, message })
+        }
+      },
+      Err(err) => {
+        let row = err.position().map(|p| p.line()).unwrap_or(0);
+        errors.push(CsvRowError { row, message: err.to_string() });
+      }
+    }
+  }
+  (objts,errors)
+}
+
+pub fn read_assets<R : Read>(rdr : R) -> (Vec<Asset>,Vec<CsvRowError>) {
+  read_rows(rdr,|headers,rec| {
+    let is_critical = match field(headers,rec,"critical").to_lowercase().as_str() {
+      "true" | "yes" | "1" => true,
+      "false" | "no" | "0" | "" => false,
+      v => { return Err(format!("{} is not a valid critical value",v)); }
+    };
+    let mut a = Asset::new(required(headers,rec,"name")?,required(headers,rec,"short_code")?,required(headers,rec,"type")?,is_critical);
+    a.critical_rationale = field(headers,rec,"critical_rationale").to_string();
+    a.description = field(headers,rec,"description").to_string();
+    a.significance = field(headers,rec,"significance").to_string();
+    a.tags = split_list(field(headers,rec,"tags")).iter().map(|t| Tag::new(t)).collect();
+    for env in environment_names(headers,&PROPERTIES) {
+      let mut aep = AssetEnvironmentProperties::new(&env);
+      let mut in_env = false;
+      for p in PROPERTIES {
+        let col = format!("{}/{}",env,p);
+        let v = field(headers,rec,&col);
+        let rationale = field(headers,rec,&format!("{}/rationale",col));
+        if !v.is_empty() || !rationale.is_empty() {
+          let v = if v.is_empty() { "None" } else { parsed(v,QualitativeValue::parse,&col)? };
+          aep.update(p,v,rationale);
+          in_env = true;
+        }
+      }
+      if in_env {
+        a.environment_properties.insert(env,aep);
+      }
+    }
+    Ok(a)
+  })
+}
+
+pub fn write_assets<W : Write>(wtr : W, assets : &[Asset]) -> Result<(),csv::Error> {
+  let mut envs : Vec<String> = assets.iter().flat_map(|a| a.environment_properties.keys().cloned()).collect();
+  envs.sort();
+  envs.dedup();
+  let mut writer = Writer::from_writer(wtr);
+  let mut headers = vec!["name","short_code","type","critical","critical_rationale","description","significance","tags"].into_iter().map(|h| h.to_string()).collect::<Vec<String>>();
+  for env in &envs {
+    for p in PROPERTIES {
+      headers.push(format!("{}/{}",env,p));
+      headers.push(format!("{}/{}/rationale",env,p));
+    }
+  }
+  writer.write_record(&headers)?;
+  for a in assets {
+    let mut rec = vec![a.name().to_string(),a.short_code().to_string(),a.asset_type().to_string(),a.is_critical().to_string(),a.critical_rationale.clone(),a.description.clone(),a.significance.clone(),tags_cell(&a.tags)];
+    for env in &envs {
+      match a.environment_properties.get(env) {
+        Some(aep) => rec.extend(aep.properties().iter().flat_map(|p| [format!("{:?}",p.value),p.rationale.clone()])),
+        None => rec.extend(PROPERTIES.iter().flat_map(|_| ["".to_string(),"".to_string()]))
+      }
+    }
+    writer.write_record(&rec)?;
+  }
+  writer.flush()?;
+  Ok(())
+}
+
+pub fn read_vulnerabilities<R : Read>(rdr : R) -> (Vec<Vulnerability>,Vec<CsvRowError>) {
+  read_rows(rdr,|headers,rec| {
+    let mut v = Vulnerability::new(required(headers,rec,"name")?,required(headers,rec,"type")?);
+    v.description = field(headers,rec,"description").to_string();
+    v.tags = split_list(field(headers,rec,"tags")).iter().map(|t| Tag::new(t)).collect();
+    for env in environment_names(headers,&["severity","assets"]) {
+      let sev_col = format!("{}/severity",env);
+      let sev = field(headers,rec,&sev_col);
+      let assets = split_list(field(headers,rec,&format!("{}/assets",env)));
+      if sev.is_empty() {
+        if !assets.is_empty() {
+          return Err(format!("{} is empty but {} has exposed assets",sev_col,env));
+        }
+        continue;
+      }
+      let mut ve = VulnerabilityEnvironment::new(&env,parsed(sev,Severity::parse,&sev_col)?);
+      ve.assets = assets;
+      v.environments.push(ve);
+    }
+    Ok(v)
+  })
+}
+
+pub fn write_vulnerabilities<W : Write>(wtr : W, vuls : &[Vulnerability]) -> Result<(),csv::Error> {
+  let mut envs : Vec<String> = vuls.iter().flat_map(|v| v.environments.iter().map(|ve| ve.name().to_string())).collect();
+  envs.sort();
+  envs.dedup();
+  let mut writer = Writer::from_writer(wtr);
+  let mut headers = vec!["name","type","description","tags"].into_iter().map(|h| h.to_string()).collect::<Vec<String>>();
+  for env in &envs {
+    headers.push(format!("{}/severity",env));
+    headers.push(format!("{}/assets",env));
+  }
+  writer.write_record(&headers)?;
+  for v in vuls {
+    let mut rec = vec![v.name().to_string(),v.vulnerability_type().to_string(),v.description.clone(),tags_cell(&v.tags)];
+    for env in &envs {
+      match v.environments.iter().find(|ve| ve.name() == env) {
+        Some(ve) => { rec.push(ve.severity().to_string()); rec.push(join_list(&ve.assets)); },
+        None => { rec.push("".to_string()); rec.push("".to_string()); }
+      }
+    }
+    writer.write_record(&rec)?;
+  }
+  writer.flush()?;
+  Ok(())
+}
+
+pub fn read_roles<R : Read>(rdr : R) -> (Vec<Role>,Vec<CsvRowError>) {
+  read_rows(rdr,|headers,rec| {
+    Ok(Role::new(required(headers,rec,"name")?,parsed(required(headers,rec,"type")?,RoleType::parse,"type")?,required(headers,rec,"short_code")?,field(headers,rec,"description")))
+  })
+}
+
+pub fn write_roles<W : Write>(wtr : W, roles : &[Role]) -> Result<(),csv::Error> {
+  let mut writer = Writer::from_writer(wtr);
+  writer.write_record(["name","type","short_code","description"])?;
+  for r in roles {
+    writer.write_record([r.name(),&r.role_type().to_string(),r.short_code(),&r.description])?;
+  }
+  writer.flush()?;
+  Ok(())
+}
+
+pub fn read_attackers<R : Read>(rdr : R) -> (Vec<Attacker>,Vec<CsvRowError>) {
+  read_rows(rdr,|headers,rec| {
+    let mut a = Attacker::new(required(headers,rec,"name")?,field(headers,rec,"image"));
+    a.description = field(headers,rec,"description").to_string();
+    a.tags = split_list(field(headers,rec,"tags")).iter().map(|t| Tag::new(t)).collect();
+    for env in environment_names(headers,&["roles","motivations","capabilities"]) {
+      let mut ae = AttackerEnvironment::new(&env);
+      ae.roles = split_list(field(headers,rec,&format!("{}/roles",env)));
+      ae.motivations = split_list(field(headers,rec,&format!("{}/motivations",env)));
+      ae.capabilities = split_list(field(headers,rec,&format!("{}/capabilities",env)));
+      if !ae.roles.is_empty() || !ae.motivations.is_empty() || !ae.capabilities.is_empty() {
+        a.environments.push(ae);
+      }
+    }
+    Ok(a)
+  })
+}
+
+pub fn write_attackers<W : Write>(wtr : W, attackers : &[Attacker]) -> Result<(),csv::Error> {
+  let mut envs : Vec<String> = attackers.iter().flat_map(|a| a.environments.iter().map(|ae| ae.name().to_string())).collect();
+  envs.sort();
+  envs.dedup();
+  let mut writer = Writer::from_writer(wtr);
+  let mut headers = vec!["name","image","description","tags"].into_iter().map(|h| h.to_string()).collect::<Vec<String>>();
+  for env in &envs {
+    headers.push(format!("{}/roles",env));
+    headers.push(format!("{}/motivations",env));
+    headers.push(format!("{}/capabilities",env));
+  }
+  writer.write_record(&headers)?;
+  for a in attackers {
+    let mut rec = vec![a.name().to_string(),a.image().to_string(),a.description.clone(),tags_cell(&a.tags)];
+    for env in &envs {
+      match a.environments.iter().find(|ae| ae.name() == env) {
+        Some(ae) => { rec.push(join_list(&ae.roles)); rec.push(join_list(&ae.motivations)); rec.push(join_list(&ae.capabilities)); },
+        None => rec.extend(["".to_string(),"".to_string(),"".to_string()])
+      }
+    }
+    writer.write_record(&rec)?;
+  }
+  writer.flush()?;
+  Ok(())
+}
+
+pub fn read_value_types<R : Read>(rdr : R) -> (Vec<ValueType>,Vec<CsvRowError>) {
+  read_rows(rdr,|headers,rec| {
+    let mut vt = ValueType::new(required(headers,rec,"name")?,field(headers,rec,"description"),required(headers,rec,"type")?);
+    let score = field(headers,rec,"score");
+    if !score.is_empty() {
+      vt.score = score.parse::<i128>().map_err(|_| format!("{} is not a valid score",score))?;
+    }
+    vt.rationale = field(headers,rec,"rationale").to_string();
+    vt.environment = field(headers,rec,"environment").to_string();
+    Ok(vt)
+  })
+}
+
+pub fn write_value_types<W : Write>(wtr : W, vts : &[ValueType]) -> Result<(),csv::Error> {
+  let mut writer = Writer::from_writer(wtr);
+  writer.write_record(["name","type","description","score","rationale","environment"])?;
+  for vt in vts {
+    writer.write_record([&vt.name,&vt.vt_type,&vt.description,&vt.score.to_string(),&vt.rationale,&vt.environment])?;
+  }
+  writer.flush()?;
+  Ok(())
+}
+
+pub fn read_environments<R : Read>(rdr : R) -> (Vec<Environment>,Vec<CsvRowError>) {
+  read_rows(rdr,|headers,rec| {
+    let mut env = Environment::new(required(headers,rec,"name")?,required(headers,rec,"short_code")?);
+    env.definition = field(headers,rec,"definition").to_string();
+    for ce in split_list(field(headers,rec,"environments")) {
+      env.environments.add(&ce);
+    }
+    let prop = field(headers,rec,"duplication_property");
+    if !prop.is_empty() {
+      env.environments.update_property(parsed(prop,CompositeProperty::parse,"duplication_property")?);
+    }
+    env.environments.overriding_environment_name = field(headers,rec,"overriding_environment").to_string();
+    Ok(env)
+  })
+}
+
+pub fn write_environments<W : Write>(wtr : W, envs : &[Environment]) -> Result<(),csv::Error> {
+  let mut writer = Writer::from_writer(wtr);
+  writer.write_record(["name","short_code","definition","environments","duplication_property","overriding_environment"])?;
+  for env in envs {
+    let prop = if env.environments.is_empty() { "".to_string() } else { env.environments.property.to_string() };
+    writer.write_record([&env.name,&env.short_code,&env.definition,&join_list(&env.environments.environments),&prop,&env.environments.overriding_environment_name])?;
+  }
+  writer.flush()?;
+  Ok(())
+}
+
+pub fn read_threats<R : Read>(rdr : R) -> (Vec<Threat>,Vec<CsvRowError>) {
+  read_rows(rdr,|headers,rec| {
+    let mut t = Threat::new(required(headers,rec,"name")?,required(headers,rec,"type")?);
+    t.method = field(headers,rec,"method").to_string();
+    t.tags = split_list(field(headers,rec,"tags")).iter().map(|t| Tag::new(t)).collect();
+    for env in environment_names(headers,&["likelihood","assets","attackers"]) {
+      let lhood_col = format!("{}/likelihood",env);
+      let lhood = field(headers,rec,&lhood_col);
+      let assets = split_list(field(headers,rec,&format!("{}/assets",env)));
+      let attackers = split_list(field(headers,rec,&format!("{}/attackers",env)));
+      if lhood.is_empty() {
+        if !assets.is_empty() || !attackers.is_empty() {
+          return Err(format!("{} is empty but {} has threatened assets or attackers",lhood_col,env));
+        }
+        continue;
+      }
+      let mut te = ThreatEnvironment::new(&env,parsed(lhood,Likelihood::parse,&lhood_col)?);
+      te.assets = assets;
+      te.attackers = attackers;
+      t.environments.push(te);
+    }
+    Ok(t)
+  })
+}
+
+pub fn write_threats<W : Write>(wtr : W, threats : &[Threat]) -> Result<(),csv::Error> {
+  let mut envs : Vec<String> = threats.iter().flat_map(|t| t.environments.iter().map(|te| te.name().to_string())).collect();
+  envs.sort();
+  envs.dedup();
+  let mut writer = Writer::from_writer(wtr);
+  let mut headers = vec!["name","type","method","tags"].into_iter().map(|h| h.to_string()).collect::<Vec<String>>();
+  for env in &envs {
+    headers.push(format!("{}/likelihood",env));
+    headers.push(format!("{}/assets",env));
+    headers.push(format!("{}/attackers",env));
+  }
+  writer.write_record(&headers)?;
+  for t in threats {
+    let mut rec = vec![t.name().to_string(),t.threat_type().to_string(),t.method.clone(),tags_cell(&t.tags)];
+    for env in &envs {
+      match t.environments.iter().find(|te| te.name() == env) {
+        Some(te) => { rec.push(te.likelihood().to_string()); rec.push(join_list(&te.assets)); rec.push(join_list(&te.attackers)); },
+        None => rec.extend(["".to_string(),"".to_string(),"".to_string()])
+      }
+    }
+    writer.write_record(&rec)?;
+  }
+  writer.flush()?;
+  Ok(())
+}
+
+pub fn read_risks<R : Read>(rdr : R) -> (Vec<Risk>,Vec<CsvRowError>) {
+  read_rows(rdr,|headers,rec| {
+    let mut r = Risk::new(required(headers,rec,"name")?,required(headers,rec,"threat")?,required(headers,rec,"vulnerability")?);
+    r.tags = split_list(field(headers,rec,"tags")).iter().map(|t| Tag::new(t)).collect();
+    Ok(r)
+  })
+}
+
+pub fn write_risks<W : Write>(wtr : W, risks : &[Risk]) -> Result<(),csv::Error> {
+  let mut writer = Writer::from_writer(wtr);
+  writer.write_record(["name","threat","vulnerability","tags"])?;
+  for r in risks {
+    writer.write_record([r.name(),&r.threat,&r.vulnerability,&tags_cell(&r.tags)])?;
+  }
+  writer.flush()?;
+  Ok(())
+}
+
+#[test]
+fn test_asset_csv_round_trip() {
+  let mut data = Asset::new("Data","DAT","Information",true);
+  data.critical_rationale = "Irreplaceable".to_string();
+  data.description = "Research data".to_string();
+  data.tags.push(Tag::new("clinical"));
+  data.tags.push(Tag::new("pii"));
+  data.add_environment("Day");
+  data.update_security_property("Day","confidentiality","High","TBC");
+  let server = Asset::new("Server","SRV","Hardware",false);
+
+  let mut buf = Vec::<u8>::new();
+  write_assets(&mut buf,&[data,server]).unwrap();
+  let txt = String::from_utf8(buf).unwrap();
+  assert!(txt.starts_with("name,short_code,type,critical,critical_rationale,description,significance,tags,Day/confidentiality,Day/confidentiality/rationale,Day/integrity,"));
+  assert!(txt.contains("Data,DAT,Information,true,Irreplaceable,Research data,,clinical;pii,High,TBC,None,None,None,None,"));
+  assert!(txt.contains(&format!("Server,SRV,Hardware,false{}\n",",".repeat(20))));
+
+  let (assets,errors) = read_assets(txt.as_bytes());
+  assert_eq!(errors.len(),0);
+  assert_eq!(assets.len(),2);
+  assert_eq!(assets[0].name(),"Data");
+  assert!(assets[0].is_critical());
+  assert_eq!(assets[0].tags.len(),2);
+  assert_eq!(assets[0].environment_properties["Day"].properties()[0].value,crate::dimensions::securityproperty::QualitativeValue::High);
+  assert_eq!(assets[0].environment_properties["Day"].properties()[0].rationale,"TBC".to_string());
+  assert_eq!(assets[0].critical_rationale,"Irreplaceable".to_string());
+  assert_eq!(assets[1].environment_properties.len(),0);
+
+  let (assets,errors) = read_assets("name,short_code,type,Day/integrity,Night/availability/rationale\nData,DAT,Information,Medium,Needed overnight\n".as_bytes());
+  assert_eq!(errors.len(),0);
+  assert_eq!(assets[0].environment_properties["Day"].properties()[1].value,crate::dimensions::securityproperty::QualitativeValue::Medium);
+  assert_eq!(assets[0].environment_properties["Night"].properties()[2].rationale,"Needed overnight".to_string());
+}
+
+#[test]
+fn test_read_assets_reports_row_errors() {
+  let txt = "name,short_code,type,critical,Day/confidentiality\nData,DAT,Information,no,Extreme\n,SRV,Hardware,no,Low\nKeys,KEY,Information,maybe,\nLogs,LOG,Information,yes,Low\n";
+  let (assets,errors) = read_assets(txt.as_bytes());
+  assert_eq!(assets.len(),1);
+  assert_eq!(assets[0].name(),"Logs");
+  assert_eq!(errors,vec![
+    CsvRowError { row: 2, message: "Extreme is not a valid Day/confidentiality value".to_string() },
+    CsvRowError { row: 3, message: "name is empty".to_string() },
+    CsvRowError { row: 4, message: "maybe is not a valid critical value".to_string() }
+  ]);
+}
+
+#[test]
+fn test_vulnerability_csv_round_trip() {
+  let mut v = Vulnerability::new("Unvalidated input","Implementation");
+  let mut ve = VulnerabilityEnvironment::new("Day","Critical");
+  ve.assets = vec!["Data".to_string(),"Server".to_string()];
+  v.environments.push(ve);
+  let mut buf = Vec::<u8>::new();
+  write_vulnerabilities(&mut buf,&[v]).unwrap();
+  let txt = String::from_utf8(buf).unwrap();
+  assert_eq!(txt,"name,type,description,tags,Day/severity,Day/assets\nUnvalidated input,Implementation,,,Critical,Data;Server\n".to_string());
+
+  let (vuls,errors) = read_vulnerabilities(txt.as_bytes());
+  assert_eq!(errors.len(),0);
+  assert_eq!(vuls[0].environments[0].assets,vec!["Data".to_string(),"Server".to_string()]);
+
+  let (vuls,errors) = read_vulnerabilities("name,type,Day/severity\nWeak passwords,Configuration,Severe\n".as_bytes());
+  assert_eq!(vuls.len(),0);
+  assert_eq!(errors[0].row,2);
+
+  let mut v = Vulnerability::new("Shared logs","Configuration");
+  v.tags.push(Tag::new("a;b"));
+  let mut ve = VulnerabilityEnvironment::new("Day","Marginal");
+  ve.assets = vec!["Logs;Audit".to_string(),"C:\\Data".to_string()];
+  v.environments.push(ve);
+  let mut buf = Vec::<u8>::new();
+  write_vulnerabilities(&mut buf,&[v]).unwrap();
+  let txt = String::from_utf8(buf).unwrap();
+  assert!(txt.ends_with("Shared logs,Configuration,,a\\;b,Marginal,Logs\\;Audit;C:\\\\Data\n"));
+  let (vuls,errors) = read_vulnerabilities(txt.as_bytes());
+  assert_eq!(errors.len(),0);
+  assert_eq!(vuls[0].tags[0].name,"a;b".to_string());
+  assert_eq!(vuls[0].environments[0].assets,vec!["Logs;Audit".to_string(),"C:\\Data".to_string()]);
+}
+
+#[test]
+fn test_role_csv_round_trip() {
+  let mut buf = Vec::<u8>::new();
+  write_roles(&mut buf,&[Role::new("Researcher","Data Subject","RES","A researcher")]).unwrap();
+  let txt = String::from_utf8(buf).unwrap();
+  assert_eq!(txt,"name,type,short_code,description\nResearcher,Data Subject,RES,A researcher\n".to_string());
+  let (roles,errors) = read_roles(txt.as_bytes());
+  assert_eq!(errors.len(),0);
+  assert_eq!(roles[0].name(),"Researcher");
+
+  let (roles,errors) = read_roles("name,type,short_code,description\nAdmin,Superuser,ADM,\n".as_bytes());
+  assert_eq!(roles.len(),0);
+  assert_eq!(errors,vec![CsvRowError { row: 2, message: "Superuser is not a valid type value".to_string() }]);
+}
+
+#[test]
+fn test_attacker_csv_round_trip() {
+  let mut a = Attacker::new("Peppa","peppa.png");
+  let mut ae = AttackerEnvironment::new("Day");
+  ae.roles.push("Hacker".to_string());
+  ae.motivations = vec!["Money".to_string(),"Fame".to_string()];
+  a.environments.push(ae);
+  let mut buf = Vec::<u8>::new();
+  write_attackers(&mut buf,&[a]).unwrap();
+  let txt = String::from_utf8(buf).unwrap();
+  assert_eq!(txt,"name,image,description,tags,Day/roles,Day/motivations,Day/capabilities\nPeppa,peppa.png,,,Hacker,Money;Fame,\n".to_string());
+  let (attackers,errors) = read_attackers(txt.as_bytes());
+  assert_eq!(errors.len(),0);
+  assert_eq!(attackers[0].environments[0].motivations,vec!["Money".to_string(),"Fame".to_string()]);
+
+  let (attackers,errors) = read_attackers("name,Night/capabilities\nPeppa,Resources\n".as_bytes());
+  assert_eq!(errors.len(),0);
+  assert_eq!(attackers[0].environments[0].name(),"Night");
+  assert_eq!(attackers[0].environments[0].capabilities,vec!["Resources".to_string()]);
+}
+
+#[test]
+fn test_value_type_csv_round_trip() {
+  let mut vt = ValueType::new("Low","Some impact","threat_value");
+  vt.score = 1;
+  let mut buf = Vec::<u8>::new();
+  write_value_types(&mut buf,&[vt]).unwrap();
+  let txt = String::from_utf8(buf).unwrap();
+  let (vts,errors) = read_value_types(txt.as_bytes());
+  assert_eq!(errors.len(),0);
+  assert_eq!(vts[0].score,1);
+  assert_eq!(vts[0].vt_type,"threat_value".to_string());
+
+  let (vts,errors) = read_value_types("name,type,score\nHigh,threat_value,lots\n".as_bytes());
+  assert_eq!(vts.len(),0);
+  assert_eq!(errors,vec![CsvRowError { row: 2, message: "lots is not a valid score".to_string() }]);
+}
+
+#[test]
+fn test_environment_csv_round_trip() {
+  let mut all = Environment::new("All day","ALL");
+  all.environments.add("Day");
+  all.environments.add("Night");
+  all.environments.update_property("Override");
+  all.environments.overriding_environment_name = "Night".to_string();
+  let mut buf = Vec::<u8>::new();
+  write_environments(&mut buf,&[Environment::new("Day","DAY"),all]).unwrap();
+  let txt = String::from_utf8(buf).unwrap();
+  assert_eq!(txt,"name,short_code,definition,environments,duplication_property,overriding_environment\nDay,DAY,,,,\nAll day,ALL,,Day;Night,Override,Night\n".to_string());
+  let (envs,errors) = read_environments(txt.as_bytes());
+  assert_eq!(errors.len(),0);
+  assert_eq!(envs[1].environments.environments,vec!["Day".to_string(),"Night".to_string()]);
+  assert_eq!(envs[1].environments.property,crate::dimensions::environment::CompositeProperty::Override);
+}
+
+#[test]
+fn test_threat_and_risk_csv_round_trip() {
+  let mut t = Threat::new("SQL injection","Software");
+  let mut te = ThreatEnvironment::new("Day","Probable");
+  te.assets.push("Data".to_string());
+  te.attackers.push("Peppa".to_string());
+  t.environments.push(te);
+  let mut buf = Vec::<u8>::new();
+  write_threats(&mut buf,&[t]).unwrap();
+  let txt = String::from_utf8(buf).unwrap();
+  assert_eq!(txt,"name,type,method,tags,Day/likelihood,Day/assets,Day/attackers\nSQL injection,Software,,,Probable,Data,Peppa\n".to_string());
+  let (threats,errors) = read_threats(txt.as_bytes());
+  assert_eq!(errors.len(),0);
+  assert_eq!(*threats[0].environments[0].likelihood(),crate::dimensions::threat::Likelihood::Probable);
+  let (threats,errors) = read_threats("name,type,Day/assets\nSQL injection,Software,Data\n".as_bytes());
+  assert_eq!(threats.len(),0);
+  assert_eq!(errors,vec![CsvRowError { row: 2, message: "Day/likelihood is empty but Day has threatened assets or attackers".to_string() }]);
+
+  let mut buf = Vec::<u8>::new();
+  write_risks(&mut buf,&[Risk::new("Upload risk","SQL injection","Unvalidated input")]).unwrap();
+  let (risks,errors) = read_risks(buf.as_slice());
+  assert_eq!(errors.len(),0);
+  assert_eq!(risks[0].vulnerability,"Unvalidated input".to_string());
+  let (risks,errors) = read_risks("name,threat\nUpload risk,SQL injection\n".as_bytes());
+  assert_eq!(risks.len(),0);
+  assert_eq!(errors,vec![CsvRowError { row: 2, message: "missing vulnerability column".to_string() }]);
+}
@@ -0,0 +1,2 @@
+pub mod csvfile;
+pub mod modeldir;
@@ -0,0 +1,150 @@
+use std::fs::{self,File};
+use std::io;
+use std::path::{Path,PathBuf};
+use crate::dimensions::asset::Asset;
+use crate::dimensions::attacker::Attacker;
+use crate::dimensions::environment::Environment;
+use crate::dimensions::projectsettings::ProjectSettings;
+use crate::dimensions::risk::Risk;
+use crate::dimensions::role::Role;
+use crate::dimensions::threat::Threat;
+use crate::dimensions::valuetype::ValueType;
+use crate::dimensions::vulnerability::Vulnerability;
+use crate::exchange::csvfile::*;
+use crate::model::Model;
+
+pub const DIMENSION_FILES : [(&str,&str); 8] = [
+  ("environments","environments.csv"),
+  ("roles","roles.csv"),
+  ("assets","assets.csv"),
+  ("attackers","attackers.csv"),
+  ("vulnerabilities","vulnerabilities.csv"),
+  ("threats","threats.csv"),
+  ("risks","risks.csv"),
+  ("value_types","value_types.csv")
+];
+
+pub struct ModelDirectory {
+  pub path : PathBuf,
+  pub settings : ProjectSettings,
+  pub environments : Vec<Environment>,
+  pub roles : Vec<Role>,
+  pub assets : Vec<Asset>,
+  pub attackers : Vec<Attacker>,
+  pub vulnerabilities : Vec<Vulnerability>,
+  pub threats : Vec<Threat>,
+  pub risks : Vec<Risk>,
+  pub value_types : Vec<ValueType>,
+  pub errors : Vec<(String,CsvRowError)>
+}
+
+fn load_file<T>(dir : &Path, file_name : &str, errors : &mut Vec<(String,CsvRowError)>, read : fn(File) -> (Vec<T>,Vec<CsvRowError>)) -> io::Result<Vec<T>> {
+  let p = dir.join(file_name);
+  if !p.exists() {
+    return Ok(Vec::<T>::new());
+  }
+  let (objts,errs) = read(File::open(p)?);
+  errors.extend(errs.into_iter().map(|e| (file_name.to_string(),e)));
+  Ok(objts)
+}
+
+fn to_io(err : csv::Error) -> io::Error {
+  io::Error::other(err)
+}
+
+impl ModelDirectory {
+  pub fn new(dir : &Path) -> ModelDirectory {
+    ModelDirectory {
+      path: dir.to_path_buf(),
+      settings: ProjectSettings::new(&dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()),
+      environments: Vec::new(),
+      roles: Vec::new(),
+      assets: Vec::new(),
+      attackers: Vec::new(),
+      vulnerabilities: Vec::new(),
+      threats: Vec::new(),
+      risks: Vec::new(),
+      value_types: Vec::new(),
+      errors: Vec::new()
+    }
+  }
+
+  pub fn load(dir : &Path) -> io::Result<ModelDirectory> {
+    if !dir.is_dir() {
+      return Err(io::Error::new(io::ErrorKind::NotFound,format!("{} is not a model directory",dir.display())));
+    }
+    let mut md = ModelDirectory::new(dir);
+    md.environments = load_file(dir,"environments.csv",&mut md.errors,read_environments)?;
+    md.roles = load_file(dir,"roles.csv",&mut md.errors,read_roles)?;
+    md.assets = load_file(dir,"assets.csv",&mut md.errors,read_assets)?;
+    md.attackers = load_file(dir,"attackers.csv",&mut md.errors,read_attackers)?;
+    md.vulnerabilities = load_file(dir,"vulnerabilities.csv",&mut md.errors,read_vulnerabilities)?;
+    md.threats = load_file(dir,"threats.csv",&mut md.errors,read_threats)?;
+    md.risks = load_file(dir,"risks.csv",&mut md.errors,read_risks)?;
+    md.value_types = load_file(dir,"value_types.csv",&mut md.errors,read_value_types)?;
+    Ok(md)
+  }
+
+  pub fn save(&self) -> io::Result<()> {
+    fs::create_dir_all(&self.path)?;
+    write_environments(File::create(self.path.join("environments.csv"))?,&self.environments).map_err(to_io)?;
+    write_roles(File::create(self.path.join("roles.csv"))?,&self.roles).map_err(to_io)?;
+    write_assets(File::create(self.path.join("assets.csv"))?,&self.assets).map_err(to_io)?;
+    write_attackers(File::create(self.path.join("attackers.csv"))?,&self.attackers).map_err(to_io)?;
+    write_vulnerabilities(File::create(self.path.join("vulnerabilities.csv"))?,&self.vulnerabilities).map_err(to_io)?;
+    write_threats(File::create(self.path.join("threats.csv"))?,&self.threats).map_err(to_io)?;
+    write_risks(File::create(self.path.join("risks.csv"))?,&self.risks).map_err(to_io)?;
+    write_value_types(File::create(self.path.join("value_types.csv"))?,&self.value_types).map_err(to_io)?;
+    Ok(())
+  }
+
+  pub fn validate(&self) -> Vec<String> {
+    let (m,mut problems) = Model::load_directory(self);
+    problems.extend(m.validate());
+    problems
+  }
+}
+
+#[test]
+fn test_model_directory_round_trip() {
+  let dir = std::env::temp_dir().join(format!("cairis_modeldir_{}",std::process::id()));
+  let mut md = ModelDirectory::new(&dir);
+  md.environments.push(Environment::new("Day","DAY"));
+  md.roles.push(Role::new("Researcher","Stakeholder","RES","A researcher"));
+  let mut data = Asset::new("Data","DAT","Information",false);
+  data.add_environment("Day");
+  md.assets.push(data);
+  md.save().unwrap();
+
+  let loaded = ModelDirectory::load(&dir).unwrap();
+  assert_eq!(loaded.environments.len(),1);
+  assert_eq!(loaded.roles.len(),1);
+  assert_eq!(loaded.assets.len(),1);
+  assert_eq!(loaded.errors.len(),0);
+  assert_eq!(loaded.validate().len(),0);
+  fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_model_directory_validate() {
+  use crate::dimensions::vulnerability::VulnerabilityEnvironment;
+
+  let mut md = ModelDirectory::new(Path::new("unused"));
+  md.environments.push(Environment::new("Day","DAY"));
+  md.roles.push(Role::new("Researcher","Stakeholder","RES","A researcher"));
+  md.roles.push(Role::new("Researcher","Stakeholder","RES2","Another researcher"));
+  let mut v = Vulnerability::new("Unvalidated input","Implementation");
+  let mut ve = VulnerabilityEnvironment::new("Night","Critical");
+  ve.assets.push("Data".to_string());
+  ve.assets.push("Server".to_string());
+  v.environments.push(ve);
+  md.vulnerabilities.push(v);
+  md.risks.push(Risk::new("Upload risk","SQL injection","Unvalidated input"));
+  assert_eq!(md.validate(),vec![
+    "Duplicate role Researcher".to_string(),
+    "Vulnerability Unvalidated input refers to unknown environment Night".to_string(),
+    "Vulnerability Unvalidated input refers to unknown asset Data".to_string(),
+    "Vulnerability Unvalidated input refers to unknown asset Server".to_string(),
+    "Risk Upload risk refers to unknown threat SQL injection".to_string()
+  ]);
+}
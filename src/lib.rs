@@ -1,2 +1,8 @@
 pub mod dimensions;
 pub mod db;
+pub mod analysis;
+pub mod report;
+pub mod visualise;
+pub mod exchange;
+pub mod server;
+pub mod model;
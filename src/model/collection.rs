@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use crate::dimensions::dimension::{Dimension,DimensionKind,Renamable};
+
+fn name_key<T : Dimension>(objt : &T) -> String {
+  objt.name().to_string()
+}
+
+#[derive(Clone)]
+pub struct Collection<T : Dimension> {
+  kind : DimensionKind,
+  key : fn(&T) -> String,
+  objts : Vec<T>,
+  index : HashMap<String,usize>
+}
+
+impl<T : Dimension> Collection<T> {
+  pub fn new(kind : DimensionKind) -> Collection<T> {
+    Collection::with_key(kind,name_key)
+  }
+
+  pub fn with_key(kind : DimensionKind, key : fn(&T) -> String) -> Collection<T> {
+    Collection{kind, key, objts : Vec::<T>::new(), index : HashMap::<String,usize>::new()}
+  }
+
+  pub fn kind(&self) -> DimensionKind {
+    self.kind
+  }
+
+  pub fn add(&mut self, objt : T) -> Result<(),String> {
+    let k = (self.key)(&objt);
+    if self.index.contains_key(&k) {
+      return Err(format!("Duplicate {} {}",self.kind,k));
+    }
+    self.index.insert(k,self.objts.len());
+    self.objts.push(objt);
+    Ok(())
+  }
+
+  pub fn get(&self, k : &str) -> Option<&T> {
+    self.index.get(k).map(|idx| &self.objts[*idx])
+  }
+
+  pub fn contains(&self, k : &str) -> bool {
+    self.index.contains_key(k)
+  }
+
+  pub fn all(&self) -> &[T] {
+    &self.objts
+  }
+
+  pub fn keys(&self) -> Vec<String> {
+    self.objts.iter().map(|o| (self.key)(o)).collect()
+  }
+
+  pub fn len(&self) -> usize {
+    self.objts.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.objts.is_empty()
+  }
+
+  pub fn remove(&mut self, k : &str) -> Option<T> {
+    let idx = self.index.remove(k)?;
+    let objt = self.objts.remove(idx);
+    self.index.values_mut().filter(|i| **i > idx).for_each(|i| *i -= 1);
+    Some(objt)
+  }
+
+  pub(crate) fn rename(&mut self, k : &str, new_name : &str) -> Result<String,String> where T : Renamable {
+    let idx = *self.index.get(k).ok_or(format!("Unknown {} {}",self.kind,k))?;
+    let old_name = self.objts[idx].name().to_string();
+    self.objts[idx].set_name(new_name);
+    let new_key = (self.key)(&self.objts[idx]);
+    if new_key != k && self.index.contains_key(&new_key) {
+      self.objts[idx].set_name(&old_name);
+      return Err(format!("Duplicate {} {}",self.kind,new_key));
+    }
+    self.reindex()?;
+    Ok(new_key)
+  }
+
+  pub fn update_each(&mut self, mut f : impl FnMut(&str,&mut T)) -> Result<(),String> {
+    for objt in self.objts.iter_mut() {
+      let k = (self.key)(objt);
+      f(&k,objt);
+    }
+    self.reindex()
+  }
+
+  fn reindex(&mut self) -> Result<(),String> {
+    let mut index = HashMap::<String,usize>::new();
+    for (idx,o) in self.objts.iter().enumerate() {
+      let k = (self.key)(o);
+      if index.contains_key(&k) {
+        return Err(format!("Duplicate {} {}",self.kind,k));
+      }
+      index.insert(k,idx);
+    }
+    self.index = index;
+    Ok(())
+  }
+
+  pub fn resolve(&self, owner : &str, keys : &[String]) -> Result<Vec<&T>,Vec<String>> {
+    let missing : Vec<String> = keys.iter().filter(|k| !self.contains(k)).map(|k| format!("{} refers to unknown {} {}",owner,self.kind,k)).collect();
+    if missing.is_empty() {
+      Ok(keys.iter().filter_map(|k| self.get(k)).collect())
+    }
+    else {
+      Err(missing)
+    }
+  }
+}
+
+#[cfg(test)]
+use crate::dimensions::role::Role;
+
+#[test]
+fn test_collection() {
+  let mut roles = Collection::<Role>::new(DimensionKind::Role);
+  assert!(roles.is_empty());
+  roles.add(Role::new("Researcher","Stakeholder","RES","")).unwrap();
+  roles.add(Role::new("Admin","Stakeholder","ADM","")).unwrap();
+  assert_eq!(roles.add(Role::new("Researcher","Attacker","R2","")).err(),Some("Duplicate role Researcher".to_string()));
+  assert_eq!(roles.len(),2);
+  assert_eq!(roles.get("Admin").unwrap().short_code(),"ADM");
+  assert_eq!(roles.keys(),vec!["Researcher".to_string(),"Admin".to_string()]);
+  assert_eq!(roles.resolve("Attacker Carol",&["Admin".to_string()]).unwrap().len(),1);
+  assert_eq!(roles.resolve("Attacker Carol",&["Nurse".to_string(),"Admin".to_string(),"Doctor".to_string()]).err(),Some(vec!["Attacker Carol refers to unknown role Nurse".to_string(),"Attacker Carol refers to unknown role Doctor".to_string()]));
+}
+
+#[test]
+fn test_collection_rename_remove() {
+  let mut roles = Collection::<Role>::new(DimensionKind::Role);
+  roles.add(Role::new("Researcher","Stakeholder","RES","")).unwrap();
+  roles.add(Role::new("Admin","Stakeholder","ADM","")).unwrap();
+  assert_eq!(roles.rename("Researcher","Admin").err(),Some("Duplicate role Admin".to_string()));
+  assert!(roles.contains("Researcher"));
+  assert_eq!(roles.rename("Researcher","Scientist"),Ok("Scientist".to_string()));
+  assert_eq!(roles.get("Scientist").unwrap().short_code(),"RES");
+  assert_eq!(roles.rename("Nurse","Doctor").err(),Some("Unknown role Nurse".to_string()));
+  assert_eq!(roles.remove("Scientist").unwrap().short_code(),"RES");
+  assert!(roles.remove("Scientist").is_none());
+  assert_eq!(roles.get("Admin").unwrap().short_code(),"ADM");
+  roles.add(Role::new("Nurse","Stakeholder","NUR","")).unwrap();
+  assert_eq!(roles.get("Nurse").unwrap().short_code(),"NUR");
+  assert_eq!(roles.update_each(|_,r| r.set_name("Admin")).err(),Some("Duplicate role Admin".to_string()));
+}
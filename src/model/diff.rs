@@ -0,0 +1,1398 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use crate::dimensions::asset::Asset;
+use crate::dimensions::assetassociation::{Adornment,AssetAssociation};
+use crate::dimensions::attacker::{Attacker,AttackerEnvironment};
+use crate::dimensions::countermeasure::{Countermeasure,CountermeasureEnvironment,CountermeasureTarget};
+use crate::dimensions::dataflow::{DataFlow,DataFlowComponentType};
+use crate::dimensions::dimension::{Dimension,DimensionKind};
+use crate::dimensions::domainproperty::{DomainProperty,DomainPropertyType};
+use crate::dimensions::environment::Environment;
+use crate::dimensions::misusecase::{MisuseCase,MisuseCaseEnvironment};
+use crate::dimensions::projectsettings::ProjectSettings;
+use crate::dimensions::response::{MitigationType,Response,ResponseEnvironment,ResponseType};
+use crate::dimensions::risk::Risk;
+use crate::dimensions::role::Role;
+use crate::dimensions::securityproperty::{QualitativeValue,SecurityPropertyValue};
+use crate::dimensions::tag::Tag;
+use crate::dimensions::threat::{Threat,ThreatEnvironment};
+use crate::dimensions::trustboundary::{TrustBoundary,TrustBoundaryEnvironment};
+use crate::dimensions::usecase::{Step,StepException,UseCase,UseCaseEnvironment};
+use crate::dimensions::valuetype::ValueType;
+use crate::dimensions::vulnerability::{Vulnerability,VulnerabilityEnvironment};
+use crate::model::Model;
+use crate::model::collection::Collection;
+
+pub type FieldMap = BTreeMap<String,String>;
+
+pub trait Fields {
+  fn fields(&self) -> FieldMap;
+  fn set_field(&mut self, field : &str, value : Option<&str>);
+}
+
+fn field_map(pairs : &[(&str,String)]) -> FieldMap {
+  pairs.iter().map(|(k,v)| (k.to_string(),v.clone())).collect()
+}
+
+fn escape(txt : &str) -> String {
+  let mut escaped = String::new();
+  for c in txt.chars() {
+    if matches!(c,'\\' | '/' | ';') {
+      escaped.push('\\');
+    }
+    escaped.push(c);
+  }
+  escaped
+}
+
+fn unescape(txt : &str) -> String {
+  let mut unescaped = String::new();
+  let mut chars = txt.chars();
+  while let Some(c) = chars.next() {
+    unescaped.push(if c == '\\' { chars.next().unwrap_or(c) } else { c });
+  }
+  unescaped
+}
+
+fn split_escaped(txt : &str, sep : char) -> Option<(String,&str)> {
+  let mut escaped = false;
+  for (idx,c) in txt.char_indices() {
+    match c {
+      _ if escaped => escaped = false,
+      '\\' => escaped = true,
+      _ if c == sep => return Some((unescape(&txt[..idx]),&txt[idx + 1..])),
+      _ => ()
+    }
+  }
+  None
+}
+
+fn split_field(field : &str) -> Option<(String,&str)> {
+  split_escaped(field,'/')
+}
+
+fn join_names(names : &[String]) -> String {
+  names.iter().map(|n| escape(n)).collect::<Vec<String>>().join(";")
+}
+
+fn tag_names(tags : &[Tag]) -> String {
+  join_names(&tags.iter().map(|t| t.name.clone()).collect::<Vec<String>>())
+}
+
+fn property_fields(fm : &mut FieldMap, env_name : &str, props : &[SecurityPropertyValue]) {
+  for p in props {
+    let prop_name = format!("{:?}",p.name).to_lowercase();
+    fm.insert(format!("{}/{}",escape(env_name),prop_name),format!("{:?}",p.value));
+    fm.insert(format!("{}/{}/rationale",escape(env_name),prop_name),p.rationale.clone());
+  }
+}
+
+fn text(value : Option<&str>) -> String {
+  value.unwrap_or("").to_string()
+}
+
+fn name_list(value : Option<&str>) -> Vec<String> {
+  let mut names = Vec::<String>::new();
+  let mut rest = value.unwrap_or("");
+  if rest.is_empty() {
+    return names;
+  }
+  while let Some((n,tail)) = split_escaped(rest,';') {
+    names.push(n);
+    rest = tail;
+  }
+  names.push(unescape(rest));
+  names
+}
+
+fn tag_list(value : Option<&str>) -> Vec<Tag> {
+  name_list(value).iter().map(|n| Tag::new(n)).collect()
+}
+
+fn property_name(field : &str) -> (&str,bool) {
+  match field.strip_suffix("/rationale") {
+    Some(p_name) => (p_name,true),
+    None => (field,false)
+  }
+}
+
+fn set_property(props : &mut Vec<SecurityPropertyValue>, field : &str, value : Option<&str>) {
+  let (p_name,is_rationale) = property_name(field);
+  let idx = props.iter().position(|p| format!("{:?}",p.name).to_lowercase() == p_name);
+  match (idx,value) {
+    (Some(idx),Some(v)) if is_rationale => props[idx].rationale = v.to_string(),
+    (Some(idx),Some(v)) => props[idx].value = QualitativeValue::parse(v).unwrap_or_else(|e| panic!("{}",e)),
+    (Some(idx),None) if !is_rationale => { props.remove(idx); },
+    (None,Some(v)) if is_rationale => props.push(SecurityPropertyValue::new(p_name,"None",v)),
+    (None,Some(v)) => props.push(SecurityPropertyValue::new(p_name,v,"")),
+    _ => ()
+  }
+}
+
+fn find_environment<'a, E>(envs : &'a mut Vec<E>, env_name : &str, name : fn(&E) -> &str, new_env : impl FnOnce(&str) -> E, create : bool) -> Option<&'a mut E> {
+  match envs.iter().position(|e| name(e) == env_name) {
+    Some(idx) => Some(&mut envs[idx]),
+    None if create => {
+      envs.push(new_env(env_name));
+      envs.last_mut()
+    },
+    None => None
+  }
+}
+
+fn environment<'a, E>(envs : &'a mut Vec<E>, env_name : &str, name : fn(&E) -> &str, new_env : impl FnOnce(&str) -> E) -> &'a mut E {
+  find_environment(envs,env_name,name,new_env,true).unwrap()
+}
+
+fn remove_environment<E>(envs : &mut Vec<E>, env_name : &str, name : fn(&E) -> &str) {
+  envs.retain(|e| name(e) != env_name);
+}
+
+fn set_step(steps : &mut Vec<Step>, field : &str, value : Option<&str>) {
+  let (step_no,exc_name) = match field.split_once("/exception/") {
+    Some((step_no,exc_name)) => (step_no,Some(unescape(exc_name))),
+    None => (field,None)
+  };
+  let step_no = step_no.parse::<usize>().unwrap_or(0);
+  if step_no == 0 {
+    return;
+  }
+  if value.is_some() {
+    while steps.len() < step_no {
+      steps.push(Step::new(""));
+    }
+  }
+  match (exc_name,value) {
+    (None,Some(v)) => steps[step_no - 1].text = v.to_string(),
+    (None,None) => steps.truncate(step_no - 1),
+    (Some(exc_name),v) => {
+      if let Some(s) = steps.get_mut(step_no - 1) {
+        s.exceptions.retain(|exc| exc.name != exc_name);
+        if let Some(v) = v {
+          let (exc_cat,exc_def) = split_escaped(v,';').unwrap_or((unescape(v),""));
+          s.exceptions.push(StepException::new(&exc_name,&exc_cat,exc_def));
+        }
+      }
+    }
+  }
+}
+
+fn response_name(rt : &ResponseType) -> &str {
+  match rt {
+    ResponseType::Accept { .. } => "Accept",
+    ResponseType::Transfer { .. } => "Transfer",
+    ResponseType::Mitigate { .. } => "Mitigate"
+  }
+}
+
+fn set_response_type(re : &mut ResponseEnvironment, r_name : &str) {
+  if response_name(&re.response_type) == r_name {
+    return;
+  }
+  let description = match &re.response_type {
+    ResponseType::Accept { description, .. } | ResponseType::Transfer { description, .. } => description.clone(),
+    ResponseType::Mitigate { .. } => "".to_string()
+  };
+  re.response_type = match r_name {
+    "Accept" => ResponseType::Accept { cost : QualitativeValue::None, description },
+    "Transfer" => ResponseType::Transfer { description, roles : Vec::<(String,QualitativeValue)>::new() },
+    _ => ResponseType::Mitigate { mitigation_type : MitigationType::Prevent, detection_point : "".to_string() }
+  };
+}
+
+fn set_response(re : &mut ResponseEnvironment, field : &str, value : &str) {
+  match field {
+    "response" => set_response_type(re,value),
+    "cost" => set_response_type(re,"Accept"),
+    "roles" => set_response_type(re,"Transfer"),
+    "mitigation_type" | "detection_point" => set_response_type(re,"Mitigate"),
+    "description" if response_name(&re.response_type) == "Mitigate" => set_response_type(re,"Accept"),
+    _ => ()
+  }
+  match (field,&mut re.response_type) {
+    ("cost",ResponseType::Accept { cost, .. }) => *cost = QualitativeValue::parse(value).unwrap_or_else(|e| panic!("{}",e)),
+    ("description",ResponseType::Accept { description, .. } | ResponseType::Transfer { description, .. }) => *description = value.to_string(),
+    ("roles",ResponseType::Transfer { roles, .. }) => {
+      *roles = name_list(Some(value)).iter().filter_map(|rc| rc.rsplit_once(':')).map(|(r,c)| (r.to_string(),QualitativeValue::parse(c).unwrap_or_else(|e| panic!("{}",e)))).collect();
+    },
+    ("mitigation_type",ResponseType::Mitigate { mitigation_type, .. }) => *mitigation_type = MitigationType::parse(value).unwrap_or_else(|e| panic!("{}",e)),
+    ("detection_point",ResponseType::Mitigate { detection_point, .. }) => *detection_point = value.to_string(),
+    _ => ()
+  }
+}
+
+impl Fields for Asset {
+  fn fields(&self) -> FieldMap {
+    let mut fm = field_map(&[
+      ("short_code",self.short_code().to_string()),
+      ("type",self.asset_type().to_string()),
+      ("critical",self.is_critical().to_string()),
+      ("critical_rationale",self.critical_rationale.clone()),
+      ("description",self.description.clone()),
+      ("significance",self.significance.clone()),
+      ("tags",tag_names(&self.tags))
+    ]);
+    for (env_name,aep) in &self.environment_properties {
+      property_fields(&mut fm,env_name,aep.properties());
+    }
+    fm
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match split_field(field) {
+      None => match field {
+        "short_code" => self.set_short_code(&text(value)),
+        "type" => self.set_asset_type(&text(value)),
+        "critical" => self.set_critical(value == Some("true")),
+        "critical_rationale" => self.critical_rationale = text(value),
+        "description" => self.description = text(value),
+        "significance" => self.significance = text(value),
+        "tags" => self.tags = tag_list(value),
+        _ => ()
+      },
+      Some((env_name,prop_field)) => match value {
+        None => { self.environment_properties.remove(&env_name); },
+        Some(v) => {
+          if !self.environment_properties.contains_key(&env_name) {
+            self.add_environment(&env_name);
+          }
+          let (p_name,is_rationale) = property_name(prop_field);
+          let aep = self.environment_properties.get_mut(&env_name).unwrap();
+          if let Some(p) = aep.properties().iter().find(|p| format!("{:?}",p.name).to_lowercase() == p_name).cloned() {
+            match is_rationale {
+              true => aep.update(p_name,&format!("{:?}",p.value),v),
+              false => aep.update(p_name,v,&p.rationale)
+            }
+          }
+        }
+      }
+    }
+  }
+}
+
+impl Fields for Attacker {
+  fn fields(&self) -> FieldMap {
+    let mut fm = field_map(&[("image",self.image().to_string()),("description",self.description.clone()),("tags",tag_names(&self.tags))]);
+    for ae in &self.environments {
+      fm.insert(format!("{}/roles",escape(ae.name())),join_names(&ae.roles));
+      fm.insert(format!("{}/motivations",escape(ae.name())),join_names(&ae.motivations));
+      fm.insert(format!("{}/capabilities",escape(ae.name())),join_names(&ae.capabilities));
+    }
+    fm
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match split_field(field) {
+      None => match field {
+        "image" => self.set_image(&text(value)),
+        "description" => self.description = text(value),
+        "tags" => self.tags = tag_list(value),
+        _ => ()
+      },
+      Some((env_name,_)) if value.is_none() => remove_environment(&mut self.environments,&env_name,AttackerEnvironment::name),
+      Some((env_name,env_field)) => {
+        let ae = environment(&mut self.environments,&env_name,AttackerEnvironment::name,AttackerEnvironment::new);
+        match env_field {
+          "roles" => ae.roles = name_list(value),
+          "motivations" => ae.motivations = name_list(value),
+          "capabilities" => ae.capabilities = name_list(value),
+          _ => ()
+        }
+      }
+    }
+  }
+}
+
+impl Fields for Vulnerability {
+  fn fields(&self) -> FieldMap {
+    let mut fm = field_map(&[("type",self.vulnerability_type().to_string()),("description",self.description.clone()),("tags",tag_names(&self.tags))]);
+    for ve in &self.environments {
+      fm.insert(format!("{}/severity",escape(ve.name())),ve.severity().to_string());
+      fm.insert(format!("{}/assets",escape(ve.name())),join_names(&ve.assets));
+    }
+    fm
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match split_field(field) {
+      None => match field {
+        "type" => self.set_vulnerability_type(&text(value)),
+        "description" => self.description = text(value),
+        "tags" => self.tags = tag_list(value),
+        _ => ()
+      },
+      Some((env_name,_)) if value.is_none() => remove_environment(&mut self.environments,&env_name,VulnerabilityEnvironment::name),
+      Some((env_name,env_field)) => {
+        let ve = environment(&mut self.environments,&env_name,VulnerabilityEnvironment::name,|n| VulnerabilityEnvironment::new(n,"Negligible"));
+        match env_field {
+          "severity" => ve.set_severity(&text(value)),
+          "assets" => ve.assets = name_list(value),
+          _ => ()
+        }
+      }
+    }
+  }
+}
+
+impl Fields for Threat {
+  fn fields(&self) -> FieldMap {
+    let mut fm = field_map(&[("type",self.threat_type().to_string()),("method",self.method.clone()),("tags",tag_names(&self.tags))]);
+    for te in &self.environments {
+      fm.insert(format!("{}/likelihood",escape(te.name())),te.likelihood().to_string());
+      fm.insert(format!("{}/assets",escape(te.name())),join_names(&te.assets));
+      fm.insert(format!("{}/attackers",escape(te.name())),join_names(&te.attackers));
+      property_fields(&mut fm,te.name(),&te.properties);
+    }
+    fm
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match split_field(field) {
+      None => match field {
+        "type" => self.set_threat_type(&text(value)),
+        "method" => self.method = text(value),
+        "tags" => self.tags = tag_list(value),
+        _ => ()
+      },
+      Some((env_name,"likelihood" | "assets" | "attackers")) if value.is_none() => remove_environment(&mut self.environments,&env_name,ThreatEnvironment::name),
+      Some((env_name,env_field)) => {
+        if let Some(te) = find_environment(&mut self.environments,&env_name,ThreatEnvironment::name,|n| ThreatEnvironment::new(n,"Incredible"),value.is_some()) {
+          match env_field {
+            "likelihood" => te.set_likelihood(&text(value)),
+            "assets" => te.assets = name_list(value),
+            "attackers" => te.attackers = name_list(value),
+            _ => set_property(&mut te.properties,env_field,value)
+          }
+        }
+      }
+    }
+  }
+}
+
+impl Fields for Risk {
+  fn fields(&self) -> FieldMap {
+    field_map(&[("threat",self.threat.clone()),("vulnerability",self.vulnerability.clone()),("tags",tag_names(&self.tags))])
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match field {
+      "threat" => self.threat = text(value),
+      "vulnerability" => self.vulnerability = text(value),
+      "tags" => self.tags = tag_list(value),
+      _ => ()
+    }
+  }
+}
+
+impl Fields for Role {
+  fn fields(&self) -> FieldMap {
+    field_map(&[("type",self.role_type().to_string()),("short_code",self.short_code().to_string()),("description",self.description.clone())])
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match field {
+      "type" => self.set_role_type(&text(value)),
+      "short_code" => self.set_short_code(&text(value)),
+      "description" => self.description = text(value),
+      _ => ()
+    }
+  }
+}
+
+impl Fields for Environment {
+  fn fields(&self) -> FieldMap {
+    let mut fm = field_map(&[("short_code",self.short_code.clone()),("definition",self.definition.clone())]);
+    if !self.environments.is_empty() {
+      fm.insert("duplication_property".to_string(),self.environments.property.to_string());
+      fm.insert("overriding_environment".to_string(),self.environments.overriding_environment_name.clone());
+      for e in &self.environments.environments {
+        fm.insert(format!("composite/{}",escape(e)),"member".to_string());
+      }
+    }
+    fm
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match (field.split_once('/'),value) {
+      (Some(("composite",env_name)),Some(_)) => {
+        let env_name = unescape(env_name);
+        if !self.environments.environments.contains(&env_name) {
+          self.environments.add(&env_name);
+        }
+      },
+      (Some(("composite",env_name)),None) => self.environments.environments.retain(|e| *e != unescape(env_name)),
+      _ => match field {
+        "short_code" => self.short_code = text(value),
+        "definition" => self.definition = text(value),
+        "duplication_property" => self.environments.update_property(value.unwrap_or("Maximise")),
+        "overriding_environment" => self.environments.overriding_environment_name = text(value),
+        _ => ()
+      }
+    }
+  }
+}
+
+impl Fields for ValueType {
+  fn fields(&self) -> FieldMap {
+    field_map(&[
+      ("description",self.description.clone()),
+      ("score",self.score.to_string()),
+      ("rationale",self.rationale.clone()),
+      ("environment",self.environment.clone())
+    ])
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match field {
+      "description" => self.description = text(value),
+      "score" => self.score = text(value).parse().unwrap_or_default(),
+      "rationale" => self.rationale = text(value),
+      "environment" => self.environment = text(value),
+      _ => ()
+    }
+  }
+}
+
+impl Fields for UseCase {
+  fn fields(&self) -> FieldMap {
+    let mut fm = field_map(&[
+      ("short_code",self.short_code().to_string()),
+      ("author",self.author.clone()),
+      ("actors",join_names(&self.actors)),
+      ("description",self.description.clone()),
+      ("tags",tag_names(&self.tags))
+    ]);
+    for uce in &self.environments {
+      fm.insert(format!("{}/preconditions",escape(uce.name())),uce.preconditions.clone());
+      fm.insert(format!("{}/postconditions",escape(uce.name())),uce.postconditions.clone());
+      for (idx,s) in uce.steps.iter().enumerate() {
+        fm.insert(format!("{}/step/{}",escape(uce.name()),idx + 1),s.text.clone());
+        for exc in &s.exceptions {
+          fm.insert(format!("{}/step/{}/exception/{}",escape(uce.name()),idx + 1,escape(&exc.name)),format!("{};{}",escape(&exc.category),exc.definition));
+        }
+      }
+    }
+    fm
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match split_field(field) {
+      None => match field {
+        "short_code" => self.set_short_code(&text(value)),
+        "author" => self.author = text(value),
+        "actors" => self.actors = name_list(value),
+        "description" => self.description = text(value),
+        "tags" => self.tags = tag_list(value),
+        _ => ()
+      },
+      Some((env_name,"preconditions" | "postconditions")) if value.is_none() => remove_environment(&mut self.environments,&env_name,UseCaseEnvironment::name),
+      Some((env_name,env_field)) => {
+        if let Some(uce) = find_environment(&mut self.environments,&env_name,UseCaseEnvironment::name,UseCaseEnvironment::new,value.is_some()) {
+          match env_field.split_once('/') {
+            None if env_field == "preconditions" => uce.preconditions = text(value),
+            None if env_field == "postconditions" => uce.postconditions = text(value),
+            Some(("step",step_field)) => set_step(&mut uce.steps,step_field,value),
+            _ => ()
+          }
+        }
+      }
+    }
+  }
+}
+
+impl Fields for MisuseCase {
+  fn fields(&self) -> FieldMap {
+    let mut fm = field_map(&[("risk",self.risk().to_string())]);
+    for mce in &self.environments {
+      fm.insert(format!("{}/narrative",escape(mce.name())),mce.narrative.clone());
+      fm.insert(format!("{}/attackers",escape(mce.name())),join_names(&mce.attackers));
+      fm.insert(format!("{}/assets",escape(mce.name())),join_names(&mce.assets));
+    }
+    fm
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match split_field(field) {
+      None => {
+        if field == "risk" {
+          self.set_risk(&text(value));
+        }
+      },
+      Some((env_name,_)) if value.is_none() => remove_environment(&mut self.environments,&env_name,MisuseCaseEnvironment::name),
+      Some((env_name,env_field)) => {
+        let mce = environment(&mut self.environments,&env_name,MisuseCaseEnvironment::name,MisuseCaseEnvironment::new);
+        match env_field {
+          "narrative" => mce.narrative = text(value),
+          "attackers" => mce.attackers = name_list(value),
+          "assets" => mce.assets = name_list(value),
+          _ => ()
+        }
+      }
+    }
+  }
+}
+
+impl Fields for Countermeasure {
+  fn fields(&self) -> FieldMap {
+    let mut fm = field_map(&[("type",self.countermeasure_type().to_string()),("description",self.description.clone()),("tags",tag_names(&self.tags))]);
+    for ce in &self.environments {
+      fm.insert(format!("{}/cost",escape(ce.name())),format!("{:?}",ce.cost));
+      fm.insert(format!("{}/requirements",escape(ce.name())),join_names(&ce.requirements));
+      fm.insert(format!("{}/roles",escape(ce.name())),join_names(&ce.roles));
+      fm.insert(format!("{}/personas",escape(ce.name())),join_names(&ce.personas));
+      for t in &ce.targets {
+        fm.insert(format!("{}/target/{}",escape(ce.name()),escape(&t.name)),format!("{:?};{}",t.effectiveness,t.rationale));
+      }
+      property_fields(&mut fm,ce.name(),&ce.properties);
+    }
+    fm
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match split_field(field) {
+      None => match field {
+        "type" => self.set_countermeasure_type(&text(value)),
+        "description" => self.description = text(value),
+        "tags" => self.tags = tag_list(value),
+        _ => ()
+      },
+      Some((env_name,"cost" | "requirements" | "roles" | "personas")) if value.is_none() => remove_environment(&mut self.environments,&env_name,CountermeasureEnvironment::name),
+      Some((env_name,env_field)) => {
+        if let Some(ce) = find_environment(&mut self.environments,&env_name,CountermeasureEnvironment::name,CountermeasureEnvironment::new,value.is_some()) {
+          match (env_field.split_once('/'),value) {
+            (Some(("target",t_name)),None) => ce.targets.retain(|t| t.name != unescape(t_name)),
+            (Some(("target",t_name)),Some(v)) => {
+              let t_name = unescape(t_name);
+              let (t_eff,t_rat) = v.split_once(';').unwrap_or((v,""));
+              ce.targets.retain(|t| t.name != t_name);
+              ce.targets.push(CountermeasureTarget::new(&t_name,t_eff,t_rat));
+            },
+            _ => match env_field {
+              "cost" => ce.cost = QualitativeValue::parse(&text(value)).unwrap_or_else(|e| panic!("{}",e)),
+              "requirements" => ce.requirements = name_list(value),
+              "roles" => ce.roles = name_list(value),
+              "personas" => ce.personas = name_list(value),
+              _ => set_property(&mut ce.properties,env_field,value)
+            }
+          }
+        }
+      }
+    }
+  }
+}
+
+impl Fields for Response {
+  fn fields(&self) -> FieldMap {
+    let mut fm = field_map(&[("risk",self.risk().to_string()),("tags",tag_names(&self.tags))]);
+    for re in &self.environments {
+      match &re.response_type {
+        ResponseType::Accept { cost, description } => {
+          fm.insert(format!("{}/response",escape(re.name())),"Accept".to_string());
+          fm.insert(format!("{}/cost",escape(re.name())),format!("{:?}",cost));
+          fm.insert(format!("{}/description",escape(re.name())),description.clone());
+        },
+        ResponseType::Transfer { description, roles } => {
+          fm.insert(format!("{}/response",escape(re.name())),"Transfer".to_string());
+          fm.insert(format!("{}/description",escape(re.name())),description.clone());
+          fm.insert(format!("{}/roles",escape(re.name())),join_names(&roles.iter().map(|(r,c)| format!("{}:{:?}",r,c)).collect::<Vec<String>>()));
+        },
+        ResponseType::Mitigate { mitigation_type, detection_point } => {
+          fm.insert(format!("{}/response",escape(re.name())),"Mitigate".to_string());
+          fm.insert(format!("{}/mitigation_type",escape(re.name())),mitigation_type.to_string());
+          fm.insert(format!("{}/detection_point",escape(re.name())),detection_point.clone());
+        }
+      }
+    }
+    fm
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match split_field(field) {
+      None => match field {
+        "risk" => self.set_risk(&text(value)),
+        "tags" => self.tags = tag_list(value),
+        _ => ()
+      },
+      Some((env_name,"response")) if value.is_none() => remove_environment(&mut self.environments,&env_name,ResponseEnvironment::name),
+      Some((env_name,env_field)) => {
+        if let Some(re) = find_environment(&mut self.environments,&env_name,ResponseEnvironment::name,|n| ResponseEnvironment::accept(n,"None",""),value.is_some()) {
+          if let Some(v) = value {
+            set_response(re,env_field,v);
+          }
+        }
+      }
+    }
+  }
+}
+
+impl Fields for DomainProperty {
+  fn fields(&self) -> FieldMap {
+    field_map(&[
+      ("short_code",self.short_code.clone()),
+      ("definition",self.definition.clone()),
+      ("type",self.dp_type.to_string()),
+      ("originator",self.originator.clone()),
+      ("tags",tag_names(&self.tags))
+    ])
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match field {
+      "short_code" => self.short_code = text(value),
+      "definition" => self.definition = text(value),
+      "type" => self.dp_type = DomainPropertyType::parse(&text(value)).unwrap_or_else(|e| panic!("{}",e)),
+      "originator" => self.originator = text(value),
+      "tags" => self.tags = tag_list(value),
+      _ => ()
+    }
+  }
+}
+
+impl Fields for AssetAssociation {
+  fn fields(&self) -> FieldMap {
+    field_map(&[
+      ("head_navigation",self.head_navigation.to_string()),
+      ("head_adornment",self.head_adornment.to_string()),
+      ("head_multiplicity",self.head_multiplicity.clone()),
+      ("head_role",self.head_role.clone()),
+      ("tail_role",self.tail_role.clone()),
+      ("tail_multiplicity",self.tail_multiplicity.clone()),
+      ("tail_adornment",self.tail_adornment.to_string()),
+      ("tail_navigation",self.tail_navigation.to_string()),
+      ("rationale",self.rationale.clone())
+    ])
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match field {
+      "head_navigation" => self.head_navigation = value == Some("true"),
+      "head_adornment" => self.head_adornment = Adornment::new(&text(value)),
+      "head_multiplicity" => self.head_multiplicity = text(value),
+      "head_role" => self.head_role = text(value),
+      "tail_role" => self.tail_role = text(value),
+      "tail_multiplicity" => self.tail_multiplicity = text(value),
+      "tail_adornment" => self.tail_adornment = Adornment::new(&text(value)),
+      "tail_navigation" => self.tail_navigation = value == Some("true"),
+      "rationale" => self.rationale = text(value),
+      _ => ()
+    }
+  }
+}
+
+impl Fields for DataFlow {
+  fn fields(&self) -> FieldMap {
+    field_map(&[
+      ("environment",self.environment().to_string()),
+      ("source",self.source_name.clone()),
+      ("source_type",self.source_type.to_string()),
+      ("target",self.target_name.clone()),
+      ("target_type",self.target_type.to_string()),
+      ("assets",join_names(&self.assets)),
+      ("obstacles",join_names(&self.obstacles)),
+      ("tags",tag_names(&self.tags))
+    ])
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match field {
+      "environment" => self.set_environment(&text(value)),
+      "source" => self.source_name = text(value),
+      "source_type" => self.source_type = DataFlowComponentType::new(&text(value)),
+      "target" => self.target_name = text(value),
+      "target_type" => self.target_type = DataFlowComponentType::new(&text(value)),
+      "assets" => self.assets = name_list(value),
+      "obstacles" => self.obstacles = name_list(value),
+      "tags" => self.tags = tag_list(value),
+      _ => ()
+    }
+  }
+}
+
+impl Fields for TrustBoundary {
+  fn fields(&self) -> FieldMap {
+    let mut fm = field_map(&[("type",self.boundary_type().to_string()),("description",self.description.clone()),("tags",tag_names(&self.tags))]);
+    for tbe in &self.environments {
+      fm.insert(format!("{}/privilege",escape(tbe.name())),tbe.privilege.clone());
+      fm.insert(format!("{}/components",escape(tbe.name())),join_names(&tbe.components.iter().map(|(t,n)| format!("{}:{}",t,n)).collect::<Vec<String>>()));
+    }
+    fm
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match split_field(field) {
+      None => match field {
+        "type" => self.set_boundary_type(&text(value)),
+        "description" => self.description = text(value),
+        "tags" => self.tags = tag_list(value),
+        _ => ()
+      },
+      Some((env_name,_)) if value.is_none() => remove_environment(&mut self.environments,&env_name,TrustBoundaryEnvironment::name),
+      Some((env_name,env_field)) => {
+        let tbe = environment(&mut self.environments,&env_name,TrustBoundaryEnvironment::name,|n| TrustBoundaryEnvironment::new(n,""));
+        match env_field {
+          "privilege" => tbe.privilege = text(value),
+          "components" => {
+            tbe.components.clear();
+            name_list(value).iter().filter_map(|c| c.split_once(':')).for_each(|(c_type,c_name)| tbe.add_component(c_type,c_name));
+          },
+          _ => ()
+        }
+      }
+    }
+  }
+}
+
+impl Fields for ProjectSettings {
+  fn fields(&self) -> FieldMap {
+    let mut fm = field_map(&[
+      ("name",self.name.clone()),
+      ("background",self.background.clone()),
+      ("strategic_goals",self.strategic_goals.clone()),
+      ("scope",self.scope.clone()),
+      ("rich_picture",self.rich_picture.clone())
+    ]);
+    for (name,value) in &self.naming_conventions {
+      fm.insert(format!("naming_convention/{}",escape(name)),value.clone());
+    }
+    for c in &self.contributors {
+      fm.insert(format!("contributor/{}",escape(&format!("{} {}",c.0,c.1))),format!("{};{}",escape(&c.2),c.3));
+    }
+    for rev in &self.revisions {
+      fm.insert(format!("revision/{}",escape(&rev.0)),format!("{};{}",escape(&rev.1),rev.2));
+    }
+    fm
+  }
+
+  fn set_field(&mut self, field : &str, value : Option<&str>) {
+    match (field.split_once('/'),value) {
+      (Some(("naming_convention",name)),Some(v)) => { self.naming_conventions.insert(unescape(name),v.to_string()); },
+      (Some(("naming_convention",name)),None) => { self.naming_conventions.remove(&unescape(name)); },
+      (Some(("contributor",c_name)),_) => {
+        let c_name = unescape(c_name);
+        let idx = self.contributors.iter().position(|c| format!("{} {}",c.0,c.1) == c_name);
+        match (idx,value) {
+          (Some(idx),None) => { self.contributors.remove(idx); },
+          (Some(idx),Some(v)) => {
+            let (affiliation,role) = split_escaped(v,';').unwrap_or((unescape(v),""));
+            self.contributors[idx].2 = affiliation;
+            self.contributors[idx].3 = role.to_string();
+          },
+          (None,Some(v)) => {
+            let (first_name,surname) = c_name.split_once(' ').unwrap_or((&c_name,""));
+            let (affiliation,role) = split_escaped(v,';').unwrap_or((unescape(v),""));
+            self.contributors.push((first_name.to_string(),surname.to_string(),affiliation,role.to_string()));
+          },
+          (None,None) => ()
+        }
+      },
+      (Some(("revision",rev_no)),_) => {
+        let rev_no = unescape(rev_no);
+        let idx = self.revisions.iter().position(|r| r.0 == rev_no);
+        match (idx,value) {
+          (Some(idx),None) => { self.revisions.remove(idx); },
+          (Some(idx),Some(v)) => {
+            let (rev_date,remarks) = split_escaped(v,';').unwrap_or((unescape(v),""));
+            self.revisions[idx] = (rev_no,rev_date,remarks.to_string());
+          },
+          (None,Some(v)) => {
+            let (rev_date,remarks) = split_escaped(v,';').unwrap_or((unescape(v),""));
+            self.revisions.push((rev_no,rev_date,remarks.to_string()));
+          },
+          (None,None) => ()
+        }
+      },
+      _ => match field {
+        "name" => self.name = text(value),
+        "background" => self.background = text(value),
+        "strategic_goals" => self.strategic_goals = text(value),
+        "scope" => self.scope = text(value),
+        "rich_picture" => self.rich_picture = text(value),
+        _ => ()
+      }
+    }
+  }
+}
+
+#[derive(Clone,PartialEq,Debug)]
+pub struct FieldChange {
+  pub field : String,
+  pub old_value : Option<String>,
+  pub new_value : Option<String>
+}
+
+impl fmt::Display for FieldChange {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match (&self.old_value,&self.new_value) {
+      (None,Some(v)) => write!(f,"{}: added {}",self.field,v),
+      (Some(v),None) => write!(f,"{}: removed {}",self.field,v),
+      (Some(o),Some(n)) => write!(f,"{}: {} -> {}",self.field,o,n),
+      (None,None) => write!(f,"{}: unchanged",self.field)
+    }
+  }
+}
+
+pub fn diff_fields(old : &FieldMap, new : &FieldMap) -> Vec<FieldChange> {
+  let mut changes = Vec::<FieldChange>::new();
+  for (k,v) in old {
+    match new.get(k) {
+      Some(nv) if nv == v => {},
+      nv => changes.push(FieldChange{field : k.clone(), old_value : Some(v.clone()), new_value : nv.cloned()})
+    }
+  }
+  for (k,v) in new {
+    if !old.contains_key(k) {
+      changes.push(FieldChange{field : k.clone(), old_value : None, new_value : Some(v.clone())});
+    }
+  }
+  changes.sort_by(|a,b| a.field.cmp(&b.field));
+  changes
+}
+
+#[test]
+fn test_diff_fields() {
+  let old = field_map(&[("scope",String::from("Lab")),("tags",String::from("a"))]);
+  let new = field_map(&[("scope",String::from("Hospital")),("background",String::from("Trial"))]);
+  assert_eq!(diff_fields(&old,&new).iter().map(|c| c.to_string()).collect::<Vec<String>>(),vec![
+    "background: added Trial".to_string(),
+    "scope: Lab -> Hospital".to_string(),
+    "tags: removed a".to_string()
+  ]);
+  assert!(diff_fields(&old,&old).is_empty());
+}
+
+#[derive(Clone,PartialEq,Debug)]
+pub enum Change {
+  Added,
+  Removed,
+  Modified(Vec<FieldChange>)
+}
+
+#[derive(Clone,PartialEq,Debug)]
+pub struct ObjectChange {
+  pub kind : DimensionKind,
+  pub name : String,
+  pub change : Change
+}
+
+impl fmt::Display for ObjectChange {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match &self.change {
+      Change::Added => write!(f,"+ {} {}",self.kind,self.name),
+      Change::Removed => write!(f,"- {} {}",self.kind,self.name),
+      Change::Modified(fields) => {
+        write!(f,"~ {} {}",self.kind,self.name)?;
+        for fc in fields {
+          write!(f,"\n    {}",fc)?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+#[derive(Clone,PartialEq,Debug,Default)]
+pub struct ChangeSet {
+  pub settings : Vec<FieldChange>,
+  pub objects : Vec<ObjectChange>
+}
+
+impl ChangeSet {
+  pub fn is_empty(&self) -> bool {
+    self.settings.is_empty() && self.objects.is_empty()
+  }
+
+  pub fn changes_to(&self, kind : DimensionKind) -> Vec<&ObjectChange> {
+    self.objects.iter().filter(|c| c.kind == kind).collect()
+  }
+}
+
+impl fmt::Display for ChangeSet {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let mut lines = Vec::<String>::new();
+    if !self.settings.is_empty() {
+      lines.push("~ settings".to_string());
+      lines.extend(self.settings.iter().map(|fc| format!("    {}",fc)));
+    }
+    lines.extend(self.objects.iter().map(|c| c.to_string()));
+    write!(f,"{}",lines.join("\n"))
+  }
+}
+
+fn diff_collection<T : Dimension + Fields>(old : &Collection<T>, new : &Collection<T>, changes : &mut Vec<ObjectChange>) {
+  let kind = old.kind();
+  for k in old.keys() {
+    match new.get(&k) {
+      None => changes.push(ObjectChange{kind, name : k, change : Change::Removed}),
+      Some(n) => {
+        let fields = diff_fields(&old.get(&k).unwrap().fields(),&n.fields());
+        if !fields.is_empty() {
+          changes.push(ObjectChange{kind, name : k, change : Change::Modified(fields)});
+        }
+      }
+    }
+  }
+  for k in new.keys() {
+    if !old.contains(&k) {
+      changes.push(ObjectChange{kind, name : k, change : Change::Added});
+    }
+  }
+}
+
+pub fn diff(old : &Model, new : &Model) -> ChangeSet {
+  let mut objects = Vec::<ObjectChange>::new();
+  diff_collection(&old.environments,&new.environments,&mut objects);
+  diff_collection(&old.roles,&new.roles,&mut objects);
+  diff_collection(&old.assets,&new.assets,&mut objects);
+  diff_collection(&old.attackers,&new.attackers,&mut objects);
+  diff_collection(&old.vulnerabilities,&new.vulnerabilities,&mut objects);
+  diff_collection(&old.threats,&new.threats,&mut objects);
+  diff_collection(&old.risks,&new.risks,&mut objects);
+  diff_collection(&old.value_types,&new.value_types,&mut objects);
+  diff_collection(&old.use_cases,&new.use_cases,&mut objects);
+  diff_collection(&old.misuse_cases,&new.misuse_cases,&mut objects);
+  diff_collection(&old.countermeasures,&new.countermeasures,&mut objects);
+  diff_collection(&old.responses,&new.responses,&mut objects);
+  diff_collection(&old.domain_properties,&new.domain_properties,&mut objects);
+  diff_collection(&old.asset_associations,&new.asset_associations,&mut objects);
+  diff_collection(&old.data_flows,&new.data_flows,&mut objects);
+  diff_collection(&old.trust_boundaries,&new.trust_boundaries,&mut objects);
+  ChangeSet{settings : diff_fields(&old.settings.fields(),&new.settings.fields()), objects}
+}
+
+#[derive(Clone,Copy,PartialEq,Debug)]
+pub enum ConflictKind {
+  BothAdded,
+  BothModified,
+  ModifiedAndRemoved,
+  UnknownReference
+}
+
+impl fmt::Display for ConflictKind {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ConflictKind::BothAdded => write!(f,"added on both sides"),
+      ConflictKind::BothModified => write!(f,"modified on both sides"),
+      ConflictKind::ModifiedAndRemoved => write!(f,"modified on one side and removed on the other"),
+      ConflictKind::UnknownReference => write!(f,"after merging")
+    }
+  }
+}
+
+#[derive(Clone,PartialEq,Debug)]
+pub struct FieldConflict {
+  pub field : String,
+  pub base : Option<String>,
+  pub ours : Option<String>,
+  pub theirs : Option<String>
+}
+
+impl fmt::Display for FieldConflict {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let v = |x : &Option<String>| x.clone().unwrap_or("(none)".to_string());
+    write!(f,"{}: base {}, ours {}, theirs {}",self.field,v(&self.base),v(&self.ours),v(&self.theirs))
+  }
+}
+
+#[derive(Clone,PartialEq,Debug)]
+pub struct Conflict {
+  pub kind : Option<DimensionKind>,
+  pub name : String,
+  pub conflict : ConflictKind,
+  pub fields : Vec<FieldConflict>
+}
+
+impl fmt::Display for Conflict {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.kind {
+      Some(k) => write!(f,"{} {} {}",k,self.name,self.conflict)?,
+      None => write!(f,"{} {}",self.name,self.conflict)?
+    }
+    for fc in &self.fields {
+      write!(f,"\n    {}",fc)?;
+    }
+    Ok(())
+  }
+}
+
+fn field_conflicts(base : &FieldMap, ours : &FieldMap, theirs : &FieldMap) -> Vec<FieldConflict> {
+  let mut keys = ours.keys().chain(theirs.keys()).cloned().collect::<Vec<String>>();
+  keys.sort();
+  keys.dedup();
+  keys.into_iter().filter_map(|k| {
+    let (b,o,t) = (base.get(&k),ours.get(&k),theirs.get(&k));
+    if o != t && o != b && t != b {
+      Some(FieldConflict{field : k, base : b.cloned(), ours : o.cloned(), theirs : t.cloned()})
+    }
+    else {
+      None
+    }
+  }).collect()
+}
+
+fn removal_conflicts(base : &FieldMap, ours : &FieldMap, theirs : &FieldMap) -> Vec<FieldConflict> {
+  let kept = if ours.is_empty() { theirs } else { ours };
+  let mut keys = base.keys().chain(kept.keys()).cloned().collect::<Vec<String>>();
+  keys.sort();
+  keys.dedup();
+  keys.into_iter().filter(|k| base.get(k) != kept.get(k)).map(|k| {
+    FieldConflict{base : base.get(&k).cloned(), ours : ours.get(&k).cloned(), theirs : theirs.get(&k).cloned(), field : k}
+  }).collect()
+}
+
+fn merge_fields<T : Fields>(objt : &mut T, base : &FieldMap, ours : &FieldMap, theirs : &FieldMap) {
+  let mut keys = base.keys().chain(theirs.keys()).cloned().collect::<Vec<String>>();
+  keys.sort();
+  keys.dedup();
+  for k in keys {
+    let (b,o,t) = (base.get(&k),ours.get(&k),theirs.get(&k));
+    if t != b && o == b {
+      objt.set_field(&k,t.map(|v| v.as_str()));
+    }
+  }
+}
+
+fn merge_object<T : Fields + Clone>(base : Option<&T>, ours : Option<&T>, theirs : Option<&T>) -> (Option<T>,Option<(ConflictKind,Vec<FieldConflict>)>) {
+  let (bf,of,tf) = (base.map(|x| x.fields()),ours.map(|x| x.fields()),theirs.map(|x| x.fields()));
+  if of == tf || tf == bf {
+    return (ours.cloned(),None);
+  }
+  if of == bf {
+    return (theirs.cloned(),None);
+  }
+  let empty = FieldMap::new();
+  let (bf,of,tf) = (bf.as_ref().unwrap_or(&empty),of.as_ref().unwrap_or(&empty),tf.as_ref().unwrap_or(&empty));
+  let (fields,merged) = match (ours,theirs) {
+    (Some(o),Some(_)) => {
+      let mut objt = o.clone();
+      merge_fields(&mut objt,bf,of,tf);
+      (field_conflicts(bf,of,tf),Some(objt))
+    },
+    _ => (removal_conflicts(bf,of,tf),ours.or(theirs).cloned())
+  };
+  if fields.is_empty() {
+    return (merged,None);
+  }
+  match (base,ours,theirs) {
+    (None,_,_) => (merged,Some((ConflictKind::BothAdded,fields))),
+    (Some(_),Some(_),Some(_)) => (merged,Some((ConflictKind::BothModified,fields))),
+    _ => (merged,Some((ConflictKind::ModifiedAndRemoved,fields)))
+  }
+}
+
+fn merge_collection<T : Dimension + Fields + Clone>(base : &Collection<T>, ours : &Collection<T>, theirs : &Collection<T>, merged : &mut Collection<T>, conflicts : &mut Vec<Conflict>) {
+  let mut keys = ours.keys();
+  keys.extend(theirs.keys().into_iter().filter(|k| !ours.contains(k)));
+  keys.extend(base.keys().into_iter().filter(|k| !ours.contains(k) && !theirs.contains(k)));
+  for k in keys {
+    let (keep,conflict) = merge_object(base.get(&k),ours.get(&k),theirs.get(&k));
+    if let Some((conflict,fields)) = conflict {
+      conflicts.push(Conflict{kind : Some(merged.kind()), name : k.clone(), conflict, fields});
+    }
+    if let Some(objt) = keep {
+      merged.add(objt).unwrap();
+    }
+  }
+}
+
+pub struct MergeResult {
+  pub model : Model,
+  pub conflicts : Vec<Conflict>
+}
+
+impl MergeResult {
+  pub fn is_clean(&self) -> bool {
+    self.conflicts.is_empty()
+  }
+}
+
+pub fn merge(base : &Model, ours : &Model, theirs : &Model) -> MergeResult {
+  let mut conflicts = Vec::<Conflict>::new();
+  let (settings,conflict) = merge_object(Some(&base.settings),Some(&ours.settings),Some(&theirs.settings));
+  if let Some((conflict,fields)) = conflict {
+    conflicts.push(Conflict{kind : None, name : "settings".to_string(), conflict, fields});
+  }
+  let mut m = Model::new(settings.unwrap());
+  merge_collection(&base.environments,&ours.environments,&theirs.environments,&mut m.environments,&mut conflicts);
+  merge_collection(&base.roles,&ours.roles,&theirs.roles,&mut m.roles,&mut conflicts);
+  merge_collection(&base.assets,&ours.assets,&theirs.assets,&mut m.assets,&mut conflicts);
+  merge_collection(&base.attackers,&ours.attackers,&theirs.attackers,&mut m.attackers,&mut conflicts);
+  merge_collection(&base.vulnerabilities,&ours.vulnerabilities,&theirs.vulnerabilities,&mut m.vulnerabilities,&mut conflicts);
+  merge_collection(&base.threats,&ours.threats,&theirs.threats,&mut m.threats,&mut conflicts);
+  merge_collection(&base.risks,&ours.risks,&theirs.risks,&mut m.risks,&mut conflicts);
+  merge_collection(&base.value_types,&ours.value_types,&theirs.value_types,&mut m.value_types,&mut conflicts);
+  merge_collection(&base.use_cases,&ours.use_cases,&theirs.use_cases,&mut m.use_cases,&mut conflicts);
+  merge_collection(&base.misuse_cases,&ours.misuse_cases,&theirs.misuse_cases,&mut m.misuse_cases,&mut conflicts);
+  merge_collection(&base.countermeasures,&ours.countermeasures,&theirs.countermeasures,&mut m.countermeasures,&mut conflicts);
+  merge_collection(&base.responses,&ours.responses,&theirs.responses,&mut m.responses,&mut conflicts);
+  merge_collection(&base.domain_properties,&ours.domain_properties,&theirs.domain_properties,&mut m.domain_properties,&mut conflicts);
+  merge_collection(&base.asset_associations,&ours.asset_associations,&theirs.asset_associations,&mut m.asset_associations,&mut conflicts);
+  merge_collection(&base.data_flows,&ours.data_flows,&theirs.data_flows,&mut m.data_flows,&mut conflicts);
+  merge_collection(&base.trust_boundaries,&ours.trust_boundaries,&theirs.trust_boundaries,&mut m.trust_boundaries,&mut conflicts);
+  let existing = ours.validate().into_iter().chain(theirs.validate()).collect::<Vec<String>>();
+  for problem in m.validate().into_iter().filter(|p| p.contains(" refers to unknown ") && !existing.contains(p)) {
+    conflicts.push(Conflict{kind : None, name : problem, conflict : ConflictKind::UnknownReference, fields : Vec::<FieldConflict>::new()});
+  }
+  MergeResult{model : m, conflicts}
+}
+
+#[cfg(test)]
+use crate::model::test_model;
+#[cfg(test)]
+use crate::dimensions::asset::AssetBuilder;
+#[cfg(test)]
+use crate::dimensions::projectsettings::ProjectSettingsBuilder;
+
+#[cfg(test)]
+fn assert_round_trip<T : Fields>(mut blank : T, objt : &T) {
+  let bf = blank.fields();
+  merge_fields(&mut blank,&bf,&bf,&objt.fields());
+  assert_eq!(blank.fields(),objt.fields());
+}
+
+#[test]
+fn test_set_fields() {
+  let m = test_model();
+  assert_round_trip(Environment::new("Complete",""),m.environments.get("Complete").unwrap());
+  assert_round_trip(Role::new("Researcher","Machine","",""),m.roles.get("Researcher").unwrap());
+  assert_round_trip(Asset::new("Data","","",true),m.assets.get("Data").unwrap());
+  assert_round_trip(Attacker::new("Carol",""),m.attackers.get("Carol").unwrap());
+  assert_round_trip(Vulnerability::new("Unvalidated input",""),m.vulnerabilities.get("Unvalidated input").unwrap());
+  assert_round_trip(Risk::new("Upload risk","",""),m.risks.get("Upload risk").unwrap());
+  assert_round_trip(ValueType::new("High","","threat_value"),m.value_types.get("threat_value/High").unwrap());
+  assert_round_trip(MisuseCase::new("Exploit upload",""),m.misuse_cases.get("Exploit upload").unwrap());
+  assert_round_trip(DomainProperty::new("Users authenticate","","Invariant",""),m.domain_properties.get("Users authenticate").unwrap());
+  assert_round_trip(AssetAssociation::new("Day","Portal","Data"),m.asset_associations.get("Day/Portal/Data").unwrap());
+  assert_round_trip(DataFlow::new("Upload","Night","Data","datastore","Portal","entity"),m.data_flows.get("Upload").unwrap());
+  assert_round_trip(TrustBoundary::new("Server room",""),m.trust_boundaries.get("Server room").unwrap());
+
+  let mut t = m.threats.get("SQL injection").unwrap().clone();
+  t.environments[0].properties.push(SecurityPropertyValue::new("integrity","High","Tampering"));
+  assert_round_trip(Threat::new("SQL injection",""),&t);
+  assert_round_trip(t,m.threats.get("SQL injection").unwrap());
+
+  let mut uc = m.use_cases.get("Upload data").unwrap().clone();
+  uc.environments[0].add_step("Researcher opens portal");
+  uc.environments[0].add_step("Researcher uploads data");
+  uc.environments[0].add_exception(2,StepException::new("Too large","Confidentiality threat","File is rejected"));
+  assert_round_trip(UseCase::new("Upload data",""),&uc);
+  assert_round_trip(uc,m.use_cases.get("Upload data").unwrap());
+
+  let mut cm = m.countermeasures.get("Input validation").unwrap().clone();
+  cm.environments[0].properties.push(SecurityPropertyValue::new("integrity","Medium","Inputs are checked"));
+  assert_round_trip(Countermeasure::new("Input validation",""),&cm);
+  assert_round_trip(cm,m.countermeasures.get("Input validation").unwrap());
+
+  let mut r = Response::new("Insure upload","Upload risk");
+  r.environments.push(ResponseEnvironment::mitigate("Day","Detect","Audit log"));
+  r.environments.push(ResponseEnvironment::accept("Night","Low","Rarely used"));
+  assert_round_trip(m.responses.get("Insure upload").unwrap().clone(),&r);
+  assert_round_trip(r,m.responses.get("Insure upload").unwrap());
+
+  let settings = ProjectSettingsBuilder::new("NeuroGrid").scope("Hospital").naming_convention("Risk","R-").contributor("Shamal","Faily","Cairis","Developer").revision("1","2026-10-19","Initial").build().unwrap();
+  assert_round_trip(ProjectSettings::new(""),&settings);
+  assert_round_trip(settings,&m.settings);
+}
+
+#[test]
+fn test_set_fields_with_separators() {
+  let mut a = Attacker::new("Carol","");
+  a.tags.push(Tag::new("a;b"));
+  let mut ae = AttackerEnvironment::new("Day/Night");
+  ae.roles = vec!["Admin;Ops".to_string(),"C:\\Users".to_string()];
+  a.environments.push(ae);
+  let mut blank = Attacker::new("Carol","");
+  let bf = blank.fields();
+  merge_fields(&mut blank,&bf,&bf,&a.fields());
+  assert_eq!(blank.tags[0].name,"a;b".to_string());
+  assert_eq!(blank.environments.len(),1);
+  assert_eq!(blank.environments[0].name(),"Day/Night");
+  assert_eq!(blank.environments[0].roles,a.environments[0].roles);
+
+  let mut cm = Countermeasure::new("Input validation","Software");
+  let mut ce = CountermeasureEnvironment::new("Day/Night");
+  ce.targets.push(CountermeasureTarget::new("SQL/NoSQL injection","High","Queries; inputs"));
+  cm.environments.push(ce);
+  assert_round_trip(Countermeasure::new("Input validation",""),&cm);
+
+  let mut uc = UseCase::new("Upload data","UC-1");
+  let mut uce = UseCaseEnvironment::new("Day/Night");
+  uce.add_step("Researcher uploads data");
+  uce.add_exception(1,StepException::new("Too large/slow","Availability; threat","File is rejected"));
+  uc.environments.push(uce);
+  assert_round_trip(UseCase::new("Upload data",""),&uc);
+
+  let mut env = Environment::new("Complete","CMP");
+  env.environments.add("Day/Night");
+  env.environments.add("Weekend;Holiday");
+  assert_round_trip(Environment::new("Complete",""),&env);
+
+  let settings = ProjectSettingsBuilder::new("NeuroGrid").naming_convention("Risk/Threat","R-").contributor("Shamal","Faily","Cairis; Bournemouth","Developer").revision("1/a","2026;10","Initial").build().unwrap();
+  assert_round_trip(ProjectSettings::new(""),&settings);
+}
+
+#[test]
+fn test_diff_models() {
+  let base = test_model();
+  assert!(diff(&base,&base).is_empty());
+  let mut changed = test_model();
+  changed.settings.scope = "Hospital".to_string();
+  changed.assets.remove("Portal");
+  changed.assets.add(AssetBuilder::new("Server").short_code("SRV").asset_type("Hardware").build().unwrap()).unwrap();
+  changed.assets.remove("Data");
+  changed.assets.add(AssetBuilder::new("Data").short_code("DAT").asset_type("Information").property("Day","confidentiality","Medium","Pseudonymised").build().unwrap()).unwrap();
+  changed.environments.remove("Complete");
+  changed.environments.add(crate::dimensions::environment::EnvironmentBuilder::new("Complete").short_code("CMP").composite(&["Day"]).build().unwrap()).unwrap();
+  let cs = diff(&base,&changed);
+  assert_eq!(cs.settings,vec![FieldChange{field : "scope".to_string(), old_value : Some("".to_string()), new_value : Some("Hospital".to_string())}]);
+  assert_eq!(cs.changes_to(DimensionKind::Asset).iter().map(|c| c.to_string()).collect::<Vec<String>>(),vec![
+    "~ asset Data\n    Day/confidentiality: High -> Medium\n    Day/confidentiality/rationale:  -> Pseudonymised".to_string(),
+    "- asset Portal".to_string(),
+    "+ asset Server".to_string()
+  ]);
+  assert_eq!(cs.changes_to(DimensionKind::Environment)[0].change,Change::Modified(vec![FieldChange{field : "composite/Night".to_string(), old_value : Some("member".to_string()), new_value : None}]));
+  assert!(cs.to_string().starts_with("~ settings\n    scope:  -> Hospital\n~ environment Complete"));
+}
+
+#[test]
+fn test_diff_all_dimensions() {
+  let base = test_model();
+  assert!(diff(&base,&base).is_empty());
+  let mut changed = test_model();
+  let mut uc = changed.use_cases.remove("Upload data").unwrap();
+  uc.environments[0].add_step("Researcher uploads data");
+  changed.use_cases.add(uc).unwrap();
+  let mut df = changed.data_flows.remove("Upload").unwrap();
+  df.assets.clear();
+  changed.data_flows.add(df).unwrap();
+  changed.domain_properties.remove("Users authenticate");
+  assert_eq!(diff(&base,&changed).to_string(),"~ use_case Upload data\n    Day/step/1: added Researcher uploads data\n- domain_property Users authenticate\n~ data_flow Upload\n    assets: Data -> ");
+}
+
+#[test]
+fn test_merge_models() {
+  let base = test_model();
+  let mut ours = test_model();
+  ours.roles.add(Role::new("Admin","Stakeholder","ADM","")).unwrap();
+  ours.risks.remove("Upload risk");
+  let mut theirs = test_model();
+  theirs.settings.scope = "Hospital".to_string();
+  theirs.assets.remove("Portal");
+  theirs.value_types.add(ValueType::new("Low","","threat_value")).unwrap();
+
+  let res = merge(&base,&ours,&theirs);
+  assert!(res.is_clean());
+  assert_eq!(res.model.roles.keys(),vec!["Researcher".to_string(),"Admin".to_string()]);
+  assert!(!res.model.assets.contains("Portal"));
+  assert!(!res.model.risks.contains("Upload risk"));
+  assert!(res.model.value_type("threat_value","Low").is_some());
+  assert_eq!(res.model.settings.scope,"Hospital".to_string());
+
+  ours.settings.scope = "Lab".to_string();
+  let res = merge(&base,&ours,&theirs);
+  assert_eq!(res.conflicts.iter().map(|c| c.to_string()).collect::<Vec<String>>(),vec!["settings modified on both sides\n    scope: base , ours Lab, theirs Hospital".to_string()]);
+  assert_eq!(res.model.settings.scope,"Lab".to_string());
+}
+
+#[test]
+fn test_merge_disjoint_fields() {
+  let base = test_model();
+  let mut ours = test_model();
+  ours.settings.scope = "Hospital".to_string();
+  let mut t = ours.threats.remove("SQL injection").unwrap();
+  t.method = "Crafted form input".to_string();
+  ours.threats.add(t).unwrap();
+  let mut uc = ours.use_cases.remove("Upload data").unwrap();
+  uc.environments[0].add_step("Researcher uploads data");
+  ours.use_cases.add(uc).unwrap();
+  let mut theirs = test_model();
+  theirs.settings.background = "Brain imaging".to_string();
+  let mut t = theirs.threats.remove("SQL injection").unwrap();
+  t.environments[0].set_likelihood("Remote");
+  t.environments[0].properties.push(SecurityPropertyValue::new("integrity","High","Tampering"));
+  theirs.threats.add(t).unwrap();
+  let mut uc = theirs.use_cases.remove("Upload data").unwrap();
+  uc.description = "Researchers share scans".to_string();
+  uc.environments[0].preconditions = "Logged in".to_string();
+  theirs.use_cases.add(uc).unwrap();
+
+  let res = merge(&base,&ours,&theirs);
+  assert!(res.is_clean());
+  assert_eq!(res.model.settings.scope,"Hospital".to_string());
+  assert_eq!(res.model.settings.background,"Brain imaging".to_string());
+  let t = res.model.threats.get("SQL injection").unwrap();
+  assert_eq!(t.method,"Crafted form input".to_string());
+  assert_eq!(t.environments[0].likelihood().to_string(),"Remote");
+  assert_eq!(t.environments[0].properties.len(),1);
+  let uc = res.model.use_cases.get("Upload data").unwrap();
+  assert_eq!(uc.description,"Researchers share scans".to_string());
+  assert_eq!(uc.environments[0].preconditions,"Logged in".to_string());
+  assert_eq!(uc.environments[0].steps.len(),1);
+  assert!(res.model.validate().is_empty());
+}
+
+#[test]
+fn test_merge_conflicts() {
+  let base = test_model();
+  let mut ours = test_model();
+  ours.assets.remove("Data");
+  ours.assets.add(AssetBuilder::new("Data").short_code("DAT").asset_type("Information").property("Day","confidentiality","Low","").build().unwrap()).unwrap();
+  ours.roles.add(Role::new("Admin","Stakeholder","ADM","")).unwrap();
+  ours.threats.remove("SQL injection");
+  let mut theirs = test_model();
+  theirs.assets.remove("Data");
+  theirs.assets.add(AssetBuilder::new("Data").short_code("DAT").asset_type("Information").property("Day","confidentiality","Medium","").build().unwrap()).unwrap();
+  theirs.roles.add(Role::new("Admin","Stakeholder","SYS","")).unwrap();
+  let mut t = theirs.threats.remove("SQL injection").unwrap();
+  t.method = "Crafted form input".to_string();
+  theirs.threats.add(t).unwrap();
+
+  let res = merge(&base,&ours,&theirs);
+  assert_eq!(res.conflicts.iter().map(|c| c.to_string()).collect::<Vec<String>>(),vec![
+    "role Admin added on both sides\n    short_code: base (none), ours ADM, theirs SYS".to_string(),
+    "asset Data modified on both sides\n    Day/confidentiality: base High, ours Low, theirs Medium".to_string(),
+    "threat SQL injection modified on one side and removed on the other\n    method: base , ours (none), theirs Crafted form input".to_string()
+  ]);
+  assert_eq!(res.model.roles.get("Admin").unwrap().short_code(),"ADM");
+  assert_eq!(res.model.threats.get("SQL injection").unwrap().method,"Crafted form input".to_string());
+  assert!(!res.is_clean());
+}
+
+#[test]
+fn test_merge_removal_against_deletion() {
+  let base = test_model();
+  let mut ours = test_model();
+  ours.threats.remove("SQL injection");
+  let mut theirs = test_model();
+  let mut t = theirs.threats.remove("SQL injection").unwrap();
+  t.environments[0].attackers.clear();
+  theirs.threats.add(t).unwrap();
+
+  let res = merge(&base,&ours,&theirs);
+  assert_eq!(res.conflicts.iter().map(|c| c.to_string()).collect::<Vec<String>>(),vec![
+    "threat SQL injection modified on one side and removed on the other\n    Day/attackers: base Carol, ours (none), theirs ".to_string()
+  ]);
+  assert!(res.model.threats.get("SQL injection").unwrap().environments[0].attackers.is_empty());
+
+  let res = merge(&base,&theirs,&ours);
+  assert_eq!(res.conflicts.len(),1);
+  assert_eq!(res.conflicts[0].fields[0].ours,Some("".to_string()));
+  assert!(res.model.threats.contains("SQL injection"));
+}
+
+#[test]
+fn test_merge_unknown_references() {
+  let base = test_model();
+  let mut ours = test_model();
+  ours.assets.remove("Portal");
+  let mut theirs = test_model();
+  let mut t = theirs.threats.remove("SQL injection").unwrap();
+  t.environments[0].assets.push("Portal".to_string());
+  theirs.threats.add(t).unwrap();
+
+  let res = merge(&base,&ours,&theirs);
+  assert_eq!(res.conflicts.iter().map(|c| c.to_string()).collect::<Vec<String>>(),vec![
+    "Threat SQL injection refers to unknown asset Portal after merging".to_string()
+  ]);
+  assert!(!res.is_clean());
+  assert!(res.model.threats.get("SQL injection").unwrap().environments[0].assets.contains(&"Portal".to_string()));
+}
@@ -0,0 +1,402 @@
+pub mod collection;
+pub mod diff;
+pub mod refactor;
+
+use std::path::Path;
+use crate::analysis::riskmatrix::RiskMatrix;
+use crate::dimensions::asset::Asset;
+use crate::dimensions::assetassociation::AssetAssociation;
+use crate::dimensions::attacker::{Attacker,AttackerEnvironment};
+use crate::dimensions::countermeasure::Countermeasure;
+use crate::dimensions::dataflow::{DataFlow,DataFlowComponentType};
+use crate::dimensions::dimension::{self,Dimension,DimensionKind};
+use crate::dimensions::domainproperty::DomainProperty;
+use crate::dimensions::environment::Environment;
+use crate::dimensions::misusecase::MisuseCase;
+use crate::dimensions::projectsettings::ProjectSettings;
+use crate::dimensions::response::Response;
+use crate::dimensions::risk::Risk;
+use crate::dimensions::role::Role;
+use crate::dimensions::threat::{Threat,ThreatEnvironment};
+use crate::dimensions::trustboundary::TrustBoundary;
+use crate::dimensions::usecase::UseCase;
+use crate::dimensions::valuetype::ValueType;
+use crate::dimensions::vulnerability::{Vulnerability,VulnerabilityEnvironment};
+use crate::exchange::modeldir::ModelDirectory;
+use crate::model::collection::Collection;
+
+fn value_type_key(vt : &ValueType) -> String {
+  dimension::value_type_key(&vt.vt_type,&vt.name)
+}
+
+fn asset_association_key(aa : &AssetAssociation) -> String {
+  dimension::asset_association_key(&aa.environment,&aa.head_asset,&aa.tail_asset)
+}
+
+#[derive(Clone)]
+pub struct Model {
+  pub settings : ProjectSettings,
+  pub environments : Collection<Environment>,
+  pub roles : Collection<Role>,
+  pub assets : Collection<Asset>,
+  pub attackers : Collection<Attacker>,
+  pub vulnerabilities : Collection<Vulnerability>,
+  pub threats : Collection<Threat>,
+  pub risks : Collection<Risk>,
+  pub value_types : Collection<ValueType>,
+  pub use_cases : Collection<UseCase>,
+  pub misuse_cases : Collection<MisuseCase>,
+  pub countermeasures : Collection<Countermeasure>,
+  pub responses : Collection<Response>,
+  pub domain_properties : Collection<DomainProperty>,
+  pub asset_associations : Collection<AssetAssociation>,
+  pub data_flows : Collection<DataFlow>,
+  pub trust_boundaries : Collection<TrustBoundary>
+}
+
+impl Model {
+  pub fn new(settings : ProjectSettings) -> Model {
+    Model{
+      settings,
+      environments : Collection::new(DimensionKind::Environment),
+      roles : Collection::new(DimensionKind::Role),
+      assets : Collection::new(DimensionKind::Asset),
+      attackers : Collection::new(DimensionKind::Attacker),
+      vulnerabilities : Collection::new(DimensionKind::Vulnerability),
+      threats : Collection::new(DimensionKind::Threat),
+      risks : Collection::new(DimensionKind::Risk),
+      value_types : Collection::with_key(DimensionKind::ValueType,value_type_key),
+      use_cases : Collection::new(DimensionKind::UseCase),
+      misuse_cases : Collection::new(DimensionKind::MisuseCase),
+      countermeasures : Collection::new(DimensionKind::Countermeasure),
+      responses : Collection::new(DimensionKind::Response),
+      domain_properties : Collection::new(DimensionKind::DomainProperty),
+      asset_associations : Collection::with_key(DimensionKind::AssetAssociation,asset_association_key),
+      data_flows : Collection::new(DimensionKind::DataFlow),
+      trust_boundaries : Collection::new(DimensionKind::TrustBoundary)
+    }
+  }
+
+  pub fn value_type(&self, vt_type : &str, vt_name : &str) -> Option<&ValueType> {
+    self.value_types.get(&dimension::value_type_key(vt_type,vt_name))
+  }
+
+  pub fn composite_environments(&self, env : &Environment) -> Result<Vec<&Environment>,Vec<String>> {
+    self.environments.resolve(&format!("Environment {}",env.name),&env.environments.environments)
+  }
+
+  pub fn attacker_roles(&self, attacker : &Attacker, ae : &AttackerEnvironment) -> Result<Vec<&Role>,Vec<String>> {
+    self.roles.resolve(&format!("Attacker {}",attacker.name()),&ae.roles)
+  }
+
+  pub fn vulnerability_assets(&self, vul : &Vulnerability, ve : &VulnerabilityEnvironment) -> Result<Vec<&Asset>,Vec<String>> {
+    self.assets.resolve(&format!("Vulnerability {}",vul.name()),&ve.assets)
+  }
+
+  pub fn threat_assets(&self, threat : &Threat, te : &ThreatEnvironment) -> Result<Vec<&Asset>,Vec<String>> {
+    self.assets.resolve(&format!("Threat {}",threat.name()),&te.assets)
+  }
+
+  pub fn threat_attackers(&self, threat : &Threat, te : &ThreatEnvironment) -> Result<Vec<&Attacker>,Vec<String>> {
+    self.attackers.resolve(&format!("Threat {}",threat.name()),&te.attackers)
+  }
+
+  pub fn risk_threat(&self, risk : &Risk) -> Result<&Threat,String> {
+    self.threats.resolve(&format!("Risk {}",risk.name()),std::slice::from_ref(&risk.threat)).map(|t| t[0]).map_err(|mut e| e.remove(0))
+  }
+
+  pub fn risk_vulnerability(&self, risk : &Risk) -> Result<&Vulnerability,String> {
+    self.vulnerabilities.resolve(&format!("Risk {}",risk.name()),std::slice::from_ref(&risk.vulnerability)).map(|v| v[0]).map_err(|mut e| e.remove(0))
+  }
+
+  fn component(&self, owner : &str, c_type : &DataFlowComponentType, c_name : &str) -> Result<(),Vec<String>> {
+    let names = [c_name.to_string()];
+    match c_type.dimension() {
+      DimensionKind::UseCase => self.use_cases.resolve(owner,&names).map(|_| ()),
+      _ => self.assets.resolve(owner,&names).map(|_| ())
+    }
+  }
+
+  pub fn validate(&self) -> Vec<String> {
+    let mut problems = Vec::<String>::new();
+    let mut check = |res : Result<(),Vec<String>>| {
+      if let Err(errs) = res {
+        problems.extend(errs);
+      }
+    };
+    for env in self.environments.all() {
+      check(self.composite_environments(env).map(|_| ()));
+    }
+    for a in self.assets.all() {
+      let mut env_names = a.environment_properties.keys().cloned().collect::<Vec<String>>();
+      env_names.sort();
+      check(self.environments.resolve(&format!("Asset {}",a.name()),&env_names).map(|_| ()));
+    }
+    for a in self.attackers.all() {
+      for ae in &a.environments {
+        check(self.environments.resolve(&format!("Attacker {}",a.name()),&[ae.name().to_string()]).map(|_| ()));
+        check(self.attacker_roles(a,ae).map(|_| ()));
+      }
+    }
+    for v in self.vulnerabilities.all() {
+      for ve in &v.environments {
+        check(self.environments.resolve(&format!("Vulnerability {}",v.name()),&[ve.name().to_string()]).map(|_| ()));
+        check(self.vulnerability_assets(v,ve).map(|_| ()));
+      }
+    }
+    for t in self.threats.all() {
+      for te in &t.environments {
+        check(self.environments.resolve(&format!("Threat {}",t.name()),&[te.name().to_string()]).map(|_| ()));
+        check(self.threat_assets(t,te).map(|_| ()));
+        check(self.threat_attackers(t,te).map(|_| ()));
+      }
+    }
+    for r in self.risks.all() {
+      check(self.risk_threat(r).map(|_| ()).map_err(|e| vec![e]));
+      check(self.risk_vulnerability(r).map(|_| ()).map_err(|e| vec![e]));
+    }
+    for uc in self.use_cases.all() {
+      let owner = format!("Use case {}",uc.name());
+      check(self.environments.resolve(&owner,&uc.environment_names()).map(|_| ()));
+      check(self.roles.resolve(&owner,&uc.actors).map(|_| ()));
+    }
+    for mc in self.misuse_cases.all() {
+      let owner = format!("Misuse case {}",mc.name());
+      check(self.risks.resolve(&owner,&[mc.risk().to_string()]).map(|_| ()));
+      for mce in &mc.environments {
+        check(self.environments.resolve(&owner,&[mce.name().to_string()]).map(|_| ()));
+        check(self.attackers.resolve(&owner,&mce.attackers).map(|_| ()));
+        check(self.assets.resolve(&owner,&mce.assets).map(|_| ()));
+      }
+    }
+    for cm in self.countermeasures.all() {
+      let owner = format!("Countermeasure {}",cm.name());
+      for ce in &cm.environments {
+        check(self.environments.resolve(&owner,&[ce.name().to_string()]).map(|_| ()));
+        check(self.roles.resolve(&owner,&ce.roles).map(|_| ()));
+        for t in ce.targets.iter().filter(|t| !self.threats.contains(&t.name) && !self.vulnerabilities.contains(&t.name)) {
+          check(Err(vec![format!("{} refers to unknown threat or vulnerability {}",owner,t.name)]));
+        }
+      }
+    }
+    for r in self.responses.all() {
+      let owner = format!("Response {}",r.name());
+      check(self.risks.resolve(&owner,&[r.risk().to_string()]).map(|_| ()));
+      check(self.environments.resolve(&owner,&r.environment_names()).map(|_| ()));
+    }
+    for aa in self.asset_associations.all() {
+      let owner = format!("Asset association {}",asset_association_key(aa));
+      check(self.environments.resolve(&owner,std::slice::from_ref(&aa.environment)).map(|_| ()));
+      check(self.assets.resolve(&owner,&[aa.head_asset.clone(),aa.tail_asset.clone()]).map(|_| ()));
+    }
+    for df in self.data_flows.all() {
+      let owner = format!("Data flow {}",df.name());
+      check(self.environments.resolve(&owner,&[df.environment().to_string()]).map(|_| ()));
+      check(self.component(&owner,&df.source_type,&df.source_name));
+      check(self.component(&owner,&df.target_type,&df.target_name));
+      check(self.assets.resolve(&owner,&df.assets).map(|_| ()));
+    }
+    for tb in self.trust_boundaries.all() {
+      let owner = format!("Trust boundary {}",tb.name());
+      for tbe in &tb.environments {
+        check(self.environments.resolve(&owner,&[tbe.name().to_string()]).map(|_| ()));
+        tbe.components.iter().for_each(|(c_type,c_name)| check(self.component(&owner,c_type,c_name)));
+      }
+    }
+    problems
+  }
+
+  pub fn risk_matrix(&self, env_name : &str) -> RiskMatrix {
+    RiskMatrix::new(env_name,self.risks.all(),self.threats.all(),self.vulnerabilities.all())
+  }
+
+  pub fn from_directory(md : &ModelDirectory) -> Result<Model,Vec<String>> {
+    let (m,problems) = Model::load_directory(md);
+    if problems.is_empty() {
+      Ok(m)
+    }
+    else {
+      Err(problems)
+    }
+  }
+
+  pub fn load_directory(md : &ModelDirectory) -> (Model,Vec<String>) {
+    let mut m = Model::new(md.settings.clone());
+    let mut problems : Vec<String> = md.errors.iter().map(|(f,e)| format!("{}: {}",f,e)).collect();
+    let mut add = |res : Result<(),String>| {
+      if let Err(err) = res {
+        problems.push(err);
+      }
+    };
+    md.environments.iter().for_each(|o| add(m.environments.add(o.clone())));
+    md.roles.iter().for_each(|o| add(m.roles.add(o.clone())));
+    md.assets.iter().for_each(|o| add(m.assets.add(o.clone())));
+    md.attackers.iter().for_each(|o| add(m.attackers.add(o.clone())));
+    md.vulnerabilities.iter().for_each(|o| add(m.vulnerabilities.add(o.clone())));
+    md.threats.iter().for_each(|o| add(m.threats.add(o.clone())));
+    md.risks.iter().for_each(|o| add(m.risks.add(o.clone())));
+    md.value_types.iter().for_each(|o| add(m.value_types.add(o.clone())));
+    (m,problems)
+  }
+
+  pub fn to_directory(&self, dir : &Path) -> Result<ModelDirectory,String> {
+    let unstored : Vec<String> = [
+      (self.use_cases.kind(),self.use_cases.is_empty()),
+      (self.misuse_cases.kind(),self.misuse_cases.is_empty()),
+      (self.countermeasures.kind(),self.countermeasures.is_empty()),
+      (self.responses.kind(),self.responses.is_empty()),
+      (self.domain_properties.kind(),self.domain_properties.is_empty()),
+      (self.asset_associations.kind(),self.asset_associations.is_empty()),
+      (self.data_flows.kind(),self.data_flows.is_empty()),
+      (self.trust_boundaries.kind(),self.trust_boundaries.is_empty())
+    ].iter().filter(|(_,empty)| !empty).map(|(kind,_)| kind.to_string()).collect();
+    if !unstored.is_empty() {
+      return Err(format!("Model directories cannot store {}",unstored.join(", ")));
+    }
+    let mut md = ModelDirectory::new(dir);
+    md.settings = self.settings.clone();
+    md.environments = self.environments.all().to_vec();
+    md.roles = self.roles.all().to_vec();
+    md.assets = self.assets.all().to_vec();
+    md.attackers = self.attackers.all().to_vec();
+    md.vulnerabilities = self.vulnerabilities.all().to_vec();
+    md.threats = self.threats.all().to_vec();
+    md.risks = self.risks.all().to_vec();
+    md.value_types = self.value_types.all().to_vec();
+    Ok(md)
+  }
+}
+
+#[cfg(test)]
+pub fn test_model() -> Model {
+  use crate::dimensions::{asset::AssetBuilder,attacker::AttackerBuilder,environment::EnvironmentBuilder,role::RoleBuilder,vulnerability::VulnerabilityBuilder,valuetype::ValueTypeBuilder};
+  use crate::dimensions::{countermeasure::{CountermeasureEnvironment,CountermeasureTarget},misusecase::MisuseCaseEnvironment,response::ResponseEnvironment,trustboundary::TrustBoundaryEnvironment,usecase::UseCaseEnvironment};
+  let mut m = Model::new(ProjectSettings::new("NeuroGrid"));
+  m.environments.add(EnvironmentBuilder::new("Day").short_code("DAY").build().unwrap()).unwrap();
+  m.environments.add(EnvironmentBuilder::new("Night").short_code("NGT").build().unwrap()).unwrap();
+  m.environments.add(EnvironmentBuilder::new("Complete").short_code("CMP").composite(&["Day","Night"]).build().unwrap()).unwrap();
+  m.roles.add(RoleBuilder::new("Researcher").role_type("Stakeholder").short_code("RES").build().unwrap()).unwrap();
+  m.assets.add(AssetBuilder::new("Data").short_code("DAT").asset_type("Information").property("Day","confidentiality","High","").build().unwrap()).unwrap();
+  m.assets.add(AssetBuilder::new("Portal").short_code("POR").asset_type("Software").environment("Day").build().unwrap()).unwrap();
+  m.attackers.add(AttackerBuilder::new("Carol").environment("Day",&["Researcher"],&["Revenge"],&[]).build().unwrap()).unwrap();
+  m.vulnerabilities.add(VulnerabilityBuilder::new("Unvalidated input").vulnerability_type("Implementation").environment("Day","Critical",&["Portal","Data"]).build().unwrap()).unwrap();
+  let mut t = Threat::new("SQL injection","Software");
+  let mut te = ThreatEnvironment::new("Day","Probable");
+  te.assets.push("Data".to_string());
+  te.attackers.push("Carol".to_string());
+  t.environments.push(te);
+  m.threats.add(t).unwrap();
+  m.risks.add(Risk::new("Upload risk","SQL injection","Unvalidated input")).unwrap();
+  m.value_types.add(ValueTypeBuilder::new("High","threat_value").score(3).build().unwrap()).unwrap();
+  m.value_types.add(ValueTypeBuilder::new("High","asset_value").score(3).build().unwrap()).unwrap();
+  let mut uc = UseCase::new("Upload data","UC-1");
+  uc.add_actor(m.roles.get("Researcher").unwrap());
+  uc.environments.push(UseCaseEnvironment::new("Day"));
+  m.use_cases.add(uc).unwrap();
+  let mut mc = MisuseCase::new("Exploit upload","Upload risk");
+  let mut mce = MisuseCaseEnvironment::new("Day");
+  mce.attackers.push("Carol".to_string());
+  mce.assets.push("Data".to_string());
+  mc.environments.push(mce);
+  m.misuse_cases.add(mc).unwrap();
+  let mut cm = Countermeasure::new("Input validation","Software");
+  let mut ce = CountermeasureEnvironment::new("Day");
+  ce.targets.push(CountermeasureTarget::new("SQL injection","Medium","Inputs are sanitised"));
+  ce.roles.push("Researcher".to_string());
+  cm.environments.push(ce);
+  m.countermeasures.add(cm).unwrap();
+  let mut r = Response::new("Insure upload","Upload risk");
+  let mut re = ResponseEnvironment::transfer("Day","Insure against it");
+  re.add_role(m.roles.get("Researcher").unwrap(),"Low");
+  r.environments.push(re);
+  m.responses.add(r).unwrap();
+  m.domain_properties.add(DomainProperty::new("Users authenticate","DP-1","Hypothesis","Shamal Faily")).unwrap();
+  m.asset_associations.add(AssetAssociation::new("Day","Portal","Data")).unwrap();
+  let mut df = DataFlow::new("Upload","Day","Portal","entity","Upload data","process");
+  df.assets.push("Data".to_string());
+  m.data_flows.add(df).unwrap();
+  let mut tb = TrustBoundary::new("Server room","Physical");
+  let mut tbe = TrustBoundaryEnvironment::new("Day","Administrator");
+  tbe.add_component("process","Upload data");
+  tbe.add_component("datastore","Data");
+  tb.environments.push(tbe);
+  m.trust_boundaries.add(tb).unwrap();
+  m
+}
+
+#[test]
+fn test_model_uniqueness() {
+  let mut m = test_model();
+  assert_eq!(m.roles.add(Role::new("Researcher","Stakeholder","R2","")).err(),Some("Duplicate role Researcher".to_string()));
+  assert_eq!(m.value_types.add(ValueType::new("High","","threat_value")).err(),Some("Duplicate value_type threat_value/High".to_string()));
+  assert_eq!(m.value_type("asset_value","High").unwrap().score,3);
+}
+
+#[test]
+fn test_model_lookups() {
+  let m = test_model();
+  let carol = m.attackers.get("Carol").unwrap();
+  assert_eq!(m.attacker_roles(carol,&carol.environments[0]).unwrap()[0].short_code(),"RES");
+  let vul = m.vulnerabilities.get("Unvalidated input").unwrap();
+  assert_eq!(m.vulnerability_assets(vul,&vul.environments[0]).unwrap().iter().map(|a| a.name()).collect::<Vec<&str>>(),vec!["Portal","Data"]);
+  let risk = m.risks.get("Upload risk").unwrap();
+  assert_eq!(m.risk_threat(risk).unwrap().threat_type(),"Software");
+  assert_eq!(m.risk_vulnerability(risk).unwrap().vulnerability_type(),"Implementation");
+  assert_eq!(m.composite_environments(m.environments.get("Complete").unwrap()).unwrap().len(),2);
+  assert_eq!(m.risk_matrix("Day").risks(crate::dimensions::threat::Likelihood::Probable,crate::dimensions::vulnerability::Severity::Critical),["Upload risk".to_string()]);
+  assert!(m.validate().is_empty());
+}
+
+#[test]
+fn test_model_validate() {
+  let mut m = test_model();
+  m.risks.add(Risk::new("Phishing risk","Phishing","Unvalidated input")).unwrap();
+  let mut a = Attacker::new("Mallory","");
+  let mut ae = AttackerEnvironment::new("Evening");
+  ae.roles.push("Hacker".to_string());
+  a.environments.push(ae);
+  m.attackers.add(a).unwrap();
+  assert_eq!(m.validate(),vec![
+    "Attacker Mallory refers to unknown environment Evening".to_string(),
+    "Attacker Mallory refers to unknown role Hacker".to_string(),
+    "Risk Phishing risk refers to unknown threat Phishing".to_string()
+  ]);
+}
+
+#[test]
+fn test_model_validate_all_dimensions() {
+  let mut m = test_model();
+  assert!(m.validate().is_empty());
+  assert_eq!(m.asset_associations.add(AssetAssociation::new("Day","Portal","Data")).err(),Some("Duplicate asset_association Day/Portal/Data".to_string()));
+  m.assets.remove("Data");
+  m.roles.remove("Researcher");
+  assert_eq!(m.validate().iter().filter(|e| !e.starts_with("Attacker") && !e.starts_with("Vulnerability") && !e.starts_with("Threat")).cloned().collect::<Vec<String>>(),vec![
+    "Use case Upload data refers to unknown role Researcher".to_string(),
+    "Misuse case Exploit upload refers to unknown asset Data".to_string(),
+    "Countermeasure Input validation refers to unknown role Researcher".to_string(),
+    "Asset association Day/Portal/Data refers to unknown asset Data".to_string(),
+    "Data flow Upload refers to unknown asset Data".to_string(),
+    "Trust boundary Server room refers to unknown asset Data".to_string()
+  ]);
+}
+
+#[test]
+fn test_model_directory_conversion() {
+  let m = test_model();
+  assert_eq!(m.to_directory(Path::new("/tmp/NeuroGrid")).err(),Some("Model directories cannot store use_case, misuse_case, countermeasure, response, domain_property, asset_association, data_flow, trust_boundary".to_string()));
+
+  let mut md = ModelDirectory::new(Path::new("/tmp/NeuroGrid"));
+  md.environments = m.environments.all().to_vec();
+  md.roles = m.roles.all().to_vec();
+  md.assets = m.assets.all().to_vec();
+  md.value_types = m.value_types.all().to_vec();
+  let m2 = Model::from_directory(&md).unwrap();
+  assert_eq!(m2.settings.name,"NeuroGrid".to_string());
+  assert_eq!(m2.value_types.len(),2);
+  let md2 = m2.to_directory(Path::new("/tmp/NeuroGrid")).unwrap();
+  assert_eq!(md2.assets.len(),2);
+  assert_eq!(md2.settings.name,"NeuroGrid".to_string());
+  let mut dup = md2;
+  dup.roles.push(Role::new("Researcher","Stakeholder","R2",""));
+  assert_eq!(Model::from_directory(&dup).err(),Some(vec!["Duplicate role Researcher".to_string()]));
+}
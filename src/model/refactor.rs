@@ -0,0 +1,408 @@
+use std::fmt;
+use crate::dimensions::dimension::{Dimension,DimensionKind,Referencing,asset_association_key,value_type_key};
+use crate::model::Model;
+
+#[derive(Clone,Copy,PartialEq,Debug)]
+pub enum DeletePolicy {
+  Cascade,
+  Restrict,
+  Nullify
+}
+
+impl DeletePolicy {
+  pub fn new(policy : &str) -> DeletePolicy {
+    match policy {
+      "cascade" => DeletePolicy::Cascade,
+      "restrict" => DeletePolicy::Restrict,
+      "nullify" => DeletePolicy::Nullify,
+      &_ => panic!("{} is not a delete policy",policy)
+    }
+  }
+}
+
+impl fmt::Display for DeletePolicy {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      DeletePolicy::Cascade => write!(f,"cascade"),
+      DeletePolicy::Restrict => write!(f,"restrict"),
+      DeletePolicy::Nullify => write!(f,"nullify")
+    }
+  }
+}
+
+#[test]
+fn test_delete_policy() {
+  assert_eq!(DeletePolicy::new("nullify"),DeletePolicy::Nullify);
+  assert_eq!(DeletePolicy::Cascade.to_string(),"cascade");
+}
+
+#[test]
+#[should_panic]
+fn test_invalid_delete_policy() {
+  DeletePolicy::new("ignore");
+}
+
+#[derive(Clone,PartialEq,Debug)]
+pub struct Reference {
+  pub kind : DimensionKind,
+  pub name : String
+}
+
+impl Reference {
+  pub fn new(kind : DimensionKind, objt_name : &str) -> Reference {
+    Reference{kind, name : objt_name.to_string()}
+  }
+}
+
+impl fmt::Display for Reference {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f,"{} {}",self.kind,self.name)
+  }
+}
+
+#[derive(Clone,PartialEq,Debug,Default)]
+pub struct DeleteReport {
+  pub deleted : Vec<Reference>,
+  pub updated : Vec<Reference>
+}
+
+impl fmt::Display for DeleteReport {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let deleted = self.deleted.iter().map(|r| r.to_string()).collect::<Vec<String>>().join(", ");
+    let updated = self.updated.iter().map(|r| r.to_string()).collect::<Vec<String>>().join(", ");
+    write!(f,"Deleted: {}, Updated: {}",deleted,updated)
+  }
+}
+
+fn push_unique(refs : &mut Vec<Reference>, r : Reference) {
+  if !refs.contains(&r) {
+    refs.push(r);
+  }
+}
+
+fn join_refs(refs : &[Reference]) -> String {
+  refs.iter().map(|r| r.to_string()).collect::<Vec<String>>().join(", ")
+}
+
+impl Model {
+  fn update_referrers(&mut self, f : &mut dyn FnMut(&str,&mut dyn Referencing)) -> Result<(),String> {
+    self.environments.update_each(|k,o| f(k,o))?;
+    self.roles.update_each(|k,o| f(k,o))?;
+    self.assets.update_each(|k,o| f(k,o))?;
+    self.attackers.update_each(|k,o| f(k,o))?;
+    self.vulnerabilities.update_each(|k,o| f(k,o))?;
+    self.threats.update_each(|k,o| f(k,o))?;
+    self.risks.update_each(|k,o| f(k,o))?;
+    self.value_types.update_each(|k,o| f(k,o))?;
+    self.use_cases.update_each(|k,o| f(k,o))?;
+    self.misuse_cases.update_each(|k,o| f(k,o))?;
+    self.countermeasures.update_each(|k,o| f(k,o))?;
+    self.responses.update_each(|k,o| f(k,o))?;
+    self.domain_properties.update_each(|k,o| f(k,o))?;
+    self.asset_associations.update_each(|k,o| f(k,o))?;
+    self.data_flows.update_each(|k,o| f(k,o))?;
+    self.trust_boundaries.update_each(|k,o| f(k,o))?;
+    Ok(())
+  }
+
+  fn contains_object(&self, kind : DimensionKind, objt_key : &str) -> bool {
+    match kind {
+      DimensionKind::Environment => self.environments.contains(objt_key),
+      DimensionKind::Role => self.roles.contains(objt_key),
+      DimensionKind::Asset => self.assets.contains(objt_key),
+      DimensionKind::Attacker => self.attackers.contains(objt_key),
+      DimensionKind::Vulnerability => self.vulnerabilities.contains(objt_key),
+      DimensionKind::Threat => self.threats.contains(objt_key),
+      DimensionKind::Risk => self.risks.contains(objt_key),
+      DimensionKind::ValueType => self.value_types.contains(objt_key),
+      DimensionKind::UseCase => self.use_cases.contains(objt_key),
+      DimensionKind::MisuseCase => self.misuse_cases.contains(objt_key),
+      DimensionKind::Countermeasure => self.countermeasures.contains(objt_key),
+      DimensionKind::Response => self.responses.contains(objt_key),
+      DimensionKind::DomainProperty => self.domain_properties.contains(objt_key),
+      DimensionKind::AssetAssociation => self.asset_associations.contains(objt_key),
+      DimensionKind::DataFlow => self.data_flows.contains(objt_key),
+      DimensionKind::TrustBoundary => self.trust_boundaries.contains(objt_key),
+      DimensionKind::Tag => true
+    }
+  }
+
+  fn remove_object(&mut self, kind : DimensionKind, objt_key : &str) -> bool {
+    match kind {
+      DimensionKind::Environment => self.environments.remove(objt_key).is_some(),
+      DimensionKind::Role => self.roles.remove(objt_key).is_some(),
+      DimensionKind::Asset => self.assets.remove(objt_key).is_some(),
+      DimensionKind::Attacker => self.attackers.remove(objt_key).is_some(),
+      DimensionKind::Vulnerability => self.vulnerabilities.remove(objt_key).is_some(),
+      DimensionKind::Threat => self.threats.remove(objt_key).is_some(),
+      DimensionKind::Risk => self.risks.remove(objt_key).is_some(),
+      DimensionKind::ValueType => self.value_types.remove(objt_key).is_some(),
+      DimensionKind::UseCase => self.use_cases.remove(objt_key).is_some(),
+      DimensionKind::MisuseCase => self.misuse_cases.remove(objt_key).is_some(),
+      DimensionKind::Countermeasure => self.countermeasures.remove(objt_key).is_some(),
+      DimensionKind::Response => self.responses.remove(objt_key).is_some(),
+      DimensionKind::DomainProperty => self.domain_properties.remove(objt_key).is_some(),
+      DimensionKind::AssetAssociation => self.asset_associations.remove(objt_key).is_some(),
+      DimensionKind::DataFlow => self.data_flows.remove(objt_key).is_some(),
+      DimensionKind::TrustBoundary => self.trust_boundaries.remove(objt_key).is_some(),
+      DimensionKind::Tag => true
+    }
+  }
+
+  fn rename_object(&mut self, kind : DimensionKind, objt_key : &str, new_name : &str) -> Result<String,String> {
+    match kind {
+      DimensionKind::Environment => self.environments.rename(objt_key,new_name),
+      DimensionKind::Role => self.roles.rename(objt_key,new_name),
+      DimensionKind::Asset => self.assets.rename(objt_key,new_name),
+      DimensionKind::Attacker => self.attackers.rename(objt_key,new_name),
+      DimensionKind::Vulnerability => self.vulnerabilities.rename(objt_key,new_name),
+      DimensionKind::Threat => self.threats.rename(objt_key,new_name),
+      DimensionKind::Risk => self.risks.rename(objt_key,new_name),
+      DimensionKind::ValueType => self.value_types.rename(objt_key,new_name),
+      DimensionKind::UseCase => self.use_cases.rename(objt_key,new_name),
+      DimensionKind::MisuseCase => self.misuse_cases.rename(objt_key,new_name),
+      DimensionKind::Countermeasure => self.countermeasures.rename(objt_key,new_name),
+      DimensionKind::Response => self.responses.rename(objt_key,new_name),
+      DimensionKind::DomainProperty => self.domain_properties.rename(objt_key,new_name),
+      DimensionKind::DataFlow => self.data_flows.rename(objt_key,new_name),
+      DimensionKind::TrustBoundary => self.trust_boundaries.rename(objt_key,new_name),
+      DimensionKind::AssetAssociation => Err(format!("Cannot rename {} {}",kind,objt_key)),
+      DimensionKind::Tag => Ok(new_name.to_string())
+    }
+  }
+
+  fn each_referrer(&self, f : &mut dyn FnMut(&str,&dyn Referencing)) {
+    self.environments.all().iter().for_each(|o| f(o.name(),o));
+    self.roles.all().iter().for_each(|o| f(o.name(),o));
+    self.assets.all().iter().for_each(|o| f(o.name(),o));
+    self.attackers.all().iter().for_each(|o| f(o.name(),o));
+    self.vulnerabilities.all().iter().for_each(|o| f(o.name(),o));
+    self.threats.all().iter().for_each(|o| f(o.name(),o));
+    self.risks.all().iter().for_each(|o| f(o.name(),o));
+    self.value_types.all().iter().for_each(|o| f(&value_type_key(&o.vt_type,&o.name),o));
+    self.use_cases.all().iter().for_each(|o| f(o.name(),o));
+    self.misuse_cases.all().iter().for_each(|o| f(o.name(),o));
+    self.countermeasures.all().iter().for_each(|o| f(o.name(),o));
+    self.responses.all().iter().for_each(|o| f(o.name(),o));
+    self.domain_properties.all().iter().for_each(|o| f(o.name(),o));
+    self.asset_associations.all().iter().for_each(|o| f(&asset_association_key(&o.environment,&o.head_asset,&o.tail_asset),o));
+    self.data_flows.all().iter().for_each(|o| f(o.name(),o));
+    self.trust_boundaries.all().iter().for_each(|o| f(o.name(),o));
+  }
+
+  pub fn referrers(&self, kind : DimensionKind, objt_key : &str) -> Vec<Reference> {
+    let mut refs = Vec::<Reference>::new();
+    self.each_referrer(&mut |k,o| {
+      if o.references(kind,objt_key) > 0 {
+        refs.push(Reference::new(o.kind(),k));
+      }
+    });
+    refs
+  }
+
+  pub fn rename(&mut self, kind : DimensionKind, objt_key : &str, new_name : &str) -> Result<Vec<Reference>,String> {
+    if new_name.is_empty() {
+      return Err(format!("Cannot rename {} {} to an empty name",kind,objt_key));
+    }
+    if kind == DimensionKind::ValueType && objt_key.split_once('/').is_none() {
+      return Err(format!("{} is not a value type key",objt_key));
+    }
+    let mut m = self.clone();
+    let new_key = m.rename_object(kind,objt_key,new_name)?;
+    let mut updated = Vec::<Reference>::new();
+    m.update_referrers(&mut |k,o| {
+      if o.update_references(kind,objt_key,Some(&new_key)) > 0 {
+        updated.push(Reference::new(o.kind(),k));
+      }
+    })?;
+    *self = m;
+    Ok(updated)
+  }
+
+  pub fn delete(&mut self, kind : DimensionKind, objt_key : &str, policy : DeletePolicy) -> Result<DeleteReport,String> {
+    if !self.contains_object(kind,objt_key) {
+      return Err(format!("Unknown {} {}",kind,objt_key));
+    }
+    let refs = self.referrers(kind,objt_key);
+    if policy == DeletePolicy::Restrict && !refs.is_empty() {
+      return Err(format!("Cannot delete {} {}: referred to by {}",kind,objt_key,join_refs(&refs)));
+    }
+    if policy == DeletePolicy::Nullify {
+      let mut required = Vec::<Reference>::new();
+      self.each_referrer(&mut |k,o| {
+        if o.requires(kind,objt_key) {
+          required.push(Reference::new(o.kind(),k));
+        }
+      });
+      if !required.is_empty() {
+        return Err(format!("Cannot nullify {} {}: required by {}",kind,objt_key,join_refs(&required)));
+      }
+    }
+
+    let mut m = self.clone();
+    let mut report = DeleteReport::default();
+    let mut pending = vec![Reference::new(kind,objt_key)];
+    while let Some(r) = pending.pop() {
+      if !m.remove_object(r.kind,&r.name) {
+        continue;
+      }
+      let mut orphans = Vec::<Reference>::new();
+      let mut updated = Vec::<Reference>::new();
+      m.update_referrers(&mut |k,o| {
+        if o.references(r.kind,&r.name) == 0 {
+          return;
+        }
+        if o.requires(r.kind,&r.name) {
+          orphans.push(Reference::new(o.kind(),k));
+          return;
+        }
+        let had_environments = !o.environment_names().is_empty();
+        o.update_references(r.kind,&r.name,None);
+        if policy == DeletePolicy::Cascade && r.kind == DimensionKind::Environment && o.defined_by_environments() && had_environments && o.environment_names().is_empty() {
+          orphans.push(Reference::new(o.kind(),k));
+        }
+        else {
+          updated.push(Reference::new(o.kind(),k));
+        }
+      })?;
+      push_unique(&mut report.deleted,r);
+      updated.into_iter().for_each(|u| push_unique(&mut report.updated,u));
+      pending.extend(orphans);
+    }
+    report.updated.retain(|u| !report.deleted.contains(u));
+    *self = m;
+    Ok(report)
+  }
+
+  pub fn delete_environment(&mut self, env_name : &str, policy : DeletePolicy) -> Result<DeleteReport,String> {
+    self.delete(DimensionKind::Environment,env_name,policy)
+  }
+
+  pub fn rename_value_type(&mut self, vt_type : &str, vt_name : &str, new_name : &str) -> Result<Vec<Reference>,String> {
+    self.rename(DimensionKind::ValueType,&value_type_key(vt_type,vt_name),new_name)
+  }
+
+  pub fn dangling_references(&self) -> Vec<String> {
+    self.validate().into_iter().filter(|e| e.contains(" refers to unknown ")).collect()
+  }
+}
+
+#[cfg(test)]
+use crate::model::test_model;
+
+#[test]
+fn test_rename_environment() {
+  let mut m = test_model();
+  let updated = m.rename(DimensionKind::Environment,"Day","Morning").unwrap();
+  assert_eq!(join_refs(&updated),"environment Complete, asset Data, asset Portal, attacker Carol, vulnerability Unvalidated input, threat SQL injection, use_case Upload data, misuse_case Exploit upload, countermeasure Input validation, response Insure upload, asset_association Day/Portal/Data, data_flow Upload, trust_boundary Server room");
+  assert!(m.environments.contains("Morning"));
+  assert!(!m.environments.contains("Day"));
+  assert_eq!(m.assets.get("Data").unwrap().environment_properties.get("Morning").unwrap().name,"Morning".to_string());
+  assert_eq!(m.environments.get("Complete").unwrap().environments.environments,vec!["Morning".to_string(),"Night".to_string()]);
+  assert!(m.validate().is_empty());
+  assert_eq!(m.rename(DimensionKind::Environment,"Morning","Night").err(),Some("Duplicate environment Night".to_string()));
+  assert_eq!(m.rename(DimensionKind::Environment,"Dusk","Evening").err(),Some("Unknown environment Dusk".to_string()));
+}
+
+#[test]
+fn test_rename_value_type() {
+  let mut m = test_model();
+  m.rename_value_type("threat_value","High","Very high").unwrap();
+  assert!(m.value_type("threat_value","Very high").is_some());
+  assert!(m.value_type("asset_value","High").is_some());
+}
+
+#[test]
+fn test_delete_restrict() {
+  let mut m = test_model();
+  assert_eq!(m.delete(DimensionKind::Asset,"Portal",DeletePolicy::Restrict).err(),Some("Cannot delete asset Portal: referred to by vulnerability Unvalidated input, asset_association Day/Portal/Data, data_flow Upload".to_string()));
+  assert!(m.assets.contains("Portal"));
+  assert_eq!(m.delete(DimensionKind::DomainProperty,"Users authenticate",DeletePolicy::Restrict).unwrap().deleted,vec![Reference::new(DimensionKind::DomainProperty,"Users authenticate")]);
+  assert_eq!(m.delete(DimensionKind::DomainProperty,"Users authenticate",DeletePolicy::Restrict).err(),Some("Unknown domain_property Users authenticate".to_string()));
+}
+
+#[test]
+fn test_delete_nullify() {
+  let mut m = test_model();
+  assert_eq!(m.delete(DimensionKind::Threat,"SQL injection",DeletePolicy::Nullify).err(),Some("Cannot nullify threat SQL injection: required by risk Upload risk".to_string()));
+  assert_eq!(m.delete(DimensionKind::Asset,"Data",DeletePolicy::Nullify).err(),Some("Cannot nullify asset Data: required by asset_association Day/Portal/Data".to_string()));
+  m.delete(DimensionKind::AssetAssociation,"Day/Portal/Data",DeletePolicy::Restrict).unwrap();
+  let report = m.delete(DimensionKind::Asset,"Data",DeletePolicy::Nullify).unwrap();
+  assert_eq!(join_refs(&report.updated),"vulnerability Unvalidated input, threat SQL injection, misuse_case Exploit upload, data_flow Upload, trust_boundary Server room");
+  assert_eq!(m.vulnerabilities.get("Unvalidated input").unwrap().environments[0].assets,vec!["Portal".to_string()]);
+  assert_eq!(m.delete_environment("Day",DeletePolicy::Nullify).err(),Some("Cannot nullify environment Day: required by data_flow Upload".to_string()));
+  m.delete(DimensionKind::DataFlow,"Upload",DeletePolicy::Restrict).unwrap();
+  let report = m.delete_environment("Day",DeletePolicy::Nullify).unwrap();
+  assert_eq!(report.deleted,vec![Reference::new(DimensionKind::Environment,"Day")]);
+  assert!(m.attackers.get("Carol").unwrap().environments.is_empty());
+  assert_eq!(m.environments.get("Complete").unwrap().environments.environments,vec!["Night".to_string()]);
+  assert!(m.dangling_references().is_empty());
+}
+
+#[test]
+fn test_delete_environment_cascade() {
+  let mut m = test_model();
+  assert!(m.delete_environment("Day",DeletePolicy::Restrict).is_err());
+  let report = m.delete_environment("Day",DeletePolicy::Cascade).unwrap();
+  assert_eq!(join_refs(&report.deleted),"environment Day, data_flow Upload, asset_association Day/Portal/Data, threat SQL injection, risk Upload risk, response Insure upload, misuse_case Exploit upload, vulnerability Unvalidated input, attacker Carol, asset Portal, asset Data");
+  assert_eq!(join_refs(&report.updated),"environment Complete, use_case Upload data, countermeasure Input validation, trust_boundary Server room");
+  assert!(m.assets.is_empty());
+  assert!(m.risks.is_empty());
+  assert_eq!(m.environments.keys(),vec!["Night".to_string(),"Complete".to_string()]);
+  assert!(m.validate().is_empty());
+}
+
+#[test]
+fn test_delete_value_type() {
+  let mut m = test_model();
+  m.value_types.add(crate::dimensions::valuetype::ValueTypeBuilder::new("Software","threat_type").environment("Night").build().unwrap()).unwrap();
+  assert_eq!(join_refs(&m.referrers(DimensionKind::ValueType,"threat_type/Software")),"threat SQL injection");
+  let report = m.delete(DimensionKind::ValueType,"threat_type/Software",DeletePolicy::Cascade).unwrap();
+  assert_eq!(join_refs(&report.deleted),"value_type threat_type/Software, threat SQL injection, risk Upload risk, response Insure upload, misuse_case Exploit upload");
+  assert!(m.delete(DimensionKind::Tag,"Legacy",DeletePolicy::Nullify).unwrap().updated.is_empty());
+}
+
+#[test]
+fn test_delete_value_type_environment() {
+  let mut m = test_model();
+  m.value_types.add(crate::dimensions::valuetype::ValueTypeBuilder::new("Software","threat_type").environment("Night").build().unwrap()).unwrap();
+  let report = m.delete_environment("Night",DeletePolicy::Cascade).unwrap();
+  assert_eq!(join_refs(&report.deleted),"environment Night");
+  assert_eq!(join_refs(&report.updated),"environment Complete, value_type threat_type/Software");
+  assert_eq!(m.value_types.get("threat_type/Software").unwrap().environment,"".to_string());
+  assert!(m.threats.contains("SQL injection"));
+  assert!(m.risks.contains("Upload risk"));
+  assert!(m.validate().is_empty());
+}
+
+#[test]
+fn test_rename_references() {
+  let mut m = test_model();
+  assert_eq!(join_refs(&m.rename(DimensionKind::Asset,"Data","Records").unwrap()),"vulnerability Unvalidated input, threat SQL injection, misuse_case Exploit upload, asset_association Day/Portal/Data, data_flow Upload, trust_boundary Server room");
+  assert!(m.asset_associations.contains("Day/Portal/Records"));
+  assert_eq!(m.misuse_cases.get("Exploit upload").unwrap().assets(),vec!["Records".to_string()]);
+  assert_eq!(join_refs(&m.rename(DimensionKind::Role,"Researcher","Scientist").unwrap()),"attacker Carol, use_case Upload data, countermeasure Input validation, response Insure upload");
+  assert_eq!(m.use_cases.get("Upload data").unwrap().actors,vec!["Scientist".to_string()]);
+  assert_eq!(join_refs(&m.rename(DimensionKind::UseCase,"Upload data","Share data").unwrap()),"data_flow Upload, trust_boundary Server room");
+  assert_eq!(m.data_flows.get("Upload").unwrap().target_name,"Share data".to_string());
+  assert_eq!(join_refs(&m.rename(DimensionKind::Risk,"Upload risk","Injection risk").unwrap()),"misuse_case Exploit upload, response Insure upload");
+  assert_eq!(join_refs(&m.rename(DimensionKind::Threat,"SQL injection","Injection").unwrap()),"risk Injection risk, countermeasure Input validation");
+  assert_eq!(m.risks.get("Injection risk").unwrap().threat,"Injection".to_string());
+  assert_eq!(m.attackers.get("Carol").unwrap().environments[0].roles,vec!["Scientist".to_string()]);
+  assert_eq!(m.rename(DimensionKind::AssetAssociation,"Day/Portal/Records","Server").err(),Some("Cannot rename asset_association Day/Portal/Records".to_string()));
+  assert!(m.validate().is_empty());
+}
+
+#[test]
+fn test_delete_references_in_all_dimensions() {
+  let mut m = test_model();
+  assert_eq!(m.delete(DimensionKind::Risk,"Upload risk",DeletePolicy::Nullify).err(),Some("Cannot nullify risk Upload risk: required by misuse_case Exploit upload, response Insure upload".to_string()));
+  let report = m.delete(DimensionKind::Asset,"Portal",DeletePolicy::Cascade).unwrap();
+  assert_eq!(join_refs(&report.deleted),"asset Portal, data_flow Upload, asset_association Day/Portal/Data");
+  assert_eq!(join_refs(&report.updated),"vulnerability Unvalidated input");
+  let report = m.delete(DimensionKind::Role,"Researcher",DeletePolicy::Nullify).unwrap();
+  assert_eq!(join_refs(&report.updated),"attacker Carol, use_case Upload data, countermeasure Input validation, response Insure upload");
+  m.delete(DimensionKind::UseCase,"Upload data",DeletePolicy::Nullify).unwrap();
+  assert_eq!(m.trust_boundaries.get("Server room").unwrap().environments[0].components.len(),1);
+  assert!(m.validate().is_empty());
+}
@@ -0,0 +1,169 @@
+use crate::report::{escape_html,escape_markdown_cell};
+
+#[derive(Clone,PartialEq,Debug)]
+pub enum Block {
+  Paragraph(String),
+  List(Vec<String>),
+  Table { headers : Vec<String>, rows : Vec<Vec<String>> }
+}
+
+#[derive(Clone,PartialEq,Debug)]
+pub struct Section {
+  pub title : String,
+  pub blocks : Vec<Block>
+}
+
+impl Section {
+  pub fn new(s_title : &str) -> Section {
+    Section { title: s_title.to_string(), blocks: Vec::<Block>::new() }
+  }
+}
+
+#[derive(Clone,PartialEq,Debug)]
+pub struct Chapter {
+  pub title : String,
+  pub blocks : Vec<Block>,
+  pub sections : Vec<Section>
+}
+
+impl Chapter {
+  pub fn new(c_title : &str) -> Chapter {
+    Chapter { title: c_title.to_string(), blocks: Vec::<Block>::new(), sections: Vec::<Section>::new() }
+  }
+}
+
+#[derive(Clone,PartialEq,Debug)]
+pub struct Document {
+  pub title : String,
+  pub chapters : Vec<Chapter>
+}
+
+fn block_markdown(b : &Block) -> String {
+  match b {
+    Block::Paragraph(p) => format!("{}\n\n",p),
+    Block::List(items) => format!("{}\n",items.iter().map(|i| format!("- {}\n",i)).collect::<String>()),
+    Block::Table { headers, rows } => {
+      let mut md = format!("| {} |\n|{}\n",headers.iter().map(|h| escape_markdown_cell(h)).collect::<Vec<String>>().join(" | ")," --- |".repeat(headers.len()));
+      for row in rows {
+        md.push_str(format!("| {} |\n",row.iter().map(|c| escape_markdown_cell(c)).collect::<Vec<String>>().join(" | ")).as_str());
+      }
+      md.push('\n');
+      md
+    }
+  }
+}
+
+fn block_html(b : &Block) -> String {
+  match b {
+    Block::Paragraph(p) => format!("<p>{}</p>\n",escape_html(p)),
+    Block::List(items) => format!("<ul>\n{}</ul>\n",items.iter().map(|i| format!("<li>{}</li>\n",escape_html(i))).collect::<String>()),
+    Block::Table { headers, rows } => {
+      let mut html = format!("<table>\n<tr>{}</tr>\n",headers.iter().map(|h| format!("<th>{}</th>",escape_html(h))).collect::<String>());
+      for row in rows {
+        html.push_str(format!("<tr>{}</tr>\n",row.iter().map(|c| format!("<td>{}</td>",escape_html(c))).collect::<String>()).as_str());
+      }
+      html.push_str("</table>\n");
+      html
+    }
+  }
+}
+
+fn block_docbook(b : &Block) -> String {
+  match b {
+    Block::Paragraph(p) => format!("<para>{}</para>\n",escape_html(p)),
+    Block::List(items) => format!("<itemizedlist>\n{}</itemizedlist>\n",items.iter().map(|i| format!("<listitem><para>{}</para></listitem>\n",escape_html(i))).collect::<String>()),
+    Block::Table { headers, rows } => {
+      let mut xml = format!("<informaltable>\n<tgroup cols=\"{}\">\n<thead>\n<row>{}</row>\n</thead>\n<tbody>\n",headers.len(),headers.iter().map(|h| format!("<entry>{}</entry>",escape_html(h))).collect::<String>());
+      for row in rows {
+        xml.push_str(format!("<row>{}</row>\n",row.iter().map(|c| format!("<entry>{}</entry>",escape_html(c))).collect::<String>()).as_str());
+      }
+      xml.push_str("</tbody>\n</tgroup>\n</informaltable>\n");
+      xml
+    }
+  }
+}
+
+impl Document {
+  pub fn new(d_title : &str) -> Document {
+    Document { title: d_title.to_string(), chapters: Vec::<Chapter>::new() }
+  }
+
+  pub fn to_markdown(&self) -> String {
+    let mut md = format!("# {}\n\n",self.title);
+    for c in &self.chapters {
+      md.push_str(format!("## {}\n\n",c.title).as_str());
+      for b in &c.blocks {
+        md.push_str(&block_markdown(b));
+      }
+      for s in &c.sections {
+        md.push_str(format!("### {}\n\n",s.title).as_str());
+        for b in &s.blocks {
+          md.push_str(&block_markdown(b));
+        }
+      }
+    }
+    md
+  }
+
+  pub fn to_html(&self) -> String {
+    let mut html = format!("<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{0}</title></head>\n<body>\n<h1>{0}</h1>\n",escape_html(&self.title));
+    for c in &self.chapters {
+      html.push_str(format!("<h2>{}</h2>\n",escape_html(&c.title)).as_str());
+      for b in &c.blocks {
+        html.push_str(&block_html(b));
+      }
+      for s in &c.sections {
+        html.push_str(format!("<h3>{}</h3>\n",escape_html(&s.title)).as_str());
+        for b in &s.blocks {
+          html.push_str(&block_html(b));
+        }
+      }
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+  }
+
+  pub fn to_docbook(&self) -> String {
+    let mut xml = format!("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<book xmlns=\"http://docbook.org/ns/docbook\" version=\"5.0\">\n<title>{}</title>\n",escape_html(&self.title));
+    for c in &self.chapters {
+      xml.push_str(format!("<chapter>\n<title>{}</title>\n",escape_html(&c.title)).as_str());
+      for b in &c.blocks {
+        xml.push_str(&block_docbook(b));
+      }
+      for s in &c.sections {
+        xml.push_str(format!("<section>\n<title>{}</title>\n",escape_html(&s.title)).as_str());
+        for b in &s.blocks {
+          xml.push_str(&block_docbook(b));
+        }
+        xml.push_str("</section>\n");
+      }
+      xml.push_str("</chapter>\n");
+    }
+    xml.push_str("</book>\n");
+    xml
+  }
+}
+
+#[test]
+fn test_document_formats() {
+  let mut d = Document::new("Spec");
+  let mut c = Chapter::new("Roles");
+  c.blocks.push(Block::Paragraph("People & machines".to_string()));
+  let mut s = Section::new("Researcher");
+  s.blocks.push(Block::List(vec!["Short code: RES".to_string()]));
+  s.blocks.push(Block::Table { headers: vec!["Name".to_string(),"Value".to_string()], rows: vec![vec!["a|b".to_string(),"<c>".to_string()]] });
+  c.sections.push(s);
+  d.chapters.push(c);
+
+  assert_eq!(d.to_markdown(),"# Spec\n\n## Roles\n\nPeople & machines\n\n### Researcher\n\n- Short code: RES\n\n| Name | Value |\n| --- | --- |\n| a\\|b | <c> |\n\n".to_string());
+
+  let html = d.to_html();
+  assert!(html.contains("<h2>Roles</h2>\n<p>People &amp; machines</p>\n<h3>Researcher</h3>\n<ul>\n<li>Short code: RES</li>\n</ul>\n"));
+  assert!(html.contains("<tr><td>a|b</td><td>&lt;c&gt;</td></tr>\n"));
+
+  let xml = d.to_docbook();
+  assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<book"));
+  assert!(xml.contains("<chapter>\n<title>Roles</title>\n<para>People &amp; machines</para>\n<section>\n<title>Researcher</title>\n"));
+  assert!(xml.contains("<tgroup cols=\"2\">"));
+  assert!(xml.ends_with("</section>\n</chapter>\n</book>\n"));
+}
@@ -0,0 +1,227 @@
+use crate::analysis::linddun::{is_privacy_property,linddun_threats,PrivacyThreat};
+use crate::dimensions::asset::Asset;
+use crate::dimensions::countermeasure::Countermeasure;
+use crate::dimensions::environment::Environment;
+use crate::dimensions::role::{Role,RoleType};
+use crate::dimensions::securityproperty::{SecurityPropertyValue,QualitativeValue};
+use crate::dimensions::threat::Threat;
+use crate::report::{escape_html,escape_markdown_cell};
+
+#[derive(Clone)]
+pub struct DpiaAsset {
+  pub name : String,
+  pub description : String,
+  pub necessity : String,
+  pub properties : Vec<SecurityPropertyValue>
+}
+
+#[derive(Clone)]
+pub struct DpiaRisk {
+  pub threat : PrivacyThreat,
+  pub mitigations : Vec<String>
+}
+
+#[derive(Clone)]
+pub struct DpiaEnvironment {
+  pub name : String,
+  pub assets : Vec<DpiaAsset>,
+  pub risks : Vec<DpiaRisk>
+}
+
+#[derive(Clone)]
+pub struct Dpia {
+  pub project : String,
+  pub data_subjects : Vec<String>,
+  pub data_controllers : Vec<String>,
+  pub data_processors : Vec<String>,
+  pub environments : Vec<DpiaEnvironment>
+}
+
+fn role_names(roles : &[Role], rt : RoleType) -> Vec<String> {
+  roles.iter().filter(|r| *r.role_type() == rt).map(|r| r.name().to_string()).collect()
+}
+
+fn mitigates(cm : &Countermeasure, env_name : &str, t : &PrivacyThreat, threats : &[Threat]) -> bool {
+  cm.environments.iter().filter(|ce| ce.name() == env_name).any(|ce| {
+    ce.properties.iter().any(|p| p.name == t.property && p.value != QualitativeValue::None) &&
+    ce.targets.iter().any(|tgt| threats.iter().any(|th| th.name() == tgt.name && th.environments.iter().any(|te| te.name() == env_name && te.assets.contains(&t.asset))))
+  })
+}
+
+impl Dpia {
+  pub fn new(proj_name : &str, envs : &[Environment], roles : &[Role], assets : &[Asset], threats : &[Threat], cms : &[Countermeasure]) -> Dpia {
+    let mut dpia = Dpia {
+      project: proj_name.to_string(),
+      data_subjects: role_names(roles,RoleType::DataSubject),
+      data_controllers: role_names(roles,RoleType::DataController),
+      data_processors: role_names(roles,RoleType::DataProcessor),
+      environments: Vec::<DpiaEnvironment>::new()
+    };
+    let privacy_threats = linddun_threats(assets,roles);
+
+    for env in envs {
+      let mut de = DpiaEnvironment { name: env.name.clone(), assets: Vec::<DpiaAsset>::new(), risks: Vec::<DpiaRisk>::new() };
+      for a in assets {
+        if let Some(aep) = a.environment_properties.get(&env.name) {
+          let props : Vec<SecurityPropertyValue> = aep.properties().iter().filter(|p| is_privacy_property(&p.name) && p.value != QualitativeValue::None).cloned().collect();
+          if !props.is_empty() {
+            de.assets.push(DpiaAsset { name: a.name().to_string(), description: a.description.clone(), necessity: a.significance.clone(), properties: props });
+          }
+        }
+      }
+      if de.assets.is_empty() {
+        continue;
+      }
+      for t in privacy_threats.iter().filter(|t| t.environment == env.name && de.assets.iter().any(|a| a.name == t.asset)) {
+        let mitigations : Vec<String> = cms.iter().filter(|cm| mitigates(cm,&env.name,t,threats)).map(|cm| cm.name().to_string()).collect();
+        de.risks.push(DpiaRisk { threat: t.clone(), mitigations });
+      }
+      dpia.environments.push(de);
+    }
+    dpia
+  }
+
+  pub fn to_markdown(&self) -> String {
+    let mut md = format!("# Data Protection Impact Assessment: {}\n\n",self.project);
+    md.push_str("## Roles\n\n");
+    for (title,names) in [("Data subjects",&self.data_subjects),("Data controllers",&self.data_controllers),("Data processors",&self.data_processors)] {
+      md.push_str(format!("### {}\n\n",title).as_str());
+      if names.is_empty() {
+        md.push_str("None identified.\n\n");
+      }
+      else {
+        for n in names {
+          md.push_str(format!("- {}\n",n).as_str());
+        }
+        md.push('\n');
+      }
+    }
+    for de in &self.environments {
+      md.push_str(format!("## Environment: {}\n\n",de.name).as_str());
+      md.push_str("### Description of processing\n\n| Asset | Description | Privacy properties |\n| --- | --- | --- |\n");
+      for a in &de.assets {
+        let props = a.properties.iter().map(|p| format!("{:?}: {:?}",p.name,p.value)).collect::<Vec<String>>().join(", ");
+        md.push_str(format!("| {} | {} | {} |\n",escape_markdown_cell(&a.name),escape_markdown_cell(&a.description),props).as_str());
+      }
+      md.push_str("\n### Necessity and proportionality\n\n");
+      for a in &de.assets {
+        md.push_str(format!("- **{}**: {}\n",a.name,a.necessity).as_str());
+      }
+      md.push_str("\n### Risks and mitigations\n\n| Risk | Category | Asset | Property | Roles | Mitigations |\n| --- | --- | --- | --- | --- | --- |\n");
+      for r in &de.risks {
+        let t = &r.threat;
+        md.push_str(format!("| {} | {} | {} | {:?} ({:?}) | {} | {} |\n",escape_markdown_cell(&t.name),t.category,escape_markdown_cell(&t.asset),t.property,t.value,escape_markdown_cell(&t.roles.join(", ")),if r.mitigations.is_empty() { "None".to_string() } else { escape_markdown_cell(&r.mitigations.join(", ")) }).as_str());
+      }
+      md.push('\n');
+    }
+    md
+  }
+
+  pub fn to_html(&self) -> String {
+    let mut html = format!("<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Data Protection Impact Assessment: {0}</title></head>\n<body>\n<h1>Data Protection Impact Assessment: {0}</h1>\n",escape_html(&self.project));
+    html.push_str("<h2>Roles</h2>\n");
+    for (title,names) in [("Data subjects",&self.data_subjects),("Data controllers",&self.data_controllers),("Data processors",&self.data_processors)] {
+      html.push_str(format!("<h3>{}</h3>\n",title).as_str());
+      if names.is_empty() {
+        html.push_str("<p>None identified.</p>\n");
+      }
+      else {
+        html.push_str("<ul>\n");
+        for n in names {
+          html.push_str(format!("<li>{}</li>\n",escape_html(n)).as_str());
+        }
+        html.push_str("</ul>\n");
+      }
+    }
+    for de in &self.environments {
+      html.push_str(format!("<h2>Environment: {}</h2>\n",escape_html(&de.name)).as_str());
+      html.push_str("<h3>Description of processing</h3>\n<table>\n<tr><th>Asset</th><th>Description</th><th>Privacy properties</th></tr>\n");
+      for a in &de.assets {
+        let props = a.properties.iter().map(|p| format!("{:?}: {:?}",p.name,p.value)).collect::<Vec<String>>().join(", ");
+        html.push_str(format!("<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",escape_html(&a.name),escape_html(&a.description),props).as_str());
+      }
+      html.push_str("</table>\n<h3>Necessity and proportionality</h3>\n<ul>\n");
+      for a in &de.assets {
+        html.push_str(format!("<li><b>{}</b>: {}</li>\n",escape_html(&a.name),escape_html(&a.necessity)).as_str());
+      }
+      html.push_str("</ul>\n<h3>Risks and mitigations</h3>\n<table>\n<tr><th>Risk</th><th>Category</th><th>Asset</th><th>Property</th><th>Roles</th><th>Mitigations</th></tr>\n");
+      for r in &de.risks {
+        let t = &r.threat;
+        html.push_str(format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{:?} ({:?})</td><td>{}</td><td>{}</td></tr>\n",escape_html(&t.name),t.category,escape_html(&t.asset),t.property,t.value,escape_html(&t.roles.join(", ")),if r.mitigations.is_empty() { "None".to_string() } else { escape_html(&r.mitigations.join(", ")) }).as_str());
+      }
+      html.push_str("</table>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+  }
+}
+
+#[test]
+fn test_new_dpia() {
+  use crate::dimensions::countermeasure::{CountermeasureEnvironment,CountermeasureTarget};
+  use crate::dimensions::securityproperty::SecurityProperty;
+  use crate::dimensions::threat::ThreatEnvironment;
+
+  let mut records = Asset::new("Patient records","PR","Information",true);
+  records.description = "Clinical notes".to_string();
+  records.significance = "Needed for treatment".to_string();
+  records.add_environment("Day");
+  records.update_security_property("Day","unlinkability","High","TBC");
+  records.update_security_property("Day","confidentiality","High","TBC");
+  let mut server = Asset::new("Server","SRV","Hardware",false);
+  server.add_environment("Day");
+  server.update_security_property("Day","availability","High","TBC");
+  let roles = [
+    Role::new("Patient","Data Subject","PAT","A patient"),
+    Role::new("Hospital","Data Controller","HOS","The hospital")
+  ];
+  let mut linkage = Threat::new("Record linkage","Privacy");
+  let mut te = ThreatEnvironment::new("Day","Probable");
+  te.assets.push("Patient records".to_string());
+  linkage.environments.push(te);
+  let mut cm = Countermeasure::new("Record pseudonymisation","Software");
+  let mut ce = CountermeasureEnvironment::new("Day");
+  ce.targets.push(CountermeasureTarget::new("Record linkage","High","TBC"));
+  ce.properties.push(SecurityPropertyValue::new("unlinkability","High","TBC"));
+  cm.environments.push(ce);
+  let mut named = Countermeasure::new("Named after threat","Software");
+  let mut ce = CountermeasureEnvironment::new("Day");
+  ce.targets.push(CountermeasureTarget::new("Linkability of Patient records","High","TBC"));
+  ce.properties.push(SecurityPropertyValue::new("unlinkability","High","TBC"));
+  named.environments.push(ce);
+  let dpia = Dpia::new("NeuroGrid",&[Environment::new("Day","DAY"),Environment::new("Night","NGT")],&roles,&[records,server],&[linkage],&[cm,named]);
+  assert_eq!(dpia.data_subjects,vec!["Patient".to_string()]);
+  assert_eq!(dpia.data_controllers,vec!["Hospital".to_string()]);
+  assert_eq!(dpia.data_processors.len(),0);
+  assert_eq!(dpia.environments.len(),1);
+  let de = &dpia.environments[0];
+  assert_eq!(de.assets.len(),1);
+  assert_eq!(de.assets[0].name,"Patient records".to_string());
+  assert_eq!(de.assets[0].necessity,"Needed for treatment".to_string());
+  assert_eq!(de.assets[0].properties.len(),1);
+  assert_eq!(de.assets[0].properties[0].name,SecurityProperty::Unlinkability);
+  assert_eq!(de.risks.len(),2);
+  assert_eq!(de.risks[0].threat.name,"Linkability of Patient records".to_string());
+  assert_eq!(de.risks[0].mitigations,vec!["Record pseudonymisation".to_string()]);
+  assert_eq!(de.risks[1].mitigations.len(),0);
+
+  let md = dpia.to_markdown();
+  assert!(md.starts_with("# Data Protection Impact Assessment: NeuroGrid\n"));
+  assert!(md.contains("## Environment: Day\n"));
+  assert!(!md.contains("## Environment: Night\n"));
+  assert!(md.contains("| Patient records | Clinical notes | Unlinkability: High |\n"));
+  assert!(md.contains("- **Patient records**: Needed for treatment\n"));
+  assert!(md.contains("| Linkability of Patient records | Linkability | Patient records | Unlinkability (High) | Patient | Record pseudonymisation |\n"));
+
+  let html = dpia.to_html();
+  assert!(html.starts_with("<!DOCTYPE html>"));
+  assert!(html.contains("<h2>Environment: Day</h2>"));
+  assert!(html.contains("<tr><td>Patient records</td><td>Clinical notes</td><td>Unlinkability: High</td></tr>"));
+  assert!(html.ends_with("</html>\n"));
+
+  let mut dpia = dpia;
+  let risk = &mut dpia.environments[0].risks[0];
+  risk.threat.roles = vec!["Patient|Carer".to_string()];
+  risk.mitigations = vec!["Audit|Logging".to_string()];
+  assert!(dpia.to_markdown().contains("| Unlinkability (High) | Patient\\|Carer | Audit\\|Logging |\n"));
+}
@@ -0,0 +1,21 @@
+pub mod dpia;
+pub mod document;
+pub mod specification;
+
+pub fn escape_html(txt : &str) -> String {
+  txt.replace('&',"&amp;").replace('<',"&lt;").replace('>',"&gt;").replace('"',"&quot;")
+}
+
+pub fn escape_markdown_cell(txt : &str) -> String {
+  txt.replace('|',"\\|").replace('\n'," ")
+}
+
+#[test]
+fn test_escape_html() {
+  assert_eq!(escape_html("<b>R&D</b> \"x\""),"&lt;b&gt;R&amp;D&lt;/b&gt; &quot;x&quot;".to_string());
+}
+
+#[test]
+fn test_escape_markdown_cell() {
+  assert_eq!(escape_markdown_cell("a|b\nc"),"a\\|b c".to_string());
+}
@@ -0,0 +1,222 @@
+use crate::dimensions::asset::Asset;
+use crate::dimensions::attacker::Attacker;
+use crate::dimensions::environment::Environment;
+use crate::dimensions::projectsettings::ProjectSettings;
+use crate::dimensions::role::Role;
+use crate::dimensions::valuetype::ValueType;
+use crate::dimensions::vulnerability::Vulnerability;
+use crate::report::document::{Block,Chapter,Document,Section};
+
+pub struct SpecificationBuilder<'a> {
+  settings : &'a ProjectSettings,
+  environments : &'a [Environment],
+  roles : &'a [Role],
+  assets : &'a [Asset],
+  attackers : &'a [Attacker],
+  vulnerabilities : &'a [Vulnerability],
+  value_types : &'a [ValueType]
+}
+
+fn table(headers : &[&str], rows : Vec<Vec<String>>) -> Block {
+  Block::Table { headers: headers.iter().map(|h| h.to_string()).collect(), rows }
+}
+
+impl<'a> SpecificationBuilder<'a> {
+  pub fn new(ps : &'a ProjectSettings) -> SpecificationBuilder<'a> {
+    SpecificationBuilder { settings: ps, environments: &[], roles: &[], assets: &[], attackers: &[], vulnerabilities: &[], value_types: &[] }
+  }
+
+  pub fn environments(mut self, envs : &'a [Environment]) -> SpecificationBuilder<'a> {
+    self.environments = envs;
+    self
+  }
+
+  pub fn roles(mut self, roles : &'a [Role]) -> SpecificationBuilder<'a> {
+    self.roles = roles;
+    self
+  }
+
+  pub fn assets(mut self, assets : &'a [Asset]) -> SpecificationBuilder<'a> {
+    self.assets = assets;
+    self
+  }
+
+  pub fn attackers(mut self, attackers : &'a [Attacker]) -> SpecificationBuilder<'a> {
+    self.attackers = attackers;
+    self
+  }
+
+  pub fn vulnerabilities(mut self, vuls : &'a [Vulnerability]) -> SpecificationBuilder<'a> {
+    self.vulnerabilities = vuls;
+    self
+  }
+
+  pub fn value_types(mut self, vts : &'a [ValueType]) -> SpecificationBuilder<'a> {
+    self.value_types = vts;
+    self
+  }
+
+  fn project_chapters(&self, doc : &mut Document) {
+    let ps = self.settings;
+    let mut intro = Chapter::new("Introduction");
+    for (title,txt) in [("Background",&ps.background),("Strategic goals",&ps.strategic_goals),("Scope",&ps.scope)] {
+      let mut s = Section::new(title);
+      s.blocks.push(Block::Paragraph(txt.clone()));
+      intro.sections.push(s);
+    }
+    doc.chapters.push(intro);
+
+    let mut ncs : Vec<(&String,&String)> = ps.naming_conventions.iter().collect();
+    ncs.sort();
+    let mut nc_chapter = Chapter::new("Naming conventions");
+    nc_chapter.blocks.push(table(&["Name","Definition"],ncs.into_iter().map(|(n,v)| vec![n.clone(),v.clone()]).collect()));
+    doc.chapters.push(nc_chapter);
+
+    let mut conts = Chapter::new("Contributors");
+    conts.blocks.push(table(&["Firstname","Surname","Affiliation","Role"],ps.contributors.iter().map(|c| vec![c.0.clone(),c.1.clone(),c.2.clone(),c.3.clone()]).collect()));
+    doc.chapters.push(conts);
+
+    let mut revs = Chapter::new("Revisions");
+    revs.blocks.push(table(&["Revision","Date","Remarks"],ps.revisions.iter().map(|r| vec![r.0.clone(),r.1.clone(),r.2.clone()]).collect()));
+    doc.chapters.push(revs);
+  }
+
+  fn environment_chapter(&self) -> Chapter {
+    let mut c = Chapter::new("Environments");
+    for env in self.environments {
+      let mut s = Section::new(&env.name);
+      s.blocks.push(Block::Paragraph(env.definition.clone()));
+      let mut details = vec![format!("Short code: {}",env.short_code)];
+      if !env.environments.is_empty() {
+        details.push(format!("Composite of: {}",env.environments.environments.join(", ")));
+        details.push(format!("Duplication property: {}",env.environments.property));
+        if !env.environments.overriding_environment_name.is_empty() {
+          details.push(format!("Overriding environment: {}",env.environments.overriding_environment_name));
+        }
+      }
+      s.blocks.push(Block::List(details));
+      c.sections.push(s);
+    }
+    c
+  }
+
+  fn role_chapter(&self) -> Chapter {
+    let mut c = Chapter::new("Roles");
+    c.blocks.push(table(&["Name","Short code","Type","Description"],self.roles.iter().map(|r| vec![r.name().to_string(),r.short_code().to_string(),r.role_type().to_string(),r.description.clone()]).collect()));
+    c
+  }
+
+  fn asset_chapter(&self) -> Chapter {
+    let mut c = Chapter::new("Assets");
+    for a in self.assets {
+      let mut s = Section::new(a.name());
+      s.blocks.push(Block::Paragraph(a.description.clone()));
+      let tags = a.tags.iter().map(|t| t.name.clone()).collect::<Vec<String>>().join(", ");
+      s.blocks.push(Block::List(vec![
+        format!("Short code: {}",a.short_code()),
+        format!("Type: {}",a.asset_type()),
+        format!("Critical: {}",if a.is_critical() { "Yes" } else { "No" }),
+        format!("Significance: {}",a.significance),
+        format!("Tags: {}",tags)
+      ]));
+      let mut env_names : Vec<&String> = a.environment_properties.keys().collect();
+      env_names.sort();
+      for env_name in env_names {
+        s.blocks.push(Block::Paragraph(format!("Security properties in {}",env_name)));
+        s.blocks.push(table(&["Property","Value","Rationale"],a.environment_properties[env_name].properties().iter().map(|p| vec![format!("{:?}",p.name),format!("{:?}",p.value),p.rationale.clone()]).collect()));
+      }
+      c.sections.push(s);
+    }
+    c
+  }
+
+  fn attacker_chapter(&self) -> Chapter {
+    let mut c = Chapter::new("Attackers");
+    for a in self.attackers {
+      let mut s = Section::new(a.name());
+      s.blocks.push(Block::Paragraph(a.description.clone()));
+      s.blocks.push(table(&["Environment","Roles","Motivations","Capabilities"],a.environments.iter().map(|ae| vec![ae.name().to_string(),ae.roles.join(", "),ae.motivations.join(", "),ae.capabilities.join(", ")]).collect()));
+      c.sections.push(s);
+    }
+    c
+  }
+
+  fn vulnerability_chapter(&self) -> Chapter {
+    let mut c = Chapter::new("Vulnerabilities");
+    for v in self.vulnerabilities {
+      let mut s = Section::new(v.name());
+      s.blocks.push(Block::Paragraph(v.description.clone()));
+      s.blocks.push(Block::List(vec![format!("Type: {}",v.vulnerability_type())]));
+      s.blocks.push(table(&["Environment","Severity","Assets"],v.environments.iter().map(|ve| vec![ve.name().to_string(),ve.severity().to_string(),ve.assets.join(", ")]).collect()));
+      c.sections.push(s);
+    }
+    c
+  }
+
+  fn value_type_chapter(&self) -> Chapter {
+    let mut c = Chapter::new("Value types");
+    c.blocks.push(table(&["Name","Type","Description","Score","Rationale"],self.value_types.iter().map(|vt| vec![vt.name.clone(),vt.vt_type.clone(),vt.description.clone(),vt.score.to_string(),vt.rationale.clone()]).collect()));
+    c
+  }
+
+  pub fn build(&self) -> Document {
+    let mut doc = Document::new(&format!("{} Requirements Specification",self.settings.name));
+    self.project_chapters(&mut doc);
+    if !self.environments.is_empty() {
+      doc.chapters.push(self.environment_chapter());
+    }
+    if !self.roles.is_empty() {
+      doc.chapters.push(self.role_chapter());
+    }
+    if !self.assets.is_empty() {
+      doc.chapters.push(self.asset_chapter());
+    }
+    if !self.attackers.is_empty() {
+      doc.chapters.push(self.attacker_chapter());
+    }
+    if !self.vulnerabilities.is_empty() {
+      doc.chapters.push(self.vulnerability_chapter());
+    }
+    if !self.value_types.is_empty() {
+      doc.chapters.push(self.value_type_chapter());
+    }
+    doc
+  }
+}
+
+#[test]
+fn test_specification_builder() {
+  use crate::dimensions::vulnerability::VulnerabilityEnvironment;
+
+  let mut ps = ProjectSettings::new("NeuroGrid");
+  ps.background = "Clinical research".to_string();
+  ps.naming_conventions.insert("PI".to_string(),"Principal investigator".to_string());
+  ps.contributors.push(("Shamal".to_string(),"Faily".to_string(),"Bournemouth".to_string(),"Lead".to_string()));
+  ps.revisions.push(("1".to_string(),"2024-01-01".to_string(),"First draft".to_string()));
+  let envs = [Environment::new("Day","DAY")];
+  let roles = [Role::new("Researcher","Stakeholder","RES","A researcher")];
+  let mut data = Asset::new("Data","DAT","Information",true);
+  data.add_environment("Day");
+  data.update_security_property("Day","confidentiality","High","Sensitive");
+  let mut vul = Vulnerability::new("Unvalidated input","Implementation");
+  let mut ve = VulnerabilityEnvironment::new("Day","Critical");
+  ve.assets.push("Data".to_string());
+  vul.environments.push(ve);
+  let assets = [data];
+  let vuls = [vul];
+
+  let doc = SpecificationBuilder::new(&ps).environments(&envs).roles(&roles).assets(&assets).vulnerabilities(&vuls).build();
+  assert_eq!(doc.title,"NeuroGrid Requirements Specification".to_string());
+  let titles = doc.chapters.iter().map(|c| c.title.clone()).collect::<Vec<String>>();
+  assert_eq!(titles,vec!["Introduction","Naming conventions","Contributors","Revisions","Environments","Roles","Assets","Vulnerabilities"]);
+  assert_eq!(doc.chapters[0].sections[0].blocks[0],Block::Paragraph("Clinical research".to_string()));
+  assert_eq!(doc.chapters[5].blocks[0],Block::Table { headers: vec!["Name".to_string(),"Short code".to_string(),"Type".to_string(),"Description".to_string()], rows: vec![vec!["Researcher".to_string(),"RES".to_string(),"Stakeholder".to_string(),"A researcher".to_string()]] });
+
+  let md = doc.to_markdown();
+  assert!(md.contains("| PI | Principal investigator |\n"));
+  assert!(md.contains("### Data\n"));
+  assert!(md.contains("| Confidentiality | High | Sensitive |\n"));
+  assert!(md.contains("| Day | Critical | Data |\n"));
+  assert!(doc.to_html().contains("<td>Shamal</td><td>Faily</td>"));
+  assert!(doc.to_docbook().contains("<section>\n<title>Unvalidated input</title>\n"));
+}
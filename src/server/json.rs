@@ -0,0 +1,120 @@
+use serde_json::{json,Value};
+use crate::dimensions::asset::Asset;
+use crate::dimensions::dimension::Identified;
+use crate::dimensions::environment::Environment;
+use crate::dimensions::objectid::id_value;
+use crate::dimensions::projectsettings::ProjectSettings;
+use crate::dimensions::tag::Tag;
+use crate::dimensions::valuetype::ValueType;
+
+fn tags_json(tags : &[Tag]) -> Value {
+  json!(tags.iter().map(|t| t.name.clone()).collect::<Vec<String>>())
+}
+
+pub fn asset_json(a : &Asset) -> Value {
+  let mut env_names = a.environment_properties.keys().cloned().collect::<Vec<String>>();
+  env_names.sort();
+  let env_props = env_names.iter().map(|env_name| {
+    let props = a.environment_properties[env_name].properties().iter().map(|p| json!({
+      "name": format!("{:?}",p.name),
+      "value": format!("{:?}",p.value),
+      "rationale": p.rationale
+    })).collect::<Vec<Value>>();
+    json!({
+      "theEnvironmentName": env_name,
+      "theProperties": props,
+      "theAssociations": []
+    })
+  }).collect::<Vec<Value>>();
+  json!({
+    "theId": id_value(a.id()),
+    "theName": a.name(),
+    "theShortCode": a.short_code(),
+    "theDescription": a.description,
+    "theSignificance": a.significance,
+    "theType": a.asset_type(),
+    "isCritical": a.is_critical(),
+    "theCriticalRationale": a.critical_rationale,
+    "theTags": tags_json(&a.tags),
+    "theInterfaces": [],
+    "theEnvironmentProperties": env_props
+  })
+}
+
+pub fn environment_json(env : &Environment) -> Value {
+  let composite = !env.environments.is_empty();
+  json!({
+    "theId": id_value(env.id),
+    "theName": env.name,
+    "theShortCode": env.short_code,
+    "theDescription": env.definition,
+    "theEnvironments": env.environments.environments,
+    "theDuplicateProperty": if composite { env.environments.property.to_string() } else { "None".to_string() },
+    "theOverridingEnvironment": env.environments.overriding_environment_name,
+    "theTensions": []
+  })
+}
+
+pub fn value_type_json(vt : &ValueType) -> Value {
+  json!({
+    "theId": id_value(vt.id),
+    "theName": vt.name,
+    "theDescription": vt.description,
+    "theType": vt.vt_type,
+    "theEnvironmentName": vt.environment,
+    "theScore": vt.score,
+    "theRationale": vt.rationale
+  })
+}
+
+pub fn settings_json(ps : &ProjectSettings) -> Value {
+  let mut names = ps.naming_conventions.keys().cloned().collect::<Vec<String>>();
+  names.sort();
+  json!({
+    "projectName": ps.name,
+    "richPicture": ps.rich_picture,
+    "projectScope": ps.scope,
+    "definitions": names.iter().map(|n| json!({"name": n, "value": ps.naming_conventions[n]})).collect::<Vec<Value>>(),
+    "projectGoals": ps.strategic_goals,
+    "contributions": ps.contributors.iter().map(|c| json!({"firstName": c.0, "surname": c.1, "affiliation": c.2, "role": c.3})).collect::<Vec<Value>>(),
+    "projectBackground": ps.background,
+    "revisions": ps.revisions.iter().map(|r| json!({"id": r.0, "date": r.1, "description": r.2})).collect::<Vec<Value>>()
+  })
+}
+
+#[test]
+fn test_asset_json() {
+  let mut a = Asset::new("Data","DAT","Information",true);
+  a.tags.push(Tag::new("pii"));
+  a.add_environment("Day");
+  a.update_security_property("Day","confidentiality","High","Personal data");
+  let v = asset_json(&a);
+  assert_eq!(v["theName"],"Data");
+  assert_eq!(v["isCritical"],true);
+  assert_eq!(v["theTags"],json!(["pii"]));
+  assert_eq!(v["theEnvironmentProperties"][0]["theEnvironmentName"],"Day");
+  assert_eq!(v["theEnvironmentProperties"][0]["theProperties"][0],json!({"name": "Confidentiality", "value": "High", "rationale": "Personal data"}));
+}
+
+#[test]
+fn test_environment_json() {
+  let mut env = Environment::new("Complete","CMP");
+  env.environments.add("Day");
+  env.environments.add("Night");
+  env.environments.update_property("Maximise");
+  let v = environment_json(&env);
+  assert_eq!(v["theEnvironments"],json!(["Day","Night"]));
+  assert_eq!(v["theDuplicateProperty"],"Maximise");
+  assert_eq!(environment_json(&Environment::new("Day","DAY"))["theDuplicateProperty"],"None");
+}
+
+#[test]
+fn test_settings_json() {
+  let mut ps = ProjectSettings::new("NeuroGrid");
+  ps.naming_conventions.insert("PII".to_string(),"Personally identifiable information".to_string());
+  ps.contributors.push(("Shamal".to_string(),"Faily".to_string(),"Cardiff Met".to_string(),"Developer".to_string()));
+  let v = settings_json(&ps);
+  assert_eq!(v["projectName"],"NeuroGrid");
+  assert_eq!(v["definitions"][0]["value"],"Personally identifiable information");
+  assert_eq!(v["contributions"][0]["surname"],"Faily");
+}
@@ -0,0 +1,162 @@
+pub mod json;
+
+use serde_json::{json,Value};
+use crate::db::mysql::MySQLDatabaseProxy;
+use crate::dimensions::asset::Asset;
+use crate::dimensions::environment::Environment;
+use crate::dimensions::projectsettings::ProjectSettings;
+use crate::dimensions::valuetype::ValueType;
+use crate::exchange::modeldir::ModelDirectory;
+use crate::server::json::*;
+
+pub trait Store {
+  fn assets(&mut self) -> Vec<Asset>;
+  fn environments(&mut self) -> Vec<Environment>;
+  fn value_types(&mut self, vt_type : &str, env_name : &str) -> Vec<ValueType>;
+  fn settings(&mut self) -> ProjectSettings;
+}
+
+impl Store for MySQLDatabaseProxy {
+  fn assets(&mut self) -> Vec<Asset> {
+    self.get_assets()
+  }
+
+  fn environments(&mut self) -> Vec<Environment> {
+    self.get_environments()
+  }
+
+  fn value_types(&mut self, vt_type : &str, env_name : &str) -> Vec<ValueType> {
+    self.get_value_types(vt_type,env_name)
+  }
+
+  fn settings(&mut self) -> ProjectSettings {
+    self.get_project_settings()
+  }
+}
+
+impl Store for ModelDirectory {
+  fn assets(&mut self) -> Vec<Asset> {
+    self.assets.clone()
+  }
+
+  fn environments(&mut self) -> Vec<Environment> {
+    self.environments.clone()
+  }
+
+  fn value_types(&mut self, vt_type : &str, env_name : &str) -> Vec<ValueType> {
+    self.value_types.iter().filter(|vt| vt.vt_type == vt_type && (env_name == "all" || vt.environment == env_name)).cloned().collect()
+  }
+
+  fn settings(&mut self) -> ProjectSettings {
+    self.settings.clone()
+  }
+}
+
+#[derive(PartialEq,Debug)]
+pub struct ApiResponse {
+  pub status : u16,
+  pub body : String
+}
+
+impl ApiResponse {
+  fn ok(v : Value) -> ApiResponse {
+    ApiResponse{status : 200, body : v.to_string()}
+  }
+
+  fn error(status : u16, msg : &str) -> ApiResponse {
+    ApiResponse{status, body : json!({"code": status, "message": msg}).to_string()}
+  }
+}
+
+fn percent_decode(seg : &str) -> String {
+  let bytes = seg.as_bytes();
+  let mut out = Vec::<u8>::new();
+  let mut idx = 0;
+  while idx < bytes.len() {
+    if bytes[idx] == b'%' && idx + 2 < bytes.len() {
+      if let Some(b) = std::str::from_utf8(&bytes[idx + 1..idx + 3]).ok().and_then(|h| u8::from_str_radix(h,16).ok()) {
+        out.push(b);
+        idx += 3;
+        continue;
+      }
+    }
+    out.push(bytes[idx]);
+    idx += 1;
+  }
+  String::from_utf8_lossy(&out).to_string()
+}
+
+pub fn route(store : &mut dyn Store, method : &str, url : &str) -> ApiResponse {
+  if method != "GET" {
+    return ApiResponse::error(405,&format!("{} is not supported",method));
+  }
+  let path = url.split('?').next().unwrap_or("");
+  let segs = path.trim_matches('/').split('/').map(percent_decode).collect::<Vec<String>>();
+  let segs = segs.iter().map(|s| s.as_str()).collect::<Vec<&str>>();
+  match segs.as_slice() {
+    ["api","assets"] => ApiResponse::ok(json!(store.assets().iter().map(asset_json).collect::<Vec<Value>>())),
+    ["api","assets","name",name] => match store.assets().iter().find(|a| a.name() == *name) {
+      Some(a) => ApiResponse::ok(asset_json(a)),
+      None => ApiResponse::error(404,&format!("Asset {} not found",name))
+    },
+    ["api","environments"] => ApiResponse::ok(json!(store.environments().iter().map(environment_json).collect::<Vec<Value>>())),
+    ["api","environments","name",name] => match store.environments().iter().find(|e| e.name == *name) {
+      Some(e) => ApiResponse::ok(environment_json(e)),
+      None => ApiResponse::error(404,&format!("Environment {} not found",name))
+    },
+    ["api","value_types","type",vt_type,"environment",env_name] => ApiResponse::ok(json!(store.value_types(vt_type,env_name).iter().map(value_type_json).collect::<Vec<Value>>())),
+    ["api","settings"] => ApiResponse::ok(settings_json(&store.settings())),
+    _ => ApiResponse::error(404,&format!("{} not found",path))
+  }
+}
+
+#[cfg(feature = "server")]
+pub fn serve(addr : &str, store : &mut dyn Store) -> std::io::Result<()> {
+  let server = tiny_http::Server::http(addr).map_err(std::io::Error::other)?;
+  let content_type = tiny_http::Header::from_bytes("Content-Type","application/json").unwrap();
+  for req in server.incoming_requests() {
+    let resp = route(store,req.method().as_str(),req.url());
+    req.respond(tiny_http::Response::from_string(resp.body).with_status_code(resp.status).with_header(content_type.clone()))?;
+  }
+  Ok(())
+}
+
+#[test]
+fn test_route() {
+  let mut md = ModelDirectory::new(std::path::Path::new("/tmp/NeuroGrid"));
+  md.environments.push(Environment::new("Day","DAY"));
+  md.environments.push(Environment::new("Night shift","NS"));
+  let mut a = Asset::new("Data","DAT","Information",false);
+  a.add_environment("Day");
+  md.assets.push(a);
+  md.assets.push(Asset::new("C++","CPP","Software",false));
+  let mut vt = ValueType::new("High","Significant harm","severity");
+  vt.environment = "Day".to_string();
+  md.value_types.push(vt);
+  md.value_types.push(ValueType::new("Software","Software threats","threat_type"));
+
+  let resp = route(&mut md,"GET","/api/assets?session_id=test");
+  assert_eq!(resp.status,200);
+  let v : Value = serde_json::from_str(&resp.body).unwrap();
+  assert_eq!(v[0]["theName"],"Data");
+  let v : Value = serde_json::from_str(&route(&mut md,"GET","/api/environments").body).unwrap();
+  assert_eq!(v.as_array().unwrap().len(),2);
+  let v : Value = serde_json::from_str(&route(&mut md,"GET","/api/settings").body).unwrap();
+  assert_eq!(v["projectName"],"NeuroGrid");
+
+  let v : Value = serde_json::from_str(&route(&mut md,"GET","/api/value_types/type/severity/environment/Day").body).unwrap();
+  assert_eq!(v[0]["theName"],"High");
+  let v : Value = serde_json::from_str(&route(&mut md,"GET","/api/value_types/type/threat_type/environment/all").body).unwrap();
+  assert_eq!(v[0]["theName"],"Software");
+  let v : Value = serde_json::from_str(&route(&mut md,"GET","/api/value_types/type/severity/environment/Night").body).unwrap();
+  assert_eq!(v,json!([]));
+
+  let v : Value = serde_json::from_str(&route(&mut md,"GET","/api/environments/name/Night%20shift").body).unwrap();
+  assert_eq!(v["theShortCode"],"NS");
+  let v : Value = serde_json::from_str(&route(&mut md,"GET","/api/assets/name/C++").body).unwrap();
+  assert_eq!(v["theShortCode"],"CPP");
+  assert_eq!(route(&mut md,"GET","/api/assets/name/C%2B%2B").status,200);
+  assert_eq!(route(&mut md,"GET","/api/assets/name/Server").status,404);
+  assert_eq!(route(&mut md,"GET","/api/risks").status,404);
+  assert_eq!(route(&mut md,"DELETE","/api/assets/name/Data").status,405);
+}
@@ -0,0 +1,81 @@
+use crate::dimensions::asset::Asset;
+use crate::dimensions::assetassociation::{AssetModel,Adornment};
+use crate::dimensions::securityproperty::QualitativeValue;
+use crate::visualise::{dot_id,value_colour};
+
+fn arrow(a : &Adornment) -> &'static str {
+  match a {
+    Adornment::Association => "none",
+    Adornment::Aggregation => "odiamond",
+    Adornment::Composition => "diamond",
+    Adornment::Inheritance => "empty"
+  }
+}
+
+fn record_field(txt : &str) -> String {
+  let mut field = String::new();
+  for c in txt.chars() {
+    match c {
+      '\\' | '"' | '{' | '}' | '|' | '<' | '>' => {
+        field.push('\\');
+        field.push(c);
+      },
+      '\n' => field.push_str("\\n"),
+      _ => field.push(c)
+    }
+  }
+  field
+}
+
+pub fn asset_model_dot(am : &AssetModel, assets : &[Asset]) -> String {
+  let mut dot = format!("digraph {} {{\n  graph [rankdir=LR];\n  node [shape=record, style=filled, fontname=\"sans-serif\"];\n  edge [dir=both];\n",dot_id(&format!("Asset model: {}",am.environment)));
+  for a_name in &am.assets {
+    let aep = assets.iter().find(|a| a.name() == a_name).and_then(|a| a.environment_properties.get(&am.environment));
+    match aep {
+      Some(aep) => {
+        let props = aep.properties().iter().filter(|p| p.value != QualitativeValue::None).map(|p| format!("{:?}: {:?}",p.name,p.value)).collect::<Vec<String>>().join("\n");
+        let highest = aep.properties().iter().map(|p| p.value.clone()).fold(QualitativeValue::None,|m,v| if v > m { v } else { m });
+        dot.push_str(format!("  {} [label=\"{{{}|{}}}\", fillcolor=\"{}\"];\n",dot_id(a_name),record_field(a_name),record_field(&props),value_colour(&highest)).as_str());
+      },
+      None => dot.push_str(format!("  {} [fillcolor=\"white\"];\n",dot_id(a_name)).as_str())
+    }
+  }
+  for aa in &am.associations {
+    dot.push_str(format!("  {} -> {} [arrowtail={}, arrowhead={}, taillabel={}, headlabel={}];\n",
+      dot_id(&aa.head_asset),dot_id(&aa.tail_asset),
+      arrow(&aa.head_adornment),arrow(&aa.tail_adornment),
+      dot_id(format!("{} {}",aa.head_multiplicity,aa.head_role).trim()),
+      dot_id(format!("{} {}",aa.tail_multiplicity,aa.tail_role).trim())).as_str());
+  }
+  dot.push_str("}\n");
+  dot
+}
+
+#[test]
+fn test_asset_model_dot() {
+  use crate::dimensions::assetassociation::AssetAssociation;
+
+  let mut data = Asset::new("Data","DAT","Information",false);
+  data.add_environment("Day");
+  data.update_security_property("Day","confidentiality","High","TBC");
+  data.update_security_property("Day","integrity","Low","TBC");
+  let mut server_data = AssetAssociation::new("Day","Server","Data");
+  server_data.head_adornment = Adornment::Composition;
+  server_data.tail_multiplicity = "*".to_string();
+  let mut logs = Asset::new("Logs|Audit","LOG","Information",false);
+  logs.add_environment("Day");
+  logs.update_security_property("Day","integrity","Medium","TBC");
+  let mut keys = Asset::new("<Keys>","KEY","Information",false);
+  keys.add_environment("Day");
+  keys.update_security_property("Day","confidentiality","High","TBC");
+  let am = AssetModel::new("Day",&[server_data,AssetAssociation::new("Day","Logs|Audit","<Keys>")]);
+
+  let dot = asset_model_dot(&am,&[data,logs,keys]);
+  assert!(dot.starts_with("digraph \"Asset model: Day\" {\n"));
+  assert!(dot.contains("  \"Server\" [fillcolor=\"white\"];\n"));
+  assert!(dot.contains("  \"Data\" [label=\"{Data|Confidentiality: High\\nIntegrity: Low}\", fillcolor=\"#d73027\"];\n"));
+  assert!(dot.contains("  \"Logs|Audit\" [label=\"{Logs\\|Audit|Integrity: Medium}\", fillcolor=\"#fc8d59\"];\n"));
+  assert!(dot.contains("  \"<Keys>\" [label=\"{\\<Keys\\>|Confidentiality: High}\", fillcolor=\"#d73027\"];\n"));
+  assert!(dot.contains("  \"Server\" -> \"Data\" [arrowtail=diamond, arrowhead=none, taillabel=\"1\", headlabel=\"*\"];\n"));
+  assert!(dot.ends_with("}\n"));
+}
@@ -0,0 +1,36 @@
+use crate::dimensions::environment::{Environment,CompositeProperty};
+use crate::visualise::dot_id;
+
+pub fn environment_model_dot(envs : &[Environment]) -> String {
+  let mut dot = "digraph \"Environment model\" {\n  node [fontname=\"sans-serif\"];\n".to_string();
+  for env in envs {
+    let shape = if env.environments.is_empty() { "ellipse" } else { "doubleoctagon" };
+    dot.push_str(format!("  {} [shape={}, label={}];\n",dot_id(&env.name),shape,dot_id(&format!("{} ({})",env.name,env.short_code))).as_str());
+  }
+  for env in envs {
+    for member in &env.environments.environments {
+      let overrides = env.environments.property == CompositeProperty::Override && env.environments.overriding_environment_name == *member;
+      let label = if overrides { "override" } else { "" };
+      dot.push_str(format!("  {} -> {} [label=\"{}\"{}];\n",dot_id(&env.name),dot_id(member),label,if overrides { ", style=bold" } else { "" }).as_str());
+    }
+  }
+  dot.push_str("}\n");
+  dot
+}
+
+#[test]
+fn test_environment_model_dot() {
+  let day = Environment::new("Day","DAY");
+  let night = Environment::new("Night","NGT");
+  let mut all = Environment::new("All day","ALL");
+  all.environments.add("Day");
+  all.environments.add("Night");
+  all.environments.update_property("Override");
+  all.environments.overriding_environment_name = "Night".to_string();
+
+  let dot = environment_model_dot(&[day,night,all]);
+  assert!(dot.contains("  \"Day\" [shape=ellipse, label=\"Day (DAY)\"];\n"));
+  assert!(dot.contains("  \"All day\" [shape=doubleoctagon, label=\"All day (ALL)\"];\n"));
+  assert!(dot.contains("  \"All day\" -> \"Day\" [label=\"\"];\n"));
+  assert!(dot.contains("  \"All day\" -> \"Night\" [label=\"override\", style=bold];\n"));
+}
@@ -0,0 +1,34 @@
+use crate::dimensions::securityproperty::QualitativeValue;
+use crate::dimensions::vulnerability::Severity;
+
+pub mod assetmodel;
+pub mod environmentmodel;
+pub mod riskmodel;
+
+pub fn dot_id(txt : &str) -> String {
+  format!("\"{}\"",txt.replace('\\',"\\\\").replace('"',"\\\"").replace('\n',"\\n"))
+}
+
+pub fn value_colour(v : &QualitativeValue) -> &'static str {
+  match v {
+    QualitativeValue::None => "white",
+    QualitativeValue::Low => "#fee08b",
+    QualitativeValue::Medium => "#fc8d59",
+    QualitativeValue::High => "#d73027"
+  }
+}
+
+pub fn severity_colour(s : &Severity) -> &'static str {
+  match s {
+    Severity::Negligible => "#fee08b",
+    Severity::Marginal => "#fdae61",
+    Severity::Critical => "#f46d43",
+    Severity::Catastrophic => "#a50026"
+  }
+}
+
+#[test]
+fn test_dot_id() {
+  assert_eq!(dot_id("Server"),"\"Server\"".to_string());
+  assert_eq!(dot_id("A \"quoted\" \\ name"),"\"A \\\"quoted\\\" \\\\ name\"".to_string());
+}
@@ -0,0 +1,90 @@
+use crate::dimensions::asset::Asset;
+use crate::dimensions::attacker::Attacker;
+use crate::dimensions::risk::Risk;
+use crate::dimensions::securityproperty::QualitativeValue;
+use crate::dimensions::threat::Threat;
+use crate::dimensions::vulnerability::Vulnerability;
+use crate::visualise::{dot_id,value_colour,severity_colour};
+
+pub fn risk_model_dot(env_name : &str, attackers : &[Attacker], threats : &[Threat], vuls : &[Vulnerability], assets : &[Asset], risks : &[Risk]) -> String {
+  let mut dot = format!("digraph {} {{\n  node [style=filled, fontname=\"sans-serif\"];\n",dot_id(&format!("Risk analysis: {}",env_name)));
+  let mut edges = Vec::<String>::new();
+
+  for a in attackers.iter().filter(|a| a.environments.iter().any(|ae| ae.name() == env_name)) {
+    dot.push_str(format!("  {} [shape=box, style=\"filled,rounded\", fillcolor=\"#d9d9d9\"];\n",dot_id(a.name())).as_str());
+  }
+  for t in threats {
+    if let Some(te) = t.environments.iter().find(|te| te.name() == env_name) {
+      dot.push_str(format!("  {} [shape=diamond, fillcolor=\"#fdd0a2\", tooltip=\"{}\"];\n",dot_id(t.name()),te.likelihood()).as_str());
+      for a in &te.attackers {
+        edges.push(format!("  {} -> {};\n",dot_id(a),dot_id(t.name())));
+      }
+      for a in &te.assets {
+        edges.push(format!("  {} -> {};\n",dot_id(t.name()),dot_id(a)));
+      }
+    }
+  }
+  for v in vuls {
+    if let Some(ve) = v.environments.iter().find(|ve| ve.name() == env_name) {
+      dot.push_str(format!("  {} [shape=triangle, fillcolor=\"{}\", tooltip=\"{}\"];\n",dot_id(v.name()),severity_colour(ve.severity()),ve.severity()).as_str());
+      for a in &ve.assets {
+        edges.push(format!("  {} -> {};\n",dot_id(v.name()),dot_id(a)));
+      }
+    }
+  }
+  for a in assets {
+    if let Some(aep) = a.environment_properties.get(env_name) {
+      let highest = aep.properties().iter().map(|p| p.value.clone()).fold(QualitativeValue::None,|m,v| if v > m { v } else { m });
+      dot.push_str(format!("  {} [shape=box, fillcolor=\"{}\"];\n",dot_id(a.name()),value_colour(&highest)).as_str());
+    }
+  }
+  for r in risks {
+    let in_env = threats.iter().any(|t| t.name() == r.threat && t.environments.iter().any(|te| te.name() == env_name)) &&
+                 vuls.iter().any(|v| v.name() == r.vulnerability && v.environments.iter().any(|ve| ve.name() == env_name));
+    if in_env {
+      dot.push_str(format!("  {} [shape=octagon, fillcolor=\"#fb6a4a\"];\n",dot_id(r.name())).as_str());
+      edges.push(format!("  {} -> {};\n",dot_id(r.name()),dot_id(&r.threat)));
+      edges.push(format!("  {} -> {};\n",dot_id(r.name()),dot_id(&r.vulnerability)));
+    }
+  }
+  for e in edges {
+    dot.push_str(&e);
+  }
+  dot.push_str("}\n");
+  dot
+}
+
+#[test]
+fn test_risk_model_dot() {
+  use crate::dimensions::attacker::AttackerEnvironment;
+  use crate::dimensions::threat::ThreatEnvironment;
+  use crate::dimensions::vulnerability::VulnerabilityEnvironment;
+
+  let mut peppa = Attacker::new("Peppa","");
+  peppa.environments.push(AttackerEnvironment::new("Day"));
+  let mut sqli = Threat::new("SQL injection","Software");
+  let mut te = ThreatEnvironment::new("Day","Probable");
+  te.attackers.push("Peppa".to_string());
+  te.assets.push("Data".to_string());
+  sqli.environments.push(te);
+  let mut unval = Vulnerability::new("Unvalidated input","Implementation");
+  let mut ve = VulnerabilityEnvironment::new("Day","Catastrophic");
+  ve.assets.push("Data".to_string());
+  unval.environments.push(ve);
+  let mut data = Asset::new("Data","DAT","Information",false);
+  data.add_environment("Day");
+  data.update_security_property("Day","integrity","Medium","TBC");
+  let risks = [Risk::new("Upload risk","SQL injection","Unvalidated input"),Risk::new("Other risk","Phishing","Unvalidated input")];
+
+  let dot = risk_model_dot("Day",&[peppa],&[sqli],&[unval],&[data],&risks);
+  assert!(dot.starts_with("digraph \"Risk analysis: Day\" {\n"));
+  assert!(dot.contains("  \"Peppa\" [shape=box, style=\"filled,rounded\", fillcolor=\"#d9d9d9\"];\n"));
+  assert!(dot.contains("  \"Unvalidated input\" [shape=triangle, fillcolor=\"#a50026\", tooltip=\"Catastrophic\"];\n"));
+  assert!(dot.contains("  \"Data\" [shape=box, fillcolor=\"#fc8d59\"];\n"));
+  assert!(dot.contains("  \"Peppa\" -> \"SQL injection\";\n"));
+  assert!(dot.contains("  \"SQL injection\" -> \"Data\";\n"));
+  assert!(dot.contains("  \"Unvalidated input\" -> \"Data\";\n"));
+  assert!(dot.contains("  \"Upload risk\" -> \"SQL injection\";\n"));
+  assert!(dot.contains("  \"Upload risk\" -> \"Unvalidated input\";\n"));
+  assert!(!dot.contains("Other risk"));
+}